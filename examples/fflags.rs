@@ -15,19 +15,25 @@
 
 use std::os::raw::c_int;
 
-use luau::fvalue::FValue;
+use luau::fvalue::{self, FValue};
 use luau::vm::Luau;
 
 fn main() {
 	// the Luau compiler initializes a lot of the more interesting FFlags
 	{ let _ = Luau::compile(""); }
 
+	let exclusive = fvalue::exclusive();
+	let defaults = fvalue::snapshot();
+
 	for mut fflag in FValue::<bool>::list() {
-		fflag.set(true);
+		fflag.set(&exclusive, true);
 		println!("{}: {}", fflag.name(), fflag.value())
 	}
 
 	for fint in FValue::<c_int>::list() {
 		println!("{}: {}", fint.name(), fint.value())
 	}
+
+	// enabling every flag at once is only good for seeing what exists
+	fvalue::restore(&exclusive, &defaults);
 }
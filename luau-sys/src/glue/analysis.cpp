@@ -12,3 +12,499 @@
 //
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#include "analysis.h"
+#include "common.hpp"
+#include "ast.hpp"
+
+#include "string.h" // NOLINT(modernize-deprecated-headers)
+
+#include <algorithm>
+#include <optional>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include <Luau/AstQuery.h>
+#include <Luau/Autocomplete.h>
+#include <Luau/BuiltinDefinitions.h>
+#include <Luau/Config.h>
+#include <Luau/Error.h>
+#include <Luau/FileResolver.h>
+#include <Luau/Frontend.h>
+#include <Luau/Linter.h>
+#include <Luau/ToString.h>
+
+static gluau_Buffer gluauT_strview(const std::string &string) {
+	return {
+		.data = const_cast<char*>(string.data()),
+		.len = string.size()
+	};
+}
+
+struct gluauT_FileResolver : Luau::FileResolver {
+	gluau_FileResolver inner;
+
+	explicit gluauT_FileResolver(gluau_FileResolver inner) : inner(inner) {}
+
+	std::optional<Luau::SourceCode> readSource(const Luau::ModuleName &name) override {
+		gluau_Buffer source {nullptr, 0};
+		uint8_t type = Luau::SourceCode::Module;
+
+		if (!inner.read_source(inner.data, gluauT_strview(name), &source, &type)) {
+			return std::nullopt;
+		}
+
+		return Luau::SourceCode {
+			.source = std::string(source.data, source.len),
+			.type = static_cast<Luau::SourceCode::Type>(type)
+		};
+	}
+
+	std::optional<Luau::ModuleInfo> resolveModule(const Luau::ModuleInfo* context, Luau::AstExpr* expr) override {
+		if (!context) {
+			return std::nullopt;
+		}
+
+		std::vector<gluau_Buffer> parts;
+		bool string = false;
+
+		if (auto* literal = expr->as<Luau::AstExprConstantString>()) {
+			parts.push_back({literal->value.data, literal->value.size});
+			string = true;
+		} else {
+			// walk down the path, collecting names from the end
+			while (auto* index = expr->as<Luau::AstExprIndexName>()) {
+				parts.push_back({const_cast<char*>(index->index.value), strlen(index->index.value)});
+				expr = index->expr;
+			}
+
+			if (auto* global = expr->as<Luau::AstExprGlobal>()) {
+				parts.push_back({const_cast<char*>(global->name.value), strlen(global->name.value)});
+			} else if (auto* local = expr->as<Luau::AstExprLocal>()) {
+				parts.push_back({const_cast<char*>(local->local->name.value), strlen(local->local->name.value)});
+			} else {
+				return std::nullopt;
+			}
+
+			std::reverse(parts.begin(), parts.end());
+		}
+
+		gluau_RequireExpr require {
+			.string = string,
+			.parts = parts.data(),
+			.len = parts.size()
+		};
+
+		gluau_Buffer name {nullptr, 0};
+
+		if (!inner.resolve_module(inner.data, gluauT_strview(context->name), require, &name)) {
+			return std::nullopt;
+		}
+
+		return Luau::ModuleInfo {
+			.name = std::string(name.data, name.len)
+		};
+	}
+};
+
+struct gluau_Frontend {
+	gluauT_FileResolver fileResolver;
+	Luau::NullConfigResolver configResolver;
+	Luau::Frontend frontend;
+
+	gluau_Frontend(gluau_FileResolver resolver, Luau::Mode mode) : fileResolver(resolver), frontend(&fileResolver, &configResolver) {
+		configResolver.defaultConfig.mode = mode;
+		// queries like type_at need the types of every expression
+		frontend.options.retainFullTypeGraphs = true;
+
+		Luau::registerBuiltinGlobals(frontend, frontend.globals);
+		Luau::freeze(frontend.globals.globalTypes);
+
+		Luau::registerBuiltinGlobals(frontend, frontend.globalsForAutocomplete, true);
+		Luau::freeze(frontend.globalsForAutocomplete.globalTypes);
+	}
+};
+
+#define GLUAUT_TYPE_ERRORS(X) \
+	X(TypeMismatch, TYPE_ERROR_TYPE_MISMATCH) \
+	X(UnknownSymbol, TYPE_ERROR_UNKNOWN_SYMBOL) \
+	X(UnknownProperty, TYPE_ERROR_UNKNOWN_PROPERTY) \
+	X(NotATable, TYPE_ERROR_NOT_A_TABLE) \
+	X(CannotExtendTable, TYPE_ERROR_CANNOT_EXTEND_TABLE) \
+	X(OnlyTablesCanHaveMethods, TYPE_ERROR_ONLY_TABLES_CAN_HAVE_METHODS) \
+	X(DuplicateTypeDefinition, TYPE_ERROR_DUPLICATE_TYPE_DEFINITION) \
+	X(CountMismatch, TYPE_ERROR_COUNT_MISMATCH) \
+	X(FunctionDoesNotTakeSelf, TYPE_ERROR_FUNCTION_DOES_NOT_TAKE_SELF) \
+	X(FunctionRequiresSelf, TYPE_ERROR_FUNCTION_REQUIRES_SELF) \
+	X(OccursCheckFailed, TYPE_ERROR_OCCURS_CHECK_FAILED) \
+	X(UnknownRequire, TYPE_ERROR_UNKNOWN_REQUIRE) \
+	X(IncorrectGenericParameterCount, TYPE_ERROR_INCORRECT_GENERIC_PARAMETER_COUNT) \
+	X(SyntaxError, TYPE_ERROR_SYNTAX_ERROR) \
+	X(CodeTooComplex, TYPE_ERROR_CODE_TOO_COMPLEX) \
+	X(UnificationTooComplex, TYPE_ERROR_UNIFICATION_TOO_COMPLEX) \
+	X(UnknownPropButFoundLikeProp, TYPE_ERROR_UNKNOWN_PROP_BUT_FOUND_LIKE_PROP) \
+	X(GenericError, TYPE_ERROR_GENERIC_ERROR) \
+	X(InternalError, TYPE_ERROR_INTERNAL_ERROR) \
+	X(CannotCallNonFunction, TYPE_ERROR_CANNOT_CALL_NON_FUNCTION) \
+	X(ExtraInformation, TYPE_ERROR_EXTRA_INFORMATION) \
+	X(DeprecatedApiUsed, TYPE_ERROR_DEPRECATED_API_USED) \
+	X(ModuleHasCyclicDependency, TYPE_ERROR_MODULE_HAS_CYCLIC_DEPENDENCY) \
+	X(IllegalRequire, TYPE_ERROR_ILLEGAL_REQUIRE) \
+	X(FunctionExitsWithoutReturning, TYPE_ERROR_FUNCTION_EXITS_WITHOUT_RETURNING) \
+	X(DuplicateGenericParameter, TYPE_ERROR_DUPLICATE_GENERIC_PARAMETER) \
+	X(CannotInferBinaryOperation, TYPE_ERROR_CANNOT_INFER_BINARY_OPERATION) \
+	X(MissingProperties, TYPE_ERROR_MISSING_PROPERTIES) \
+	X(SwappedGenericTypeParameter, TYPE_ERROR_SWAPPED_GENERIC_TYPE_PARAMETER) \
+	X(OptionalValueAccess, TYPE_ERROR_OPTIONAL_VALUE_ACCESS) \
+	X(MissingUnionProperty, TYPE_ERROR_MISSING_UNION_PROPERTY) \
+	X(TypesAreUnrelated, TYPE_ERROR_TYPES_ARE_UNRELATED) \
+	X(NormalizationTooComplex, TYPE_ERROR_NORMALIZATION_TOO_COMPLEX) \
+	X(TypePackMismatch, TYPE_ERROR_TYPE_PACK_MISMATCH)
+
+static gluau_TypeErrorKind gluauT_errorkind(const Luau::TypeErrorData &data) {
+#define X(type, kind) if (Luau::get_if<Luau::type>(&data)) return kind;
+	GLUAUT_TYPE_ERRORS(X)
+#undef X
+
+	return TYPE_ERROR_OTHER;
+}
+
+static gluau_TypeErrors gluauT_errors(const std::vector<Luau::TypeError> &errors) {
+	auto len = errors.size();
+	auto* converted = new gluau_TypeError[len];
+
+	unsigned int i = 0;
+	for (const auto &error: errors) {
+		converted[i++] = {
+			.kind = gluauT_errorkind(error.data),
+			.message = gluauU_strtobuf(Luau::toString(error)),
+			.module = gluauU_strtobuf(error.moduleName),
+			.span = gluauA_loctospan(error.location)
+		};
+	}
+
+	return {
+		.errors = converted,
+		.len = len
+	};
+}
+
+static gluau_LintWarnings gluauT_lints(const std::vector<Luau::LintWarning> &warnings) {
+	auto len = warnings.size();
+	auto* converted = new gluau_LintWarning[len];
+
+	unsigned int i = 0;
+	for (const auto &warning: warnings) {
+		converted[i++] = {
+			.code = static_cast<uint8_t>(warning.code),
+			.message = gluauU_strtobuf(warning.text),
+			.span = gluauA_loctospan(warning.location)
+		};
+	}
+
+	return {
+		.warnings = converted,
+		.len = len
+	};
+}
+
+template<typename Iterator>
+static gluau_ModuleNames gluauT_names(Iterator begin, Iterator end, size_t len) {
+	auto* names = new gluau_Buffer[len];
+
+	unsigned int i = 0;
+	for (auto it = begin; it != end; ++it) {
+		names[i++] = gluauU_strtobuf(*it);
+	}
+
+	return {
+		.names = names,
+		.len = len
+	};
+}
+
+static gluau_Buffer gluauT_optbuf(const std::optional<std::string> &string) {
+	return string ? gluauU_strtobuf(*string) : gluau_Buffer {nullptr, 0};
+}
+
+static gluau_CompletionKind gluauT_completionkind(const Luau::AutocompleteEntry &entry) {
+	bool function = entry.type && Luau::get<Luau::FunctionType>(Luau::follow(*entry.type));
+
+	switch (entry.kind) {
+		case Luau::AutocompleteEntryKind::Property: return function ? COMPLETION_METHOD : COMPLETION_PROPERTY;
+		case Luau::AutocompleteEntryKind::Binding: return function ? COMPLETION_FUNCTION : COMPLETION_VARIABLE;
+		case Luau::AutocompleteEntryKind::Keyword: return COMPLETION_KEYWORD;
+		case Luau::AutocompleteEntryKind::String: return COMPLETION_STRING;
+		case Luau::AutocompleteEntryKind::Type: return COMPLETION_TYPE;
+		case Luau::AutocompleteEntryKind::Module: return COMPLETION_MODULE;
+		case Luau::AutocompleteEntryKind::GeneratedFunction: return COMPLETION_GENERATED_FUNCTION;
+		case Luau::AutocompleteEntryKind::RequirePath: return COMPLETION_REQUIRE_PATH;
+	}
+
+	return COMPLETION_VARIABLE;
+}
+
+static gluau_Completions gluauT_completions(const Luau::AutocompleteEntryMap &entries) {
+	auto len = entries.size();
+	auto* converted = new gluau_Completion[len];
+
+	unsigned int i = 0;
+	for (const auto &[label, entry]: entries) {
+		converted[i++] = {
+			.kind = gluauT_completionkind(entry),
+			.label = gluauU_strtobuf(label),
+			.type = entry.type ? gluauU_strtobuf(Luau::toString(*entry.type)) : gluau_Buffer {nullptr, 0},
+			.documentation_symbol = gluauT_optbuf(entry.documentationSymbol),
+			.insert_text = gluauT_optbuf(entry.insertText),
+			.deprecated = entry.deprecated
+		};
+	}
+
+	return {
+		.completions = converted,
+		.len = len
+	};
+}
+
+GLUE_API struct gluau_Frontend* gluau_frontend_new(struct gluau_FileResolver resolver, uint8_t mode) {
+	return new gluau_Frontend(resolver, static_cast<Luau::Mode>(mode));
+}
+
+GLUE_API void gluau_frontend_set_mode(struct gluau_Frontend* frontend, uint8_t mode) {
+	frontend->configResolver.defaultConfig.mode = static_cast<Luau::Mode>(mode);
+	// every module may have been checked in the old mode
+	frontend->frontend.clear();
+}
+
+GLUE_API void gluau_frontend_set_globals(struct gluau_Frontend* frontend, const struct gluau_Buffer* globals, size_t len) {
+	std::vector<std::string> &configGlobals = frontend->configResolver.defaultConfig.globals;
+	configGlobals.clear();
+
+	for (size_t i = 0; i < len; i++) {
+		configGlobals.emplace_back(globals[i].data, globals[i].len);
+	}
+
+	frontend->frontend.clear();
+}
+
+GLUE_API struct gluau_ModuleNames gluau_frontend_mark_dirty(struct gluau_Frontend* frontend, struct gluau_Buffer name) {
+	std::vector<Luau::ModuleName> marked;
+	frontend->frontend.markDirty(std::string(name.data, name.len), &marked);
+	return gluauT_names(marked.begin(), marked.end(), marked.size());
+}
+
+GLUE_API struct gluau_ModuleGraph gluau_frontend_module_graph(struct gluau_Frontend* frontend) {
+	auto &sourceNodes = frontend->frontend.sourceNodes;
+	auto len = sourceNodes.size();
+	auto* nodes = new gluau_ModuleNode[len];
+
+	unsigned int i = 0;
+	for (const auto &[name, node]: sourceNodes) {
+		nodes[i++] = {
+			.name = gluauU_strtobuf(name),
+			.required = gluauT_names(node->requireSet.begin(), node->requireSet.end(), node->requireSet.size())
+		};
+	}
+
+	return {
+		.nodes = nodes,
+		.len = len
+	};
+}
+
+GLUE_API struct gluau_TypeErrors gluau_frontend_check(struct gluau_Frontend* frontend, struct gluau_Buffer name) {
+	std::string module(name.data, name.len);
+
+	try {
+		return gluauT_errors(frontend->frontend.check(module).errors);
+	} catch (std::exception &error) {
+		return gluauT_errors({Luau::TypeError(Luau::Location(), module, Luau::InternalError {error.what()})});
+	}
+}
+
+GLUE_API struct gluau_LintWarnings gluau_frontend_lint(struct gluau_Frontend* frontend, struct gluau_Buffer name, uint64_t mask) {
+	std::string module(name.data, name.len);
+
+	try {
+		frontend->frontend.check(module);
+
+		const Luau::SourceModule* source = frontend->frontend.getSourceModule(module);
+
+		if (!source || !source->root) {
+			return gluauT_lints({});
+		}
+
+		Luau::ModulePtr checked = frontend->frontend.moduleResolver.getModule(module);
+
+		Luau::LintOptions options;
+		options.warningMask = mask & ~Luau::LintWarning::parseMask(source->hotcomments);
+
+		return gluauT_lints(Luau::lint(source->root, *source->names, frontend->frontend.globals.globalScope, checked.get(), source->hotcomments, options));
+	} catch (std::exception &error) {
+		return gluauT_lints({Luau::LintWarning {
+			.code = Luau::LintWarning::Code_Unknown,
+			.location = Luau::Location(),
+			.text = std::string("internal error: ") + error.what()
+		}});
+	}
+}
+
+GLUE_API struct gluau_Completions gluau_frontend_autocomplete(struct gluau_Frontend* frontend, struct gluau_Buffer name, unsigned int line, unsigned int column) {
+	std::string module(name.data, name.len);
+
+	try {
+		Luau::FrontendOptions options;
+		options.retainFullTypeGraphs = true;
+		options.forAutocomplete = true;
+
+		frontend->frontend.check(module, options);
+
+		Luau::AutocompleteResult result = Luau::autocomplete(frontend->frontend, module, Luau::Position(line, column), [](const std::string &, std::optional<const Luau::ClassType*>, std::optional<std::string>) -> std::optional<Luau::AutocompleteEntryMap> {
+			return std::nullopt;
+		});
+
+		return gluauT_completions(result.entryMap);
+	} catch (std::exception &) {
+		return gluauT_completions({});
+	}
+}
+
+// Checks the module if it isn't already, and then returns it along with its
+// source, if both exist.
+static std::optional<std::pair<const Luau::SourceModule*, Luau::ModulePtr>> gluauT_checked(gluau_Frontend* frontend, const std::string &module) {
+	frontend->frontend.check(module);
+
+	const Luau::SourceModule* source = frontend->frontend.getSourceModule(module);
+	Luau::ModulePtr checked = frontend->frontend.moduleResolver.getModule(module);
+
+	if (!source || !source->root || !checked) {
+		return std::nullopt;
+	}
+
+	return std::make_pair(source, checked);
+}
+
+GLUE_API struct gluau_Buffer gluau_frontend_type_at(struct gluau_Frontend* frontend, struct gluau_Buffer name, unsigned int line, unsigned int column) {
+	std::string module(name.data, name.len);
+
+	try {
+		auto checked = gluauT_checked(frontend, module);
+
+		if (!checked) {
+			return {nullptr, 0};
+		}
+
+		std::optional<Luau::TypeId> type = Luau::findTypeAtPosition(*checked->second, *checked->first, Luau::Position(line, column));
+		return type ? gluauU_strtobuf(Luau::toString(*type)) : gluau_Buffer {nullptr, 0};
+	} catch (std::exception &) {
+		return {nullptr, 0};
+	}
+}
+
+GLUE_API bool gluau_frontend_definition_of(struct gluau_Frontend* frontend, struct gluau_Buffer name, unsigned int line, unsigned int column, struct gluau_Buffer* module, struct gluau_Span* span) {
+	std::string moduleName(name.data, name.len);
+	Luau::Position position(line, column);
+
+	try {
+		auto checked = gluauT_checked(frontend, moduleName);
+
+		if (!checked) {
+			return false;
+		}
+
+		auto [source, checkedModule] = *checked;
+
+		// locals and globals defined in this module
+		if (std::optional<Luau::Binding> binding = Luau::findBindingAtPosition(*checkedModule, *source, position)) {
+			// builtin globals have no location
+			if (binding->location == Luau::Location()) {
+				return false;
+			}
+
+			*module = gluauU_strtobuf(moduleName);
+			*span = gluauA_loctospan(binding->location);
+			return true;
+		}
+
+		// properties of tables and classes, which may be defined in other modules
+		Luau::AstExpr* expr = Luau::findExprAtPosition(*source, position);
+		auto* index = expr ? expr->as<Luau::AstExprIndexName>() : nullptr;
+
+		if (!index) {
+			return false;
+		}
+
+		Luau::TypeId* parent = checkedModule->astTypes.find(index->expr);
+
+		if (!parent) {
+			return false;
+		}
+
+		Luau::TypeId type = Luau::follow(*parent);
+		const Luau::Property* property = nullptr;
+		std::string definitionModule;
+
+		if (auto* table = Luau::get<Luau::TableType>(type)) {
+			auto found = table->props.find(index->index.value);
+			property = found == table->props.end() ? nullptr : &found->second;
+			definitionModule = table->definitionModuleName;
+		} else if (auto* clazz = Luau::get<Luau::ClassType>(type)) {
+			property = Luau::lookupClassProp(clazz, index->index.value);
+			definitionModule = clazz->definitionModuleName;
+		}
+
+		if (!property || !property->location) {
+			return false;
+		}
+
+		*module = gluauU_strtobuf(definitionModule.empty() ? moduleName : definitionModule);
+		*span = gluauA_loctospan(*property->location);
+		return true;
+	} catch (std::exception &) {
+		return false;
+	}
+}
+
+GLUE_API struct gluau_TypeErrors gluau_frontend_load_definitions(struct gluau_Frontend* frontend, struct gluau_Buffer name, struct gluau_Buffer source) {
+	std::string package(name.data, name.len);
+
+	try {
+		Luau::GlobalTypes &globals = frontend->frontend.globals;
+
+		Luau::unfreeze(globals.globalTypes);
+		Luau::LoadDefinitionFileResult result = frontend->frontend.loadDefinitionFile(globals, globals.globalScope, std::string_view(source.data, source.len), package, false);
+		Luau::freeze(globals.globalTypes);
+
+		if (result.success) {
+			// autocomplete checks modules against its own copy of the globals
+			Luau::GlobalTypes &autocomplete = frontend->frontend.globalsForAutocomplete;
+
+			Luau::unfreeze(autocomplete.globalTypes);
+			frontend->frontend.loadDefinitionFile(autocomplete, autocomplete.globalScope, std::string_view(source.data, source.len), package, false, true);
+			Luau::freeze(autocomplete.globalTypes);
+
+			// modules checked before may refer to the new globals
+			frontend->frontend.clear();
+			return gluauT_errors({});
+		}
+
+		std::vector<Luau::TypeError> errors;
+
+		for (const auto &error: result.parseResult.errors) {
+			errors.emplace_back(error.getLocation(), package, Luau::SyntaxError {error.getMessage()});
+		}
+
+		if (result.module) {
+			errors.insert(errors.end(), result.module->errors.begin(), result.module->errors.end());
+		}
+
+		return gluauT_errors(errors);
+	} catch (std::exception &error) {
+		return gluauT_errors({Luau::TypeError(Luau::Location(), package, Luau::InternalError {error.what()})});
+	}
+}
+
+GLUE_API void gluau_frontend_free(struct gluau_Frontend* frontend) {
+	delete frontend;
+}
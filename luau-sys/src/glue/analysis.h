@@ -15,3 +15,165 @@
 
 #pragma once
 
+#include "common.h"
+#include "ast.h"
+
+#include "stdbool.h" // NOLINT(modernize-deprecated-headers)
+
+enum gluau_TypeErrorKind : uint8_t {
+	TYPE_ERROR_TYPE_MISMATCH,
+	TYPE_ERROR_UNKNOWN_SYMBOL,
+	TYPE_ERROR_UNKNOWN_PROPERTY,
+	TYPE_ERROR_NOT_A_TABLE,
+	TYPE_ERROR_CANNOT_EXTEND_TABLE,
+	TYPE_ERROR_ONLY_TABLES_CAN_HAVE_METHODS,
+	TYPE_ERROR_DUPLICATE_TYPE_DEFINITION,
+	TYPE_ERROR_COUNT_MISMATCH,
+	TYPE_ERROR_FUNCTION_DOES_NOT_TAKE_SELF,
+	TYPE_ERROR_FUNCTION_REQUIRES_SELF,
+	TYPE_ERROR_OCCURS_CHECK_FAILED,
+	TYPE_ERROR_UNKNOWN_REQUIRE,
+	TYPE_ERROR_INCORRECT_GENERIC_PARAMETER_COUNT,
+	TYPE_ERROR_SYNTAX_ERROR,
+	TYPE_ERROR_CODE_TOO_COMPLEX,
+	TYPE_ERROR_UNIFICATION_TOO_COMPLEX,
+	TYPE_ERROR_UNKNOWN_PROP_BUT_FOUND_LIKE_PROP,
+	TYPE_ERROR_GENERIC_ERROR,
+	TYPE_ERROR_INTERNAL_ERROR,
+	TYPE_ERROR_CANNOT_CALL_NON_FUNCTION,
+	TYPE_ERROR_EXTRA_INFORMATION,
+	TYPE_ERROR_DEPRECATED_API_USED,
+	TYPE_ERROR_MODULE_HAS_CYCLIC_DEPENDENCY,
+	TYPE_ERROR_ILLEGAL_REQUIRE,
+	TYPE_ERROR_FUNCTION_EXITS_WITHOUT_RETURNING,
+	TYPE_ERROR_DUPLICATE_GENERIC_PARAMETER,
+	TYPE_ERROR_CANNOT_INFER_BINARY_OPERATION,
+	TYPE_ERROR_MISSING_PROPERTIES,
+	TYPE_ERROR_SWAPPED_GENERIC_TYPE_PARAMETER,
+	TYPE_ERROR_OPTIONAL_VALUE_ACCESS,
+	TYPE_ERROR_MISSING_UNION_PROPERTY,
+	TYPE_ERROR_TYPES_ARE_UNRELATED,
+	TYPE_ERROR_NORMALIZATION_TOO_COMPLEX,
+	TYPE_ERROR_TYPE_PACK_MISMATCH,
+	// any error added to Luau after this glue was written
+	TYPE_ERROR_OTHER
+};
+
+struct gluau_TypeError {
+	enum gluau_TypeErrorKind kind;
+	struct gluau_Buffer message;
+	struct gluau_Buffer module;
+	struct gluau_Span span;
+};
+
+struct gluau_TypeErrors {
+	struct gluau_TypeError* errors;
+	size_t len;
+};
+
+struct gluau_LintWarning {
+	// numbered like Luau::LintWarning::Code
+	uint8_t code;
+	struct gluau_Buffer message;
+	struct gluau_Span span;
+};
+
+struct gluau_LintWarnings {
+	struct gluau_LintWarning* warnings;
+	size_t len;
+};
+
+enum gluau_CompletionKind : uint8_t {
+	COMPLETION_PROPERTY,
+	// a property whose type is a function
+	COMPLETION_METHOD,
+	COMPLETION_VARIABLE,
+	// a variable whose type is a function
+	COMPLETION_FUNCTION,
+	COMPLETION_KEYWORD,
+	COMPLETION_STRING,
+	COMPLETION_TYPE,
+	COMPLETION_MODULE,
+	COMPLETION_GENERATED_FUNCTION,
+	COMPLETION_REQUIRE_PATH
+};
+
+// Buffers with null data are absent.
+struct gluau_Completion {
+	enum gluau_CompletionKind kind;
+	struct gluau_Buffer label;
+	struct gluau_Buffer type;
+	struct gluau_Buffer documentation_symbol;
+	struct gluau_Buffer insert_text;
+	bool deprecated;
+};
+
+struct gluau_Completions {
+	struct gluau_Completion* completions;
+	size_t len;
+};
+
+struct gluau_ModuleNames {
+	struct gluau_Buffer* names;
+	size_t len;
+};
+
+struct gluau_ModuleNode {
+	struct gluau_Buffer name;
+	struct gluau_ModuleNames required;
+};
+
+struct gluau_ModuleGraph {
+	struct gluau_ModuleNode* nodes;
+	size_t len;
+};
+
+// The argument of a `require` call, if it is either a string literal or a path
+// of names, like `script.Parent.Module`.
+struct gluau_RequireExpr {
+	// whether the argument is a string literal, which is the only part
+	bool string;
+	const struct gluau_Buffer* parts;
+	size_t len;
+};
+
+// Implemented on the Rust side. Every callback is passed `data`. Buffers that
+// are written by a callback only need to stay valid until the next callback.
+struct gluau_FileResolver {
+	void* data;
+	// returns whether the module exists, writing its source to `source` and its
+	// type, numbered like Luau::SourceCode::Type, to `type`
+	bool (*read_source)(void* data, struct gluau_Buffer name, struct gluau_Buffer* source, uint8_t* type);
+	// returns whether `expr`, the argument to a `require` call inside of the
+	// module `from`, refers to a module, writing its name to `name`
+	bool (*resolve_module)(void* data, struct gluau_Buffer from, struct gluau_RequireExpr expr, struct gluau_Buffer* name);
+};
+
+// Wraps a Luau::Frontend along with its resolvers and the builtin globals.
+struct gluau_Frontend;
+
+// Modes are numbered like Luau::Mode.
+GLUE_API struct gluau_Frontend* gluau_frontend_new(struct gluau_FileResolver resolver, uint8_t mode);
+GLUE_API void gluau_frontend_set_mode(struct gluau_Frontend* frontend, uint8_t mode);
+// Sets the extra globals that modules can use, which are typed as `any`.
+GLUE_API void gluau_frontend_set_globals(struct gluau_Frontend* frontend, const struct gluau_Buffer* globals, size_t len);
+// Marks a module and every module that depends on it as dirty, returning them.
+GLUE_API struct gluau_ModuleNames gluau_frontend_mark_dirty(struct gluau_Frontend* frontend, struct gluau_Buffer name);
+// Returns every module that the frontend knows of, along with what it requires.
+GLUE_API struct gluau_ModuleGraph gluau_frontend_module_graph(struct gluau_Frontend* frontend);
+GLUE_API struct gluau_TypeErrors gluau_frontend_check(struct gluau_Frontend* frontend, struct gluau_Buffer name);
+// Checks the module if it isn't already, and then lints it. Warnings disabled
+// by `--!nolint` hot comments are removed from the mask.
+GLUE_API struct gluau_LintWarnings gluau_frontend_lint(struct gluau_Frontend* frontend, struct gluau_Buffer name, uint64_t mask);
+// Checks the module for autocomplete if it isn't already, and then returns the
+// completions at the position.
+GLUE_API struct gluau_Completions gluau_frontend_autocomplete(struct gluau_Frontend* frontend, struct gluau_Buffer name, unsigned int line, unsigned int column);
+// Returns the type of the expression at the position, or a null buffer.
+GLUE_API struct gluau_Buffer gluau_frontend_type_at(struct gluau_Frontend* frontend, struct gluau_Buffer name, unsigned int line, unsigned int column);
+// Returns whether the name at the position has a known definition, writing the
+// module it is in to `module` and its location to `span`.
+GLUE_API bool gluau_frontend_definition_of(struct gluau_Frontend* frontend, struct gluau_Buffer name, unsigned int line, unsigned int column, struct gluau_Buffer* module, struct gluau_Span* span);
+// Adds the globals declared by a definitions file to every module checked
+// afterwards. Returns the errors that prevented the file from loading, if any.
+GLUE_API struct gluau_TypeErrors gluau_frontend_load_definitions(struct gluau_Frontend* frontend, struct gluau_Buffer name, struct gluau_Buffer source);
+GLUE_API void gluau_frontend_free(struct gluau_Frontend* frontend);
@@ -12,3 +12,814 @@
 //
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#include "ast.hpp"
+#include "common.hpp"
+
+#include "string.h" // NOLINT(modernize-deprecated-headers)
+
+#include <optional>
+#include <unordered_map>
+#include <vector>
+
+#include <Luau/Ast.h>
+#include <Luau/Parser.h>
+
+Luau::ParseOptions gluauA_parseopt2luau(const gluau_ParseOpts &opts) {
+	return {
+		.allowDeclarationSyntax = opts.allowDeclarationSyntax,
+		.captureComments = opts.captureComments
+	};
+}
+
+gluau_Span gluauA_loctospan(const Luau::Location &location) {
+	return {
+		.start_line = location.begin.line,
+		.start_column = location.begin.column,
+		.end_line = location.end.line,
+		.end_column = location.end.column
+	};
+}
+
+// Luau's errors only carry a message, so their code is recovered from its
+// start. These are the beginnings of Luau's own format strings, and this is the
+// only place that depends on their wording; anything unrecognized is reported
+// as ERROR_CODE_UNKNOWN rather than misclassified.
+gluau_ErrorCode gluauA_classify(const std::string &message) {
+	static const std::pair<const char*, gluau_ErrorCode> prefixes[] = {
+		{"Incomplete statement", ERROR_CODE_INCOMPLETE_STATEMENT},
+		{"Ambiguous syntax", ERROR_CODE_AMBIGUOUS_SYNTAX},
+		{"Malformed number", ERROR_CODE_MALFORMED_NUMBER},
+		{"Malformed string", ERROR_CODE_MALFORMED_STRING},
+		{"Malformed interpolated string", ERROR_CODE_MALFORMED_STRING},
+		{"Unfinished string", ERROR_CODE_MALFORMED_STRING},
+		{"Unfinished long string", ERROR_CODE_MALFORMED_STRING},
+		{"Unfinished comment", ERROR_CODE_UNFINISHED_COMMENT},
+		{"Unfinished long comment", ERROR_CODE_UNFINISHED_COMMENT},
+		{"Unexpected Unicode character", ERROR_CODE_UNEXPECTED_CHARACTER},
+		{"Malformed UTF-8", ERROR_CODE_UNEXPECTED_CHARACTER},
+		{"Cannot use '...' outside of a vararg function", ERROR_CODE_INVALID_VARARG},
+		{"Exceeded allowed recursion depth", ERROR_CODE_RECURSION_LIMIT},
+		{"Out of registers", ERROR_CODE_REGISTER_LIMIT},
+		{"Out of upvalue registers", ERROR_CODE_UPVALUE_LIMIT},
+		{"Out of local registers", ERROR_CODE_LOCAL_LIMIT},
+		{"Exceeded constant limit", ERROR_CODE_CONSTANT_LIMIT},
+		{"Expected", ERROR_CODE_UNEXPECTED_TOKEN}
+	};
+
+	for (const auto &[prefix, code]: prefixes) {
+		if (message.rfind(prefix, 0) != 0) {
+			continue;
+		}
+
+		// the parser was looking for the end of a block or bracket
+		if (code == ERROR_CODE_UNEXPECTED_TOKEN && message.find("(to close ") != std::string::npos) {
+			return ERROR_CODE_UNCLOSED_BLOCK;
+		}
+
+		return code;
+	}
+
+	return ERROR_CODE_UNKNOWN;
+}
+
+gluau_Error gluauA_error(gluau_ErrorCode code, const std::string &message, const Luau::Location &location) {
+	return {
+		.message = gluauU_strtobuf(message),
+		.span = gluauA_loctospan(location),
+		.code = code,
+		.related = {.presence = gluau_Optionality::None}
+	};
+}
+
+struct gluauA_Opener {
+	Luau::Lexeme::Type type;
+	Luau::Location location;
+	// while and for loops are opened by their keyword, so the `do` that follows
+	// doesn't open another block
+	bool awaitingDo;
+};
+
+// Finds the innermost block or bracket that is still open at a position, by
+// matching the tokens before it the same way the parser does.
+static std::optional<gluauA_Opener> gluauA_opener(const char* source, size_t len, const Luau::Position &position) {
+	Luau::Allocator allocator;
+	Luau::AstNameTable names(allocator);
+	Luau::Lexer lexer(source, len, names);
+	lexer.setSkipComments(true);
+
+	std::vector<gluauA_Opener> open;
+
+	auto close = [&open](int opener) {
+		if (!open.empty() && open.back().type == opener) {
+			open.pop_back();
+		}
+	};
+
+	for (const Luau::Lexeme* lexeme = &lexer.next(); lexeme->type != Luau::Lexeme::Eof && lexeme->location.begin < position; lexeme = &lexer.next()) {
+		// characters aren't named in Lexeme::Type
+		switch (static_cast<int>(lexeme->type)) {
+			case Luau::Lexeme::ReservedFunction:
+			case Luau::Lexeme::ReservedIf:
+			case Luau::Lexeme::ReservedRepeat:
+			case '(':
+			case '[':
+			case '{':
+				open.push_back({lexeme->type, lexeme->location, false});
+				break;
+
+			case Luau::Lexeme::ReservedWhile:
+			case Luau::Lexeme::ReservedFor:
+				open.push_back({lexeme->type, lexeme->location, true});
+				break;
+
+			case Luau::Lexeme::ReservedDo:
+				if (!open.empty() && open.back().awaitingDo) {
+					open.back().awaitingDo = false;
+				} else {
+					open.push_back({lexeme->type, lexeme->location, false});
+				}
+
+				break;
+
+			case Luau::Lexeme::ReservedEnd:
+				if (!open.empty() && open.back().type >= Luau::Lexeme::Reserved_BEGIN && open.back().type != Luau::Lexeme::ReservedRepeat) {
+					open.pop_back();
+				}
+
+				break;
+
+			case Luau::Lexeme::ReservedUntil: close(Luau::Lexeme::ReservedRepeat); break;
+			case ')': close('('); break;
+			case ']': close('['); break;
+			case '}': close('{'); break;
+			default: break;
+		}
+	}
+
+	if (open.empty()) {
+		return std::nullopt;
+	}
+
+	return open.back();
+}
+
+gluau_Errors gluauA_errors(const std::vector<Luau::ParseError> &errors, const char* source, size_t source_len) {
+	auto len = errors.size();
+	auto* converted = new gluau_Error[len];
+
+	unsigned int i = 0;
+	for (const auto &error: errors) {
+		gluau_ErrorCode code = gluauA_classify(error.getMessage());
+		gluau_Error &current = converted[i++] = gluauA_error(code, error.getMessage(), error.getLocation());
+
+		if (code != ERROR_CODE_UNCLOSED_BLOCK) {
+			continue;
+		}
+
+		if (auto opener = gluauA_opener(source, source_len, error.getLocation().begin)) {
+			current.related = {
+				.presence = gluau_Optionality::Some,
+				.span = gluauA_loctospan(opener->location),
+				.message = gluauU_strtobuf("to close " + Luau::Lexeme(opener->location, opener->type).toString())
+			};
+		}
+	}
+
+	return {
+		.errors = converted,
+		.len = len
+	};
+}
+
+// The serialized format is read by src/ast/decode.rs, and the tags below must be
+// kept in sync with the ones there. Every node starts with its location, and
+// then its tag. Integers are little-endian u32s, strings and arrays are prefixed
+// with their length as a u32, and optional values are prefixed with a 0 or 1
+// byte. Locals are referred to by ID, and their definitions follow the tree.
+// Comments come next, and are only present if they were captured, followed by
+// hot comments, which always are.
+
+enum class StatTag : uint8_t {
+	Block, If, While, Repeat, Break, Continue, Return, Expr, Local, For, ForIn, Assign, CompoundAssign, Function,
+	LocalFunction, TypeAlias, DeclareGlobal, DeclareFunction, DeclareClass, Error
+};
+
+enum class ExprTag : uint8_t {
+	Group, Nil, Bool, Number, String, Local, Global, Varargs, Call, IndexName, IndexExpr, Function, Table, Unary,
+	Binary, TypeAssertion, IfElse, InterpString, Error
+};
+
+enum class TypeTag : uint8_t {
+	Reference, Table, Function, Typeof, Union, Intersection, SingletonBool, SingletonString, Error
+};
+
+enum class TypePackTag : uint8_t {
+	Explicit, Variadic, Generic
+};
+
+class gluauA_AstWriter {
+	public:
+		std::string out;
+
+		void root(const Luau::ParseResult &result) {
+			u32(result.lines);
+			block(result.root);
+
+			// a shadowed local gets its ID here if the tree never wrote it, so
+			// the table is terminated instead of length-prefixed
+			for (size_t i = 0; i < locals.size(); i++) {
+				Luau::AstLocal* local = locals[i];
+
+				u8(1);
+				name(local->name);
+				location(local->location);
+				u32(local->shadow ? localId(local->shadow) + 1 : 0);
+				u32(local->functionDepth);
+				u32(local->loopDepth);
+			}
+
+			u8(0);
+
+			u32(result.commentLocations.size());
+			for (const auto &comment: result.commentLocations) {
+				location(comment.location);
+
+				switch (comment.type) {
+					case Luau::Lexeme::Comment: u8(0); break;
+					case Luau::Lexeme::BlockComment: u8(1); break;
+					default: u8(2); break;
+				}
+			}
+
+			u32(result.hotcomments.size());
+			for (const auto &hotcomment: result.hotcomments) {
+				location(hotcomment.location);
+				u8(hotcomment.header);
+				bytes(hotcomment.content.data(), hotcomment.content.size());
+			}
+		}
+
+	private:
+		std::unordered_map<Luau::AstLocal*, uint32_t> localIds;
+		std::vector<Luau::AstLocal*> locals;
+
+		void u8(uint8_t value) {
+			out.push_back(static_cast<char>(value));
+		}
+
+		void u32(size_t value) {
+			auto truncated = static_cast<uint32_t>(value);
+
+			for (int shift = 0; shift < 32; shift += 8) {
+				u8(static_cast<uint8_t>(truncated >> shift));
+			}
+		}
+
+		void f64(double value) {
+			uint64_t bits;
+			memcpy(&bits, &value, sizeof(bits));
+
+			for (int shift = 0; shift < 64; shift += 8) {
+				u8(static_cast<uint8_t>(bits >> shift));
+			}
+		}
+
+		void bytes(const char* data, size_t len) {
+			u32(len);
+			out.append(data, len);
+		}
+
+		void chars(Luau::AstArray<char> array) {
+			bytes(array.data, array.size);
+		}
+
+		void name(const Luau::AstName &name) {
+			bytes(name.value ? name.value : "", name.value ? strlen(name.value) : 0);
+		}
+
+		void optionalName(const std::optional<Luau::AstName> &name) {
+			u8(name.has_value());
+			if (name) this->name(*name);
+		}
+
+		void location(const Luau::Location &location) {
+			u32(location.begin.line);
+			u32(location.begin.column);
+			u32(location.end.line);
+			u32(location.end.column);
+		}
+
+		void optionalLocation(const std::optional<Luau::Location> &location) {
+			u8(location.has_value());
+			if (location) this->location(*location);
+		}
+
+		template<typename T>
+			void optional(T* node, void (gluauA_AstWriter::*write)(T*)) {
+				u8(node != nullptr);
+				if (node) (this->*write)(node);
+			}
+
+		template<typename T>
+			void array(const Luau::AstArray<T> &array, void (gluauA_AstWriter::*write)(T)) {
+				u32(array.size);
+				for (const T &item: array) (this->*write)(item);
+			}
+
+		uint32_t localId(Luau::AstLocal* local) {
+			auto [it, inserted] = localIds.try_emplace(local, static_cast<uint32_t>(locals.size()));
+			if (inserted) locals.push_back(local);
+			return it->second;
+		}
+
+		void local(Luau::AstLocal* local) {
+			u32(localId(local));
+		}
+
+		void binding(Luau::AstLocal* local) {
+			this->local(local);
+			optional(local->annotation, &gluauA_AstWriter::type);
+		}
+
+		void attribute(Luau::AstAttr* attr) {
+			location(attr->location);
+			u8(static_cast<uint8_t>(attr->type));
+		}
+
+		void genericType(Luau::AstGenericType generic) {
+			name(generic.name);
+			location(generic.location);
+			optional(generic.defaultValue, &gluauA_AstWriter::type);
+		}
+
+		void genericTypePack(Luau::AstGenericTypePack generic) {
+			name(generic.name);
+			location(generic.location);
+			optional(generic.defaultValue, &gluauA_AstWriter::typePack);
+		}
+
+		void argumentName(Luau::AstArgumentName argument) {
+			name(argument.first);
+			location(argument.second);
+		}
+
+		void optionalArgumentName(std::optional<Luau::AstArgumentName> argument) {
+			u8(argument.has_value());
+			if (argument) argumentName(*argument);
+		}
+
+		void typeList(const Luau::AstTypeList &list) {
+			array(list.types, &gluauA_AstWriter::type);
+			optional(list.tailType, &gluauA_AstWriter::typePack);
+		}
+
+		void typeOrPack(Luau::AstTypeOrPack typeOrPack) {
+			if (typeOrPack.type) {
+				u8(0);
+				type(typeOrPack.type);
+			} else {
+				u8(1);
+				typePack(typeOrPack.typePack);
+			}
+		}
+
+		void tableProp(Luau::AstTableProp prop) {
+			name(prop.name);
+			location(prop.location);
+			type(prop.type);
+			u8(static_cast<uint8_t>(prop.access));
+			optionalLocation(prop.accessLocation);
+		}
+
+		void tableIndexer(Luau::AstTableIndexer* indexer) {
+			location(indexer->location);
+			type(indexer->indexType);
+			type(indexer->resultType);
+			u8(static_cast<uint8_t>(indexer->access));
+			optionalLocation(indexer->accessLocation);
+		}
+
+		void declaredClassProp(Luau::AstDeclaredClassProp prop) {
+			name(prop.name);
+			location(prop.nameLocation);
+			type(prop.ty);
+			u8(prop.isMethod);
+			location(prop.location);
+		}
+
+		void tableItem(Luau::AstExprTable::Item item) {
+			u8(static_cast<uint8_t>(item.kind));
+			optional(item.key, &gluauA_AstWriter::expr);
+			expr(item.value);
+		}
+
+		void function(Luau::AstExprFunction* func) {
+			location(func->location);
+			array(func->attributes, &gluauA_AstWriter::attribute);
+			array(func->generics, &gluauA_AstWriter::genericType);
+			array(func->genericPacks, &gluauA_AstWriter::genericTypePack);
+			u8(func->self != nullptr);
+			if (func->self) local(func->self);
+			array(func->args, &gluauA_AstWriter::binding);
+			u8(func->returnAnnotation.has_value());
+			if (func->returnAnnotation) typeList(*func->returnAnnotation);
+			u8(func->vararg);
+			location(func->varargLocation);
+			optional(func->varargAnnotation, &gluauA_AstWriter::typePack);
+			block(func->body);
+			u32(func->functionDepth);
+			name(func->debugname);
+			optionalLocation(func->argLocation);
+		}
+
+		void block(Luau::AstStatBlock* block) {
+			location(block->location);
+			array(block->body, &gluauA_AstWriter::stat);
+			u8(block->hasEnd);
+		}
+
+		void stat(Luau::AstStat* node) {
+			location(node->location);
+
+			if (auto* n = node->as<Luau::AstStatBlock>()) {
+				u8(static_cast<uint8_t>(StatTag::Block));
+				block(n);
+			} else if (auto* n = node->as<Luau::AstStatIf>()) {
+				u8(static_cast<uint8_t>(StatTag::If));
+				expr(n->condition);
+				block(n->thenbody);
+				optional(n->elsebody, &gluauA_AstWriter::stat);
+				optionalLocation(n->thenLocation);
+				optionalLocation(n->elseLocation);
+			} else if (auto* n = node->as<Luau::AstStatWhile>()) {
+				u8(static_cast<uint8_t>(StatTag::While));
+				expr(n->condition);
+				block(n->body);
+				u8(n->hasDo);
+				location(n->doLocation);
+			} else if (auto* n = node->as<Luau::AstStatRepeat>()) {
+				u8(static_cast<uint8_t>(StatTag::Repeat));
+				block(n->body);
+				expr(n->condition);
+			} else if (node->is<Luau::AstStatBreak>()) {
+				u8(static_cast<uint8_t>(StatTag::Break));
+			} else if (node->is<Luau::AstStatContinue>()) {
+				u8(static_cast<uint8_t>(StatTag::Continue));
+			} else if (auto* n = node->as<Luau::AstStatReturn>()) {
+				u8(static_cast<uint8_t>(StatTag::Return));
+				array(n->list, &gluauA_AstWriter::expr);
+			} else if (auto* n = node->as<Luau::AstStatExpr>()) {
+				u8(static_cast<uint8_t>(StatTag::Expr));
+				expr(n->expr);
+			} else if (auto* n = node->as<Luau::AstStatLocal>()) {
+				u8(static_cast<uint8_t>(StatTag::Local));
+				array(n->vars, &gluauA_AstWriter::binding);
+				array(n->values, &gluauA_AstWriter::expr);
+				optionalLocation(n->equalsSignLocation);
+			} else if (auto* n = node->as<Luau::AstStatFor>()) {
+				u8(static_cast<uint8_t>(StatTag::For));
+				binding(n->var);
+				expr(n->from);
+				expr(n->to);
+				optional(n->step, &gluauA_AstWriter::expr);
+				block(n->body);
+				u8(n->hasDo);
+				location(n->doLocation);
+			} else if (auto* n = node->as<Luau::AstStatForIn>()) {
+				u8(static_cast<uint8_t>(StatTag::ForIn));
+				array(n->vars, &gluauA_AstWriter::binding);
+				array(n->values, &gluauA_AstWriter::expr);
+				block(n->body);
+				u8(n->hasIn);
+				location(n->inLocation);
+				u8(n->hasDo);
+				location(n->doLocation);
+			} else if (auto* n = node->as<Luau::AstStatAssign>()) {
+				u8(static_cast<uint8_t>(StatTag::Assign));
+				array(n->vars, &gluauA_AstWriter::expr);
+				array(n->values, &gluauA_AstWriter::expr);
+			} else if (auto* n = node->as<Luau::AstStatCompoundAssign>()) {
+				u8(static_cast<uint8_t>(StatTag::CompoundAssign));
+				u8(static_cast<uint8_t>(n->op));
+				expr(n->var);
+				expr(n->value);
+			} else if (auto* n = node->as<Luau::AstStatFunction>()) {
+				u8(static_cast<uint8_t>(StatTag::Function));
+				expr(n->name);
+				function(n->func);
+			} else if (auto* n = node->as<Luau::AstStatLocalFunction>()) {
+				u8(static_cast<uint8_t>(StatTag::LocalFunction));
+				local(n->name);
+				function(n->func);
+			} else if (auto* n = node->as<Luau::AstStatTypeAlias>()) {
+				u8(static_cast<uint8_t>(StatTag::TypeAlias));
+				name(n->name);
+				location(n->nameLocation);
+				array(n->generics, &gluauA_AstWriter::genericType);
+				array(n->genericPacks, &gluauA_AstWriter::genericTypePack);
+				type(n->type);
+				u8(n->exported);
+			} else if (auto* n = node->as<Luau::AstStatDeclareGlobal>()) {
+				u8(static_cast<uint8_t>(StatTag::DeclareGlobal));
+				name(n->name);
+				location(n->nameLocation);
+				type(n->type);
+			} else if (auto* n = node->as<Luau::AstStatDeclareFunction>()) {
+				u8(static_cast<uint8_t>(StatTag::DeclareFunction));
+				array(n->attributes, &gluauA_AstWriter::attribute);
+				name(n->name);
+				location(n->nameLocation);
+				array(n->generics, &gluauA_AstWriter::genericType);
+				array(n->genericPacks, &gluauA_AstWriter::genericTypePack);
+				typeList(n->params);
+				array(n->paramNames, &gluauA_AstWriter::argumentName);
+				u8(n->vararg);
+				location(n->varargLocation);
+				typeList(n->retTypes);
+			} else if (auto* n = node->as<Luau::AstStatDeclareClass>()) {
+				u8(static_cast<uint8_t>(StatTag::DeclareClass));
+				name(n->name);
+				optionalName(n->superName);
+				array(n->props, &gluauA_AstWriter::declaredClassProp);
+				optional(n->indexer, &gluauA_AstWriter::tableIndexer);
+			} else if (auto* n = node->as<Luau::AstStatError>()) {
+				u8(static_cast<uint8_t>(StatTag::Error));
+				array(n->expressions, &gluauA_AstWriter::expr);
+				array(n->statements, &gluauA_AstWriter::stat);
+				u32(n->messageIndex);
+			} else {
+				// a statement kind we don't know about yet
+				u8(static_cast<uint8_t>(StatTag::Error));
+				u32(0);
+				u32(0);
+				u32(UINT32_MAX);
+			}
+		}
+
+		void expr(Luau::AstExpr* node) {
+			location(node->location);
+
+			if (auto* n = node->as<Luau::AstExprGroup>()) {
+				u8(static_cast<uint8_t>(ExprTag::Group));
+				expr(n->expr);
+			} else if (node->is<Luau::AstExprConstantNil>()) {
+				u8(static_cast<uint8_t>(ExprTag::Nil));
+			} else if (auto* n = node->as<Luau::AstExprConstantBool>()) {
+				u8(static_cast<uint8_t>(ExprTag::Bool));
+				u8(n->value);
+			} else if (auto* n = node->as<Luau::AstExprConstantNumber>()) {
+				u8(static_cast<uint8_t>(ExprTag::Number));
+				f64(n->value);
+			} else if (auto* n = node->as<Luau::AstExprConstantString>()) {
+				u8(static_cast<uint8_t>(ExprTag::String));
+				chars(n->value);
+				u8(static_cast<uint8_t>(n->quoteStyle));
+			} else if (auto* n = node->as<Luau::AstExprLocal>()) {
+				u8(static_cast<uint8_t>(ExprTag::Local));
+				local(n->local);
+				u8(n->upvalue);
+			} else if (auto* n = node->as<Luau::AstExprGlobal>()) {
+				u8(static_cast<uint8_t>(ExprTag::Global));
+				name(n->name);
+			} else if (node->is<Luau::AstExprVarargs>()) {
+				u8(static_cast<uint8_t>(ExprTag::Varargs));
+			} else if (auto* n = node->as<Luau::AstExprCall>()) {
+				u8(static_cast<uint8_t>(ExprTag::Call));
+				expr(n->func);
+				array(n->args, &gluauA_AstWriter::expr);
+				u8(n->self);
+				location(n->argLocation);
+			} else if (auto* n = node->as<Luau::AstExprIndexName>()) {
+				u8(static_cast<uint8_t>(ExprTag::IndexName));
+				expr(n->expr);
+				name(n->index);
+				location(n->indexLocation);
+				u8(static_cast<uint8_t>(n->op));
+			} else if (auto* n = node->as<Luau::AstExprIndexExpr>()) {
+				u8(static_cast<uint8_t>(ExprTag::IndexExpr));
+				expr(n->expr);
+				expr(n->index);
+			} else if (auto* n = node->as<Luau::AstExprFunction>()) {
+				u8(static_cast<uint8_t>(ExprTag::Function));
+				function(n);
+			} else if (auto* n = node->as<Luau::AstExprTable>()) {
+				u8(static_cast<uint8_t>(ExprTag::Table));
+				array(n->items, &gluauA_AstWriter::tableItem);
+			} else if (auto* n = node->as<Luau::AstExprUnary>()) {
+				u8(static_cast<uint8_t>(ExprTag::Unary));
+				u8(static_cast<uint8_t>(n->op));
+				expr(n->expr);
+			} else if (auto* n = node->as<Luau::AstExprBinary>()) {
+				u8(static_cast<uint8_t>(ExprTag::Binary));
+				u8(static_cast<uint8_t>(n->op));
+				expr(n->left);
+				expr(n->right);
+			} else if (auto* n = node->as<Luau::AstExprTypeAssertion>()) {
+				u8(static_cast<uint8_t>(ExprTag::TypeAssertion));
+				expr(n->expr);
+				type(n->annotation);
+			} else if (auto* n = node->as<Luau::AstExprIfElse>()) {
+				u8(static_cast<uint8_t>(ExprTag::IfElse));
+				expr(n->condition);
+				u8(n->hasThen);
+				expr(n->trueExpr);
+				u8(n->hasElse);
+				expr(n->falseExpr);
+			} else if (auto* n = node->as<Luau::AstExprInterpString>()) {
+				u8(static_cast<uint8_t>(ExprTag::InterpString));
+				array(n->strings, &gluauA_AstWriter::chars);
+				array(n->expressions, &gluauA_AstWriter::expr);
+			} else if (auto* n = node->as<Luau::AstExprError>()) {
+				u8(static_cast<uint8_t>(ExprTag::Error));
+				array(n->expressions, &gluauA_AstWriter::expr);
+				u32(n->messageIndex);
+			} else {
+				// an expression kind we don't know about yet
+				u8(static_cast<uint8_t>(ExprTag::Error));
+				u32(0);
+				u32(UINT32_MAX);
+			}
+		}
+
+		void type(Luau::AstType* node) {
+			location(node->location);
+
+			if (auto* n = node->as<Luau::AstTypeReference>()) {
+				u8(static_cast<uint8_t>(TypeTag::Reference));
+				optionalName(n->prefix);
+				optionalLocation(n->prefixLocation);
+				name(n->name);
+				location(n->nameLocation);
+				u8(n->hasParameterList);
+				array(n->parameters, &gluauA_AstWriter::typeOrPack);
+			} else if (auto* n = node->as<Luau::AstTypeTable>()) {
+				u8(static_cast<uint8_t>(TypeTag::Table));
+				array(n->props, &gluauA_AstWriter::tableProp);
+				optional(n->indexer, &gluauA_AstWriter::tableIndexer);
+			} else if (auto* n = node->as<Luau::AstTypeFunction>()) {
+				u8(static_cast<uint8_t>(TypeTag::Function));
+				array(n->attributes, &gluauA_AstWriter::attribute);
+				array(n->generics, &gluauA_AstWriter::genericType);
+				array(n->genericPacks, &gluauA_AstWriter::genericTypePack);
+				typeList(n->argTypes);
+				array(n->argNames, &gluauA_AstWriter::optionalArgumentName);
+				typeList(n->returnTypes);
+			} else if (auto* n = node->as<Luau::AstTypeTypeof>()) {
+				u8(static_cast<uint8_t>(TypeTag::Typeof));
+				expr(n->expr);
+			} else if (auto* n = node->as<Luau::AstTypeUnion>()) {
+				u8(static_cast<uint8_t>(TypeTag::Union));
+				array(n->types, &gluauA_AstWriter::type);
+			} else if (auto* n = node->as<Luau::AstTypeIntersection>()) {
+				u8(static_cast<uint8_t>(TypeTag::Intersection));
+				array(n->types, &gluauA_AstWriter::type);
+			} else if (auto* n = node->as<Luau::AstTypeSingletonBool>()) {
+				u8(static_cast<uint8_t>(TypeTag::SingletonBool));
+				u8(n->value);
+			} else if (auto* n = node->as<Luau::AstTypeSingletonString>()) {
+				u8(static_cast<uint8_t>(TypeTag::SingletonString));
+				chars(n->value);
+			} else if (auto* n = node->as<Luau::AstTypeError>()) {
+				u8(static_cast<uint8_t>(TypeTag::Error));
+				array(n->types, &gluauA_AstWriter::type);
+				u8(n->isMissing);
+				u32(n->messageIndex);
+			} else {
+				// a type kind we don't know about yet
+				u8(static_cast<uint8_t>(TypeTag::Error));
+				u32(0);
+				u8(false);
+				u32(UINT32_MAX);
+			}
+		}
+
+		void typePack(Luau::AstTypePack* node) {
+			location(node->location);
+
+			if (auto* n = node->as<Luau::AstTypePackExplicit>()) {
+				u8(static_cast<uint8_t>(TypePackTag::Explicit));
+				typeList(n->typeList);
+			} else if (auto* n = node->as<Luau::AstTypePackVariadic>()) {
+				u8(static_cast<uint8_t>(TypePackTag::Variadic));
+				type(n->variadicType);
+			} else if (auto* n = node->as<Luau::AstTypePackGeneric>()) {
+				u8(static_cast<uint8_t>(TypePackTag::Generic));
+				name(n->genericName);
+			} else {
+				// a type pack kind we don't know about yet; an empty pack is the
+				// least surprising substitute
+				u8(static_cast<uint8_t>(TypePackTag::Explicit));
+				u32(0);
+				u8(false);
+			}
+		}
+};
+
+GLUE_API struct gluau_ParseResult gluau_parse(struct gluau_Buffer source, struct gluau_ParseOpts parse_opts) {
+	Luau::Allocator allocator;
+	Luau::AstNameTable names(allocator);
+
+	try {
+		Luau::ParseResult result = Luau::Parser::parse(source.data, source.len, names, allocator, gluauA_parseopt2luau(parse_opts));
+
+		gluauA_AstWriter writer;
+		writer.root(result);
+
+		return {
+			.ast = gluauU_strtobuf(writer.out),
+			.errors = gluauA_errors(result.errors, source.data, source.len)
+		};
+	} catch (std::exception &error) {
+		return {
+			.ast = {nullptr, 0},
+			.errors = {
+				.errors = new gluau_Error[1] {gluauA_error(ERROR_CODE_INTERNAL, error.what(), Luau::Location())},
+				.len = 1
+			}
+		};
+	}
+}
+
+GLUE_API const char* gluau_find_confusable(uint32_t codepoint) {
+	return Luau::findConfusable(codepoint);
+}
+
+struct gluau_Lexer {
+	Luau::Allocator allocator;
+	Luau::AstNameTable names;
+	Luau::Lexer lexer;
+
+	gluau_Lexer(const char* data, size_t len) : names(allocator), lexer(data, len, names) {
+		lexer.setSkipComments(false);
+	}
+};
+
+GLUE_API struct gluau_Lexer* gluau_lexer_new(struct gluau_Buffer source) {
+	return new gluau_Lexer(source.data, source.len);
+}
+
+GLUE_API struct gluau_Token gluau_lexer_next(struct gluau_Lexer* lexer) {
+	const Luau::Lexeme &lexeme = lexer->lexer.next();
+
+	gluau_TokenKind kind;
+	uint8_t data = 0;
+
+	switch (lexeme.type) {
+		case Luau::Lexeme::Eof: kind = TOKEN_EOF; break;
+		case Luau::Lexeme::Equal: kind = TOKEN_EQUAL; break;
+		case Luau::Lexeme::LessEqual: kind = TOKEN_LESS_EQUAL; break;
+		case Luau::Lexeme::GreaterEqual: kind = TOKEN_GREATER_EQUAL; break;
+		case Luau::Lexeme::NotEqual: kind = TOKEN_NOT_EQUAL; break;
+		case Luau::Lexeme::Dot2: kind = TOKEN_DOT2; break;
+		case Luau::Lexeme::Dot3: kind = TOKEN_DOT3; break;
+		case Luau::Lexeme::SkinnyArrow: kind = TOKEN_SKINNY_ARROW; break;
+		case Luau::Lexeme::DoubleColon: kind = TOKEN_DOUBLE_COLON; break;
+		case Luau::Lexeme::FloorDiv: kind = TOKEN_FLOOR_DIV; break;
+		case Luau::Lexeme::InterpStringBegin: kind = TOKEN_INTERP_STRING_BEGIN; break;
+		case Luau::Lexeme::InterpStringMid: kind = TOKEN_INTERP_STRING_MID; break;
+		case Luau::Lexeme::InterpStringEnd: kind = TOKEN_INTERP_STRING_END; break;
+		case Luau::Lexeme::InterpStringSimple: kind = TOKEN_INTERP_STRING_SIMPLE; break;
+		case Luau::Lexeme::AddAssign: kind = TOKEN_ADD_ASSIGN; break;
+		case Luau::Lexeme::SubAssign: kind = TOKEN_SUB_ASSIGN; break;
+		case Luau::Lexeme::MulAssign: kind = TOKEN_MUL_ASSIGN; break;
+		case Luau::Lexeme::DivAssign: kind = TOKEN_DIV_ASSIGN; break;
+		case Luau::Lexeme::FloorDivAssign: kind = TOKEN_FLOOR_DIV_ASSIGN; break;
+		case Luau::Lexeme::ModAssign: kind = TOKEN_MOD_ASSIGN; break;
+		case Luau::Lexeme::PowAssign: kind = TOKEN_POW_ASSIGN; break;
+		case Luau::Lexeme::ConcatAssign: kind = TOKEN_CONCAT_ASSIGN; break;
+		case Luau::Lexeme::RawString: kind = TOKEN_RAW_STRING; break;
+		case Luau::Lexeme::QuotedString: kind = TOKEN_QUOTED_STRING; break;
+		case Luau::Lexeme::Number: kind = TOKEN_NUMBER; break;
+		case Luau::Lexeme::Name: kind = TOKEN_NAME; break;
+		case Luau::Lexeme::Comment: kind = TOKEN_COMMENT; break;
+		case Luau::Lexeme::BlockComment: kind = TOKEN_BLOCK_COMMENT; break;
+		case Luau::Lexeme::Attribute: kind = TOKEN_ATTRIBUTE; break;
+		case Luau::Lexeme::BrokenString: kind = TOKEN_BROKEN_STRING; break;
+		case Luau::Lexeme::BrokenComment: kind = TOKEN_BROKEN_COMMENT; break;
+		case Luau::Lexeme::BrokenUnicode: kind = TOKEN_BROKEN_UNICODE; break;
+		case Luau::Lexeme::BrokenInterpDoubleBrace: kind = TOKEN_BROKEN_INTERP_DOUBLE_BRACE; break;
+		case Luau::Lexeme::Error: kind = TOKEN_ERROR; break;
+
+		default:
+			if (lexeme.type < Luau::Lexeme::Char_END) {
+				kind = TOKEN_CHAR;
+				data = static_cast<uint8_t>(lexeme.type);
+			} else if (lexeme.type >= Luau::Lexeme::Reserved_BEGIN && lexeme.type < Luau::Lexeme::Reserved_END) {
+				kind = TOKEN_KEYWORD;
+				data = static_cast<uint8_t>(lexeme.type - Luau::Lexeme::Reserved_BEGIN);
+			} else {
+				kind = TOKEN_UNKNOWN;
+			}
+	}
+
+	return {
+		.kind = kind,
+		.data = data,
+		.span = gluauA_loctospan(lexeme.location)
+	};
+}
+
+GLUE_API void gluau_lexer_free(struct gluau_Lexer* lexer) {
+	delete lexer;
+}
@@ -15,3 +15,133 @@
 
 #pragma once
 
+#include "common.h"
+
+#include "stdbool.h" // NOLINT(modernize-deprecated-headers)
+
+struct gluau_Span {
+	unsigned int start_line;
+	unsigned int start_column;
+	unsigned int end_line;
+	unsigned int end_column;
+};
+
+// Identifies what went wrong independently of the wording of the message. New
+// codes are only ever added at the end, so the values are stable.
+enum gluau_ErrorCode : uint16_t {
+	ERROR_CODE_UNKNOWN,
+	// Luau failed internally, for example by running out of memory
+	ERROR_CODE_INTERNAL,
+	ERROR_CODE_UNEXPECTED_TOKEN,
+	// the related location is where the block or bracket was opened
+	ERROR_CODE_UNCLOSED_BLOCK,
+	ERROR_CODE_INCOMPLETE_STATEMENT,
+	ERROR_CODE_AMBIGUOUS_SYNTAX,
+	ERROR_CODE_MALFORMED_NUMBER,
+	ERROR_CODE_MALFORMED_STRING,
+	ERROR_CODE_UNFINISHED_COMMENT,
+	ERROR_CODE_UNEXPECTED_CHARACTER,
+	ERROR_CODE_INVALID_VARARG,
+	ERROR_CODE_RECURSION_LIMIT,
+	ERROR_CODE_REGISTER_LIMIT,
+	ERROR_CODE_UPVALUE_LIMIT,
+	ERROR_CODE_LOCAL_LIMIT,
+	ERROR_CODE_CONSTANT_LIMIT
+};
+
+// Code related to an error, such as the start of a block that was never closed.
+// The message is only allocated if the location is present.
+struct gluau_Related {
+	enum gluau_Optionality presence;
+	struct gluau_Span span;
+	struct gluau_Buffer message;
+};
+
+struct gluau_Error {
+	struct gluau_Buffer message;
+	struct gluau_Span span;
+	enum gluau_ErrorCode code;
+	struct gluau_Related related;
+};
+
+struct gluau_Errors {
+	struct gluau_Error* errors;
+	size_t len;
+};
+
+struct gluau_ParseOpts {
+	bool allowDeclarationSyntax;
+	bool captureComments;
+};
+
+// The AST is serialized into a buffer, since walking Luau's class hierarchy from
+// Rust isn't feasible. See ast.cpp for the format. If serialization fails, the
+// AST buffer is null and the reason is reported as an error.
+struct gluau_ParseResult {
+	struct gluau_Buffer ast;
+	struct gluau_Errors errors;
+};
+
+GLUE_API struct gluau_ParseResult gluau_parse(struct gluau_Buffer source, struct gluau_ParseOpts parse_opts);
+
+// Returns the ASCII character that a Unicode code point looks like, or null if
+// it does not look like one. The string is static and must not be freed.
+GLUE_API const char* gluau_find_confusable(uint32_t codepoint);
+
+enum gluau_TokenKind : uint8_t {
+	TOKEN_EOF,
+	// a single character, stored in the token's data
+	TOKEN_CHAR,
+	TOKEN_EQUAL,
+	TOKEN_LESS_EQUAL,
+	TOKEN_GREATER_EQUAL,
+	TOKEN_NOT_EQUAL,
+	TOKEN_DOT2,
+	TOKEN_DOT3,
+	TOKEN_SKINNY_ARROW,
+	TOKEN_DOUBLE_COLON,
+	TOKEN_FLOOR_DIV,
+	TOKEN_INTERP_STRING_BEGIN,
+	TOKEN_INTERP_STRING_MID,
+	TOKEN_INTERP_STRING_END,
+	TOKEN_INTERP_STRING_SIMPLE,
+	TOKEN_ADD_ASSIGN,
+	TOKEN_SUB_ASSIGN,
+	TOKEN_MUL_ASSIGN,
+	TOKEN_DIV_ASSIGN,
+	TOKEN_FLOOR_DIV_ASSIGN,
+	TOKEN_MOD_ASSIGN,
+	TOKEN_POW_ASSIGN,
+	TOKEN_CONCAT_ASSIGN,
+	TOKEN_RAW_STRING,
+	TOKEN_QUOTED_STRING,
+	TOKEN_NUMBER,
+	TOKEN_NAME,
+	TOKEN_COMMENT,
+	TOKEN_BLOCK_COMMENT,
+	TOKEN_ATTRIBUTE,
+	TOKEN_BROKEN_STRING,
+	TOKEN_BROKEN_COMMENT,
+	TOKEN_BROKEN_UNICODE,
+	TOKEN_BROKEN_INTERP_DOUBLE_BRACE,
+	TOKEN_ERROR,
+	// a reserved word, whose index in alphabetical order is stored in the
+	// token's data
+	TOKEN_KEYWORD,
+	// a token added to Luau after this glue was written
+	TOKEN_UNKNOWN
+};
+
+struct gluau_Token {
+	enum gluau_TokenKind kind;
+	uint8_t data;
+	struct gluau_Span span;
+};
+
+// Wraps a Luau::Lexer along with the name table it needs. The source buffer
+// must outlive the lexer.
+struct gluau_Lexer;
+
+GLUE_API struct gluau_Lexer* gluau_lexer_new(struct gluau_Buffer source);
+GLUE_API struct gluau_Token gluau_lexer_next(struct gluau_Lexer* lexer);
+GLUE_API void gluau_lexer_free(struct gluau_Lexer* lexer);
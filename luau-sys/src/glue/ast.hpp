@@ -0,0 +1,30 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#pragma once
+
+#include "ast.h"
+
+#include <Luau/Confusables.h>
+#include <Luau/Lexer.h>
+#include <Luau/Location.h>
+#include <Luau/ParseOptions.h>
+#include <Luau/ParseResult.h>
+
+Luau::ParseOptions gluauA_parseopt2luau(const gluau_ParseOpts &opts);
+gluau_Span gluauA_loctospan(const Luau::Location &location);
+gluau_ErrorCode gluauA_classify(const std::string &message);
+gluau_Error gluauA_error(gluau_ErrorCode code, const std::string &message, const Luau::Location &location);
+gluau_Errors gluauA_errors(const std::vector<Luau::ParseError> &errors, const char* source, size_t source_len);
@@ -43,6 +43,11 @@ struct gluau_OptionalFValue {
 	gluau_FValue value;
 };
 
+struct gluau_OptionalBuffer {
+	enum gluau_Optionality presence;
+	struct gluau_Buffer value;
+};
+
 GLUE_API struct gluau_OptionalFValue gluau_find_fflag(struct gluau_Buffer name);
 GLUE_API struct gluau_OptionalFValue gluau_find_fint(struct gluau_Buffer name);
 
@@ -15,6 +15,7 @@
 
 #include "compiler.h"
 #include "common.hpp"
+#include "ast.hpp"
 
 #include <Luau/BytecodeBuilder.h>
 #include <Luau/Compiler.h>
@@ -31,21 +32,42 @@ Luau::CompileOptions gluaC_compopt2luau(const gluau_CompileOpts &opts) {
 	};
 }
 
-Luau::ParseOptions gluaC_parseopt2luau(const gluau_ParseOpts &opts) {
-	return {
-		.allowDeclarationSyntax = opts.allowDeclarationSyntax,
-		.captureComments = opts.captureComments
-	};
-}
+template<typename Success>
+	gluau_CompileResult gluauC_compile(
+		Luau::BytecodeBuilder &bcb,
+		const std::string &source,
+		gluau_CompileOpts compile_opts,
+		gluau_ParseOpts parse_opts,
+		Success success
+	) {
+		try {
+			Luau::compileOrThrow(
+				bcb, source, gluaC_compopt2luau(compile_opts),
+				gluauA_parseopt2luau(parse_opts)
+			);
 
-gluau_Span gluauC_loctospan(const Luau::Location &location) {
-	return {
-		.start_line = location.begin.line,
-		.start_column = location.begin.column,
-		.end_line = location.end.line,
-		.end_column = location.end.column
-	};
-}
+			return {
+				.type = gluau_CompileResultType::SUCCESS,
+				.data {
+					.success = success()
+				}
+			};
+		} catch (Luau::ParseErrors &container) {
+			return {
+				.type = gluau_CompileResultType::PARSE_FAILURE,
+				.data = {
+					.parse_failure = gluauA_errors(container.getErrors(), source.data(), source.size())
+				}
+			};
+		} catch (Luau::CompileError &error) {
+			return {
+				.type = gluau_CompileResultType::COMPILE_FAILURE,
+				.data = {
+					.compile_failure = gluauA_error(gluauA_classify(error.what()), error.what(), error.getLocation())
+				}
+			};
+		}
+	}
 
 GLUE_API gluau_CompileResult gluau_compile(
 	gluau_Buffer source,
@@ -55,52 +77,58 @@ GLUE_API gluau_CompileResult gluau_compile(
 	Luau::BytecodeBuilder bcb;
 	std::string source_string(source.data, source.len);
 
-	try {
-		Luau::compileOrThrow(
-			bcb, source_string, gluaC_compopt2luau(compile_opts),
-			gluaC_parseopt2luau(parse_opts)
-		);
-
+	return gluauC_compile(bcb, source_string, compile_opts, parse_opts, [&bcb]() -> gluau_CompileSuccess {
 		return {
-			.type = gluau_CompileResultType::SUCCESS,
-			.data {
-				.success = {
-					.bytecode = {gluauU_strtobuf(bcb.getBytecode())}
-				}
-			}
+			.bytecode = gluauU_strtobuf(bcb.getBytecode()),
+			.text = {.presence = gluau_Optionality::None},
+			.remarks = {.presence = gluau_Optionality::None}
 		};
-	} catch (Luau::ParseErrors &container) {
-		auto len = container.getErrors().size();
-		auto* errors = new gluau_Error[len];
-
-		unsigned int i = 0;
-		for (const auto &error: container.getErrors()) {
-			errors[i++] = {
-				.message = gluauU_strtobuf(error.getMessage()),
-				.span = gluauC_loctospan(error.getLocation())
-			};
-		}
+	});
+}
 
+GLUE_API gluau_CompileResult gluau_compile_with_output(
+	gluau_Buffer source,
+	gluau_CompileOpts compile_opts,
+	gluau_ParseOpts parse_opts,
+	gluau_OutputOpts output_opts
+) {
+	Luau::BytecodeBuilder bcb;
+	std::string source_string(source.data, source.len);
+
+	// source annotations are part of the text dump, so they imply it
+	bool text = output_opts.text || output_opts.sourceAnnotations;
+	uint32_t flags = 0;
+
+	if (text) {
+		flags |= Luau::BytecodeBuilder::Dump_Code | Luau::BytecodeBuilder::Dump_Locals;
+	}
+
+	if (output_opts.sourceAnnotations) {
+		flags |= Luau::BytecodeBuilder::Dump_Source;
+	}
+
+	if (output_opts.remarks) {
+		flags |= Luau::BytecodeBuilder::Dump_Remarks;
+	}
+
+	bcb.setDumpFlags(flags);
+
+	// remarks are always interleaved with the source, even without annotations
+	if (output_opts.sourceAnnotations || output_opts.remarks) {
+		bcb.setDumpSource(source_string);
+	}
+
+	return gluauC_compile(bcb, source_string, compile_opts, parse_opts, [&bcb, output_opts, text]() -> gluau_CompileSuccess {
 		return {
-			.type = gluau_CompileResultType::PARSE_FAILURE,
-			.data = {
-				.parse_failure = {
-					.errors = errors,
-					.len = len
-				}
-			}
-		};
-	} catch (Luau::CompileError &error) {
-		return {
-			.type = gluau_CompileResultType::COMPILE_FAILURE,
-			.data = {
-				.compile_failure = {
-					.message = gluauU_strtobuf(error.what()),
-					.span = gluauC_loctospan(error.getLocation())
-				}
-			}
+			.bytecode = gluauU_strtobuf(bcb.getBytecode()),
+			.text = text
+				? gluau_OptionalBuffer {gluau_Optionality::Some, gluauU_strtobuf(bcb.dumpEverything())}
+				: gluau_OptionalBuffer {gluau_Optionality::None},
+			.remarks = output_opts.remarks
+				? gluau_OptionalBuffer {gluau_Optionality::Some, gluauU_strtobuf(bcb.dumpSourceRemarks())}
+				: gluau_OptionalBuffer {gluau_Optionality::None}
 		};
-	}
+	});
 }
 
 GLUE_API gluau_Buffer gluau_compile_sneakily(
@@ -112,7 +140,7 @@ GLUE_API gluau_Buffer gluau_compile_sneakily(
 		Luau::compile(
 			std::string(source.data, source.len),
 			gluaC_compopt2luau(compile_opts),
-			gluaC_parseopt2luau(parse_opts)
+			gluauA_parseopt2luau(parse_opts)
 		)
 	);
 }
@@ -16,6 +16,7 @@
 #pragma once
 
 #include "common.h"
+#include "ast.h"
 
 #include "stdbool.h" // NOLINT(modernize-deprecated-headers)
 
@@ -27,23 +28,9 @@ enum gluau_CompileResultType {
 
 struct gluau_CompileSuccess {
 	struct gluau_Buffer bytecode;
-};
-
-struct gluau_Span {
-	unsigned int start_line;
-	unsigned int start_column;
-	unsigned int end_line;
-	unsigned int end_column;
-};
-
-struct gluau_Error {
-	struct gluau_Buffer message;
-	struct gluau_Span span;
-};
-
-struct gluau_Errors {
-	struct gluau_Error* errors;
-	size_t len;
+	// only present when requested through gluau_compile_with_output
+	struct gluau_OptionalBuffer text;
+	struct gluau_OptionalBuffer remarks;
 };
 
 union gluau_CompileUnion {
@@ -66,10 +53,12 @@ struct gluau_CompileOpts {
 	const char** mutableGlobals;
 };
 
-struct gluau_ParseOpts {
-	bool allowDeclarationSyntax;
-	bool captureComments;
+struct gluau_OutputOpts {
+	bool text;
+	bool remarks;
+	bool sourceAnnotations;
 };
 
 GLUE_API struct gluau_CompileResult gluau_compile(struct gluau_Buffer source, struct gluau_CompileOpts compile_opts, struct gluau_ParseOpts parse_opts);
+GLUE_API struct gluau_CompileResult gluau_compile_with_output(struct gluau_Buffer source, struct gluau_CompileOpts compile_opts, struct gluau_ParseOpts parse_opts, struct gluau_OutputOpts output_opts);
 GLUE_API struct gluau_Buffer gluau_compile_sneakily(struct gluau_Buffer source, struct gluau_CompileOpts compile_opts, struct gluau_ParseOpts parse_opts);
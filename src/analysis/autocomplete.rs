@@ -0,0 +1,91 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use luau_sys::glue::{gluau_Buffer, gluau_Completion, gluau_CompletionKind};
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum CompletionKind {
+	Property,
+	/// A property whose type is a function.
+	Method,
+	Variable,
+	/// A variable whose type is a function.
+	Function,
+	Keyword,
+	/// A string literal, such as an option accepted by the called function.
+	String,
+	Type,
+	Module,
+	/// A whole function definition generated from the expected type, such as
+	/// for a callback argument.
+	GeneratedFunction,
+	RequirePath
+}
+
+impl CompletionKind {
+	fn from_gluau(kind: gluau_CompletionKind) -> Self {
+		match kind {
+			gluau_CompletionKind::COMPLETION_PROPERTY => Self::Property,
+			gluau_CompletionKind::COMPLETION_METHOD => Self::Method,
+			gluau_CompletionKind::COMPLETION_VARIABLE => Self::Variable,
+			gluau_CompletionKind::COMPLETION_FUNCTION => Self::Function,
+			gluau_CompletionKind::COMPLETION_KEYWORD => Self::Keyword,
+			gluau_CompletionKind::COMPLETION_STRING => Self::String,
+			gluau_CompletionKind::COMPLETION_TYPE => Self::Type,
+			gluau_CompletionKind::COMPLETION_MODULE => Self::Module,
+			gluau_CompletionKind::COMPLETION_GENERATED_FUNCTION => Self::GeneratedFunction,
+			gluau_CompletionKind::COMPLETION_REQUIRE_PATH => Self::RequirePath
+		}
+	}
+}
+
+/// Something that can be typed at the position passed to
+/// [`Frontend::autocomplete`](crate::analysis::Frontend::autocomplete).
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CompletionItem {
+	pub label: String,
+	pub kind: CompletionKind,
+	/// The type of the completion, formatted like in type errors.
+	pub ty: Option<String>,
+	/// Identifies the documentation of builtins and definitions, like
+	/// `@luau/global/print`.
+	pub documentation_symbol: Option<String>,
+	/// The text to insert, if it is not the label.
+	pub insert_text: Option<String>,
+	pub deprecated: bool
+}
+
+/// Takes ownership of a string allocated by the glue, if there is one.
+unsafe fn string_from_gluau(buffer: gluau_Buffer) -> Option<String> {
+	if buffer.data.is_null() {
+		return None
+	}
+
+	Some(String::from_raw_parts(buffer.data as _, buffer.len as _, buffer.len as _))
+}
+
+pub(crate) fn completion_from_gluau(gluau: gluau_Completion) -> CompletionItem {
+	unsafe {
+		CompletionItem {
+			// SAFETY: the buffers were allocated by the glue from strings
+			label: string_from_gluau(gluau.label).unwrap_or_default(),
+			kind: CompletionKind::from_gluau(gluau.kind),
+			ty: string_from_gluau(gluau.type_),
+			documentation_symbol: string_from_gluau(gluau.documentation_symbol),
+			insert_text: string_from_gluau(gluau.insert_text),
+			deprecated: gluau.deprecated
+		}
+	}
+}
@@ -0,0 +1,364 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::analysis::{LintCode, LintOptions};
+use crate::analysis::resolver::absolute;
+use crate::ast::Mode;
+
+/// The file name that Luau looks for in every directory above a module.
+pub const CONFIG_FILE: &str = ".luaurc";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+	#[error("{}: {error}", path.display())]
+	Io { path: PathBuf, error: std::io::Error },
+
+	#[error("{}: {message}", path.display())]
+	Parse { path: PathBuf, message: String }
+}
+
+/// Project configuration, as read from `.luaurc` files. These are JSON
+/// objects, optionally with comments and trailing commas:
+///
+/// ```text
+/// {
+///     "languageMode": "strict",
+///     "lint": { "*": true, "LocalUnused": false },
+///     "lintErrors": false,
+///     "typeErrors": true,
+///     "globals": ["game", "script"],
+///     "aliases": { "shared": "./src/shared" }
+/// }
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Config {
+	/// The mode that modules are checked in if they don't select one
+	/// themselves. See [`Frontend::set_config`](crate::analysis::Frontend::set_config).
+	pub mode: Mode,
+	pub lint: LintOptions,
+	/// Whether lint warnings should be treated as errors.
+	pub lint_errors: bool,
+	/// Whether type errors should be reported.
+	pub type_errors: bool,
+	/// Extra globals that scripts can use, which are typed as `any`.
+	pub globals: Vec<String>,
+	/// Maps the aliases usable in `require("@alias/path")` to the directories
+	/// they refer to. Paths are written relative to the `.luaurc` that declared
+	/// them, and stored as absolute paths. Aliases are case insensitive, so
+	/// they are stored in lowercase. See
+	/// [`FsResolver::set_aliases`](crate::analysis::FsResolver::set_aliases).
+	pub aliases: HashMap<String, PathBuf>
+}
+
+impl Default for Config {
+	fn default() -> Self {
+		Self {
+			mode: Mode::Nonstrict,
+			lint: LintOptions::default(),
+			lint_errors: false,
+			type_errors: true,
+			globals: Vec::new(),
+			aliases: HashMap::new()
+		}
+	}
+}
+
+impl Config {
+	/// Loads the configuration that applies to a file or directory, by merging
+	/// every `.luaurc` in the directories above it, from the outermost to the
+	/// innermost. Settings in inner files override outer ones, lints and
+	/// aliases are overridden one by one, and globals are added together.
+	pub fn load_for(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+		let path = absolute(path.as_ref());
+		let dir = if path.is_dir() { &path } else { path.parent().unwrap_or(&path) };
+
+		let mut dirs: Vec<&Path> = dir.ancestors().collect();
+		dirs.reverse();
+
+		let mut config = Self::default();
+
+		for dir in dirs {
+			let file = dir.join(CONFIG_FILE);
+
+			let source = match std::fs::read_to_string(&file) {
+				Ok(source) => source,
+				Err(error) if error.kind() == std::io::ErrorKind::NotFound => continue,
+				Err(error) => return Err(ConfigError::Io { path: file, error })
+			};
+
+			config.apply(&source, dir).map_err(|message| ConfigError::Parse { path: file, message })?;
+		}
+
+		Ok(config)
+	}
+
+	/// Applies the settings in the source of a `.luaurc` on top of this
+	/// configuration. Aliases are resolved relative to `dir`, which is made
+	/// absolute if it isn't already.
+	pub fn apply(&mut self, source: &str, dir: &Path) -> Result<(), String> {
+		let Value::Object(entries) = Parser::new(source).document()? else {
+			return Err("expected an object".to_owned())
+		};
+
+		for (key, value) in entries {
+			match (key.as_str(), value) {
+				("languageMode", Value::String(mode)) => self.mode = match mode.as_str() {
+					"strict" => Mode::Strict,
+					"nonstrict" => Mode::Nonstrict,
+					"nocheck" => Mode::NoCheck,
+					_ => return Err(format!("bad mode \"{}\", valid modes are nocheck, nonstrict and strict", mode))
+				},
+
+				("lint", Value::Object(lints)) => for (name, enabled) in lints {
+					let Value::Bool(enabled) = enabled else { return Err(format!("bad setting for lint {}, expected a boolean", name)) };
+
+					if name == "*" {
+						self.lint = if enabled { LintOptions::default() } else { LintOptions::none() };
+					} else {
+						let code = LintCode::from_name(&name).ok_or_else(|| format!("unknown lint {}", name))?;
+						self.lint.set_enabled(code, enabled);
+					}
+				}
+
+				("lintErrors", Value::Bool(enabled)) => self.lint_errors = enabled,
+				("typeErrors", Value::Bool(enabled)) => self.type_errors = enabled,
+
+				("globals", Value::Array(globals)) => for global in globals {
+					let Value::String(global) = global else { return Err("bad global, expected a string".to_owned()) };
+					self.globals.push(global);
+				}
+
+				("aliases", Value::Object(aliases)) => for (name, path) in aliases {
+					let Value::String(path) = path else { return Err(format!("bad path for alias {}, expected a string", name)) };
+
+					if name.is_empty() || !name.chars().all(|char| char.is_ascii_alphanumeric() || "-_.".contains(char)) {
+						return Err(format!("invalid alias name {}", name))
+					}
+
+					self.aliases.insert(name.to_ascii_lowercase(), absolute(&dir.join(path)));
+				}
+
+				("languageMode" | "lint" | "lintErrors" | "typeErrors" | "globals" | "aliases", _) => {
+					return Err(format!("bad value for {}", key))
+				}
+
+				_ => return Err(format!("unknown key {}", key))
+			}
+		}
+
+		Ok(())
+	}
+}
+
+enum Value {
+	Null,
+	Bool(bool),
+	Number,
+	String(String),
+	Array(Vec<Value>),
+	Object(Vec<(String, Value)>)
+}
+
+/// Parses JSON as leniently as Luau does, allowing `//` and `--` comments as
+/// well as trailing commas.
+struct Parser<'a> {
+	source: &'a str,
+	offset: usize
+}
+
+impl<'a> Parser<'a> {
+	fn new(source: &'a str) -> Self {
+		Self { source, offset: 0 }
+	}
+
+	fn rest(&self) -> &'a str {
+		&self.source[self.offset..]
+	}
+
+	fn error(&self, message: &str) -> String {
+		let line = self.source[..self.offset].matches('\n').count() + 1;
+		format!("line {}: {}", line, message)
+	}
+
+	fn skip_trivia(&mut self) {
+		loop {
+			let rest = self.rest();
+			let trimmed = rest.trim_start();
+			self.offset += rest.len() - trimmed.len();
+
+			if trimmed.starts_with("//") || trimmed.starts_with("--") {
+				self.offset += trimmed.find('\n').unwrap_or(trimmed.len());
+			} else {
+				break
+			}
+		}
+	}
+
+	fn eat(&mut self, token: &str) -> bool {
+		self.skip_trivia();
+
+		if self.rest().starts_with(token) {
+			self.offset += token.len();
+			true
+		} else {
+			false
+		}
+	}
+
+	fn document(&mut self) -> Result<Value, String> {
+		let value = self.value()?;
+		self.skip_trivia();
+
+		if !self.rest().is_empty() {
+			return Err(self.error("expected end of file"))
+		}
+
+		Ok(value)
+	}
+
+	fn value(&mut self) -> Result<Value, String> {
+		self.skip_trivia();
+
+		if self.eat("{") {
+			let mut entries = Vec::new();
+
+			loop {
+				if self.eat("}") {
+					break
+				}
+
+				self.skip_trivia();
+				let key = self.string()?;
+
+				if !self.eat(":") {
+					return Err(self.error("expected ':'"))
+				}
+
+				entries.push((key, self.value()?));
+
+				if self.eat("}") {
+					break
+				} else if !self.eat(",") {
+					return Err(self.error("expected ',' or '}'"))
+				}
+			}
+
+			Ok(Value::Object(entries))
+		} else if self.eat("[") {
+			let mut values = Vec::new();
+
+			loop {
+				if self.eat("]") {
+					break
+				}
+
+				values.push(self.value()?);
+
+				if self.eat("]") {
+					break
+				} else if !self.eat(",") {
+					return Err(self.error("expected ',' or ']'"))
+				}
+			}
+
+			Ok(Value::Array(values))
+		} else if self.rest().starts_with('"') {
+			self.string().map(Value::String)
+		} else if self.eat("true") {
+			Ok(Value::Bool(true))
+		} else if self.eat("false") {
+			Ok(Value::Bool(false))
+		} else if self.eat("null") {
+			Ok(Value::Null)
+		} else {
+			let rest = self.rest();
+			let len = rest.find(|char: char| !(char.is_ascii_alphanumeric() || "+-.".contains(char))).unwrap_or(rest.len());
+
+			if len == 0 || rest[..len].parse::<f64>().is_err() {
+				return Err(self.error("expected a value"))
+			}
+
+			self.offset += len;
+			Ok(Value::Number)
+		}
+	}
+
+	fn string(&mut self) -> Result<String, String> {
+		if !self.rest().starts_with('"') {
+			return Err(self.error("expected a string"))
+		}
+
+		self.offset += 1;
+		let mut string = String::new();
+		let mut chars = self.rest().char_indices();
+
+		while let Some((index, char)) = chars.next() {
+			match char {
+				'"' => {
+					self.offset += index + 1;
+					return Ok(string)
+				}
+
+				'\\' => match chars.next() {
+					Some((_, 'n')) => string.push('\n'),
+					Some((_, 't')) => string.push('\t'),
+					Some((_, 'r')) => string.push('\r'),
+					Some((_, 'b')) => string.push('\u{8}'),
+					Some((_, 'f')) => string.push('\u{c}'),
+					Some((_, escaped @ ('"' | '\\' | '/'))) => string.push(escaped),
+
+					Some((_, 'u')) => {
+						let unit = Self::code_unit(&mut chars).ok_or_else(|| self.error("bad unicode escape"))?;
+
+						// characters outside the BMP are escaped as UTF-16 surrogate pairs
+						let char = if (0xd800..0xdc00).contains(&unit) {
+							let low = match (chars.next(), chars.next()) {
+								(Some((_, '\\')), Some((_, 'u'))) => Self::code_unit(&mut chars).filter(|low| (0xdc00..0xe000).contains(low)),
+								_ => None
+							}.ok_or_else(|| self.error("unpaired surrogate in unicode escape"))?;
+
+							char::from_u32(0x10000 + ((unit - 0xd800) << 10) + (low - 0xdc00))
+						} else {
+							char::from_u32(unit)
+						};
+
+						string.push(char.ok_or_else(|| self.error("unpaired surrogate in unicode escape"))?);
+					}
+
+					_ => return Err(self.error("unsupported escape sequence"))
+				},
+
+				'\n' => break,
+				char => string.push(char)
+			}
+		}
+
+		Err(self.error("unterminated string"))
+	}
+
+	/// Reads the four hex digits of a `\u` escape.
+	fn code_unit(chars: &mut impl Iterator<Item = (usize, char)>) -> Option<u32> {
+		let mut unit = 0;
+
+		for _ in 0..4 {
+			unit = unit * 16 + chars.next()?.1.to_digit(16)?;
+		}
+
+		Some(unit)
+	}
+}
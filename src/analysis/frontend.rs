@@ -0,0 +1,473 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::any::Any;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::c_void;
+use std::panic::{AssertUnwindSafe, catch_unwind, resume_unwind};
+use std::ptr::NonNull;
+
+use luau_sys::glue::{gluau_Buffer, gluau_FileResolver, gluau_Frontend, gluau_frontend_autocomplete, gluau_frontend_check, gluau_frontend_definition_of, gluau_frontend_free, gluau_frontend_lint, gluau_frontend_load_definitions, gluau_frontend_mark_dirty, gluau_frontend_module_graph, gluau_frontend_new, gluau_frontend_set_globals, gluau_frontend_set_mode, gluau_frontend_type_at, gluau_RequireExpr, gluau_Span};
+
+use crate::analysis::{type_error_from_gluau, Config, ModuleName, TypeError};
+use crate::analysis::autocomplete::{completion_from_gluau, CompletionItem};
+use crate::analysis::graph::{graph_from_gluau, names_from_gluau, ModuleGraph};
+use crate::analysis::lint::{lint_warning_from_gluau, LintOptions, LintWarning};
+use crate::analysis::resolver::{FileResolver, MemoryResolver, RequireExpr, Source, SourceKind};
+use crate::ast::{Mode, Position, Span};
+use crate::fvalue;
+
+/// Owns the resolver on behalf of the glue, which calls into it through the
+/// callbacks below while checking.
+struct Bridge<R> {
+	resolver: R,
+	// the glue copies these out before the next callback
+	source: String,
+	name: ModuleName,
+	panic: Option<Box<dyn Any + Send>>
+}
+
+impl<R: FileResolver> Bridge<R> {
+	/// Calls into the resolver, catching any panic so that it doesn't unwind
+	/// into C++. Once the resolver has panicked, it isn't called again.
+	fn guard<T>(&mut self, call: impl FnOnce(&R) -> Option<T>) -> Option<T> {
+		if self.panic.is_some() {
+			return None
+		}
+
+		match catch_unwind(AssertUnwindSafe(|| call(&self.resolver))) {
+			Ok(result) => result,
+
+			Err(panic) => {
+				self.panic = Some(panic);
+				None
+			}
+		}
+	}
+
+	fn resume_panic(&mut self) {
+		if let Some(panic) = self.panic.take() {
+			resume_unwind(panic)
+		}
+	}
+}
+
+fn str2buf(str: &str) -> gluau_Buffer {
+	gluau_Buffer {
+		data: str.as_ptr() as _,
+		len: str.len() as _
+	}
+}
+
+/// Borrows a buffer passed in by the glue, which is only valid for the
+/// duration of the callback.
+unsafe fn buf2str<'a>(buffer: gluau_Buffer) -> Option<&'a str> {
+	if buffer.data.is_null() {
+		return Some("")
+	}
+
+	std::str::from_utf8(std::slice::from_raw_parts(buffer.data as *const u8, buffer.len as _)).ok()
+}
+
+unsafe extern "C" fn read_source<R: FileResolver>(data: *mut c_void, name: gluau_Buffer, source: *mut gluau_Buffer, kind: *mut u8) -> bool {
+	let bridge = &mut *(data as *mut Bridge<R>);
+	let Some(name) = buf2str(name) else { return false };
+	let Some(found) = bridge.guard(|resolver| resolver.read_source(name)) else { return false };
+
+	// numbered like Luau::SourceCode::Type
+	*kind = match found.kind {
+		SourceKind::Module => 1,
+		SourceKind::Script => 2
+	};
+
+	bridge.source = found.source;
+	*source = str2buf(&bridge.source);
+	true
+}
+
+unsafe extern "C" fn resolve_module<R: FileResolver>(data: *mut c_void, from: gluau_Buffer, expr: gluau_RequireExpr, name: *mut gluau_Buffer) -> bool {
+	let bridge = &mut *(data as *mut Bridge<R>);
+	let Some(from) = buf2str(from) else { return false };
+
+	let parts: Option<Vec<&str>> = std::slice::from_raw_parts(expr.parts, expr.len as _)
+		.iter()
+		.map(|part| buf2str(*part))
+		.collect();
+
+	let Some(parts) = parts else { return false };
+
+	let expr = match (expr.string, parts.as_slice()) {
+		(true, [string]) => RequireExpr::String(string),
+		(false, path) => RequireExpr::Path(path),
+		_ => return false
+	};
+
+	let Some(resolved) = bridge.guard(|resolver| resolver.resolve_module(from, expr)) else { return false };
+
+	bridge.name = resolved;
+	*name = str2buf(&bridge.name);
+	true
+}
+
+fn mode_to_gluau(mode: Mode) -> u8 {
+	match mode {
+		Mode::NoCheck => 0,
+		Mode::Nonstrict => 1,
+		Mode::Strict => 2,
+		Mode::Definition => 3
+	}
+}
+
+/// Type checks modules using Luau's `Frontend`, which caches the result of
+/// checking each module until its source changes. Modules are found through a
+/// [`FileResolver`], which defaults to a [`MemoryResolver`].
+///
+/// Modules are checked in the mode given to the frontend, unless they start
+/// with a `--!strict`, `--!nonstrict` or `--!nocheck` hot comment.
+pub struct Frontend<R: FileResolver = MemoryResolver> {
+	raw: NonNull<gluau_Frontend>,
+	// owned by the frontend, but the glue holds a pointer to it as well
+	bridge: NonNull<Bridge<R>>,
+	mode: Mode,
+	lint: LintOptions,
+	lint_errors: bool,
+	type_errors: bool,
+	dirty: BTreeSet<ModuleName>
+}
+
+impl Frontend<MemoryResolver> {
+	pub fn new(mode: Mode) -> Self {
+		Self::with_resolver(MemoryResolver::new(), mode)
+	}
+
+	/// Adds a module, or replaces the source of an existing one. Other modules
+	/// can refer to it with `require("name")`.
+	pub fn set_source(&mut self, name: impl Into<ModuleName>, source: impl Into<String>) -> &mut Self {
+		let name = name.into();
+		self.mark_dirty(&name);
+
+		// SAFETY: the glue only uses the bridge while the frontend is being
+		// used, which requires &mut self
+		unsafe { self.bridge.as_mut() }.resolver.set_source(name, Source::module(source));
+		self
+	}
+
+	/// Removes a module. Modules that require it are checked again, while the
+	/// module itself no longer needs to be.
+	pub fn remove_source(&mut self, name: &str) -> &mut Self {
+		self.mark_dirty(name);
+		self.dirty.remove(name);
+
+		// SAFETY: see set_source
+		unsafe { self.bridge.as_mut() }.resolver.remove(name);
+		self
+	}
+}
+
+impl<R: FileResolver> Frontend<R> {
+	pub fn with_resolver(resolver: R, mode: Mode) -> Self {
+		let bridge = NonNull::from(Box::leak(Box::new(Bridge {
+			resolver,
+			source: String::new(),
+			name: ModuleName::new(),
+			panic: None
+		})));
+
+		let resolver = gluau_FileResolver {
+			data: bridge.as_ptr() as _,
+			read_source: Some(read_source::<R>),
+			resolve_module: Some(resolve_module::<R>)
+		};
+
+		let _flags = fvalue::read_lock();
+
+		// SAFETY: the bridge outlives the frontend, which is freed on drop
+		let raw = unsafe { gluau_frontend_new(resolver, mode_to_gluau(mode)) };
+
+		Self {
+			raw: NonNull::new(raw).expect("couldn't create frontend"),
+			bridge,
+			mode,
+			lint: LintOptions::default(),
+			lint_errors: false,
+			type_errors: true,
+			dirty: BTreeSet::new()
+		}
+	}
+
+	/// Sets the mode that modules are checked in if they don't select one
+	/// themselves. Every module will be checked again.
+	pub fn set_mode(&mut self, mode: Mode) -> &mut Self {
+		self.mode = mode;
+
+		let _flags = fvalue::read_lock();
+		unsafe { gluau_frontend_set_mode(self.raw.as_ptr(), mode_to_gluau(mode)) };
+		self
+	}
+
+	/// Applies a [`Config`]. Its mode and extra globals are used when checking,
+	/// and type errors aren't reported by [`Self::check`] if it disables them.
+	/// Its lint options are kept for [`Self::lint_options`] and
+	/// [`Self::lint_errors`], and the resolver gets it through
+	/// [`FileResolver::apply_config`]. Every module will be checked again.
+	pub fn set_config(&mut self, config: &Config) -> &mut Self {
+		// SAFETY: the glue only uses the bridge while the frontend is being
+		// used, which requires &mut self
+		unsafe { self.bridge.as_mut() }.resolver.apply_config(config);
+
+		let globals: Vec<gluau_Buffer> = config.globals.iter().map(|global| str2buf(global)).collect();
+
+		{
+			let _flags = fvalue::read_lock();
+			unsafe { gluau_frontend_set_globals(self.raw.as_ptr(), globals.as_ptr(), globals.len() as _) };
+		}
+
+		self.lint = config.lint;
+		self.lint_errors = config.lint_errors;
+		self.type_errors = config.type_errors;
+		self.set_mode(config.mode)
+	}
+
+	pub fn mode(&self) -> Mode {
+		self.mode
+	}
+
+	/// The lints enabled by the last [`Config`] passed to [`Self::set_config`],
+	/// or every lint if there wasn't one.
+	pub fn lint_options(&self) -> LintOptions {
+		self.lint
+	}
+
+	/// Whether the last [`Config`] passed to [`Self::set_config`] asked for lint
+	/// warnings to be treated as errors.
+	pub fn lint_errors(&self) -> bool {
+		self.lint_errors
+	}
+
+	pub fn resolver(&self) -> &R {
+		unsafe { &self.bridge.as_ref().resolver }
+	}
+
+	/// Marks a module as changed, along with every module that depends on it,
+	/// so that they are checked again. This is needed whenever the resolver
+	/// would return a different source for the module.
+	pub fn mark_dirty(&mut self, module: &str) -> &mut Self {
+		let marked = {
+			let _flags = fvalue::read_lock();
+
+			// SAFETY: the array was allocated by the glue
+			unsafe { names_from_gluau(gluau_frontend_mark_dirty(self.raw.as_ptr(), str2buf(module))) }
+		};
+
+		// modules that have never been checked aren't known to Luau yet
+		self.dirty.insert(module.to_owned());
+		self.dirty.extend(marked);
+		self
+	}
+
+	/// The modules that have been marked dirty since they were last checked.
+	pub fn dirty_modules(&self) -> &BTreeSet<ModuleName> {
+		&self.dirty
+	}
+
+	/// Checks every dirty module, returning the errors in each of them. Other
+	/// modules are not checked again, so this is much cheaper than checking
+	/// everything after a change.
+	pub fn check_changed(&mut self) -> BTreeMap<ModuleName, Vec<TypeError>> {
+		let mut results = BTreeMap::new();
+
+		for module in std::mem::take(&mut self.dirty) {
+			let errors = self.check(&module).into_iter().filter(|error| error.module == module).collect();
+			results.insert(module, errors);
+		}
+
+		results
+	}
+
+	/// Returns the `require` edges between every module that has been checked.
+	pub fn module_graph(&self) -> ModuleGraph {
+		// SAFETY: the array was allocated by the glue
+		unsafe { graph_from_gluau(gluau_frontend_module_graph(self.raw.as_ptr())) }
+	}
+
+	/// Type checks a module along with everything it requires, returning the
+	/// errors found in all of them. Modules that haven't changed since they
+	/// were last checked are not checked again. No errors are returned if the
+	/// [`Config`] disables type errors.
+	pub fn check(&mut self, module: &str) -> Vec<TypeError> {
+		let errors = {
+			let _flags = fvalue::read_lock();
+
+			// SAFETY: C++ exceptions are caught by the C++ glue, and never unwind into Rust
+			unsafe { gluau_frontend_check(self.raw.as_ptr(), str2buf(module)) }
+		};
+
+		let errors: Vec<TypeError> = unsafe {
+			// SAFETY: the array was allocated by the glue
+			Vec::from_raw_parts(errors.errors, errors.len as _, errors.len as _)
+		}.into_iter().map(type_error_from_gluau).collect();
+
+		unsafe { self.bridge.as_mut() }.resume_panic();
+
+		// everything the module requires was checked along with it
+		let graph = self.module_graph();
+		let mut pending = vec![module];
+		let mut seen = BTreeSet::new();
+
+		while let Some(next) = pending.pop() {
+			if seen.insert(next) {
+				self.dirty.remove(next);
+				pending.extend(graph.requires(next).map(String::as_str));
+			}
+		}
+
+		if self.type_errors { errors } else { Vec::new() }
+	}
+
+	/// Returns what can be typed at a position in a module, sorted by label.
+	/// The module is checked first if needed, with extra type information kept
+	/// around for autocomplete, which is checked separately from
+	/// [`Self::check`].
+	pub fn autocomplete(&mut self, module: &str, position: Position) -> Vec<CompletionItem> {
+		let completions = {
+			let _flags = fvalue::read_lock();
+
+			// SAFETY: C++ exceptions are caught by the C++ glue, and never unwind into Rust
+			unsafe { gluau_frontend_autocomplete(self.raw.as_ptr(), str2buf(module), position.line, position.column) }
+		};
+
+		let mut completions: Vec<CompletionItem> = unsafe {
+			// SAFETY: the array was allocated by the glue
+			Vec::from_raw_parts(completions.completions, completions.len as _, completions.len as _)
+		}.into_iter().map(completion_from_gluau).collect();
+
+		unsafe { self.bridge.as_mut() }.resume_panic();
+
+		// Luau returns them in hash order
+		completions.sort_by(|a, b| a.label.cmp(&b.label));
+		completions
+	}
+
+	/// Returns the inferred type of the expression at a position in a module,
+	/// formatted like in type errors, for showing on hover. The module is
+	/// checked first if needed.
+	pub fn type_at(&mut self, module: &str, position: Position) -> Option<String> {
+		let ty = {
+			let _flags = fvalue::read_lock();
+
+			// SAFETY: C++ exceptions are caught by the C++ glue, and never unwind into Rust
+			unsafe { gluau_frontend_type_at(self.raw.as_ptr(), str2buf(module), position.line, position.column) }
+		};
+
+		unsafe { self.bridge.as_mut() }.resume_panic();
+
+		if ty.data.is_null() {
+			return None
+		}
+
+		// SAFETY: the buffer was allocated by the glue from a string
+		Some(unsafe { String::from_raw_parts(ty.data as _, ty.len as _, ty.len as _) })
+	}
+
+	/// Finds where the variable or property at a position in a module is
+	/// defined, which may be in a module it requires. Builtins have no
+	/// definition. The module is checked first if needed.
+	pub fn definition_of(&mut self, module: &str, position: Position) -> Option<(ModuleName, Span)> {
+		let mut name = gluau_Buffer { data: std::ptr::null_mut(), len: 0 };
+		let mut span = gluau_Span { start_line: 0, start_column: 0, end_line: 0, end_column: 0 };
+
+		let found = {
+			let _flags = fvalue::read_lock();
+
+			// SAFETY: C++ exceptions are caught by the C++ glue, and never unwind into Rust
+			unsafe { gluau_frontend_definition_of(self.raw.as_ptr(), str2buf(module), position.line, position.column, &mut name, &mut span) }
+		};
+
+		unsafe { self.bridge.as_mut() }.resume_panic();
+
+		if !found {
+			return None
+		}
+
+		// SAFETY: the buffer was allocated by the glue from a string
+		let name = unsafe { String::from_raw_parts(name.data as _, name.len as _, name.len as _) };
+		Some((name, Span::new(span.start_line, span.start_column, span.end_line, span.end_column)))
+	}
+
+	/// Loads a definitions file, such as a `.d.luau` file describing a host
+	/// API, declaring its globals, types and classes in every module checked
+	/// afterwards. Definitions files use `declare` syntax:
+	///
+	/// ```text
+	/// declare class Part
+	///     Name: string
+	///     function Destroy(self): ()
+	/// end
+	///
+	/// declare function spawn(name: string): Part
+	/// ```
+	///
+	/// If the file fails to parse or check, none of it is loaded and the errors
+	/// are returned. Every module will be checked again.
+	pub fn load_definitions(&mut self, name: &str, source: &str) -> Result<(), Vec<TypeError>> {
+		let errors = {
+			let _flags = fvalue::read_lock();
+
+			// SAFETY: C++ exceptions are caught by the C++ glue, and never unwind into Rust
+			unsafe { gluau_frontend_load_definitions(self.raw.as_ptr(), str2buf(name), str2buf(source)) }
+		};
+
+		let errors: Vec<TypeError> = unsafe {
+			// SAFETY: the array was allocated by the glue
+			Vec::from_raw_parts(errors.errors, errors.len as _, errors.len as _)
+		}.into_iter().map(type_error_from_gluau).collect();
+
+		if errors.is_empty() { Ok(()) } else { Err(errors) }
+	}
+
+	/// Lints a module, checking it first if needed so that lints can make use
+	/// of its types. Lints disabled by `--!nolint` hot comments in the module
+	/// are not reported. Type errors are not reported either; use
+	/// [`Self::check`] for those. Pass [`Self::lint_options`] to use the lints
+	/// selected by the [`Config`].
+	///
+	/// If Luau fails internally, the failure is reported as a single warning
+	/// with [`LintCode::Unknown`](crate::analysis::LintCode::Unknown).
+	pub fn lint(&mut self, module: &str, options: &LintOptions) -> Vec<LintWarning> {
+		let warnings = {
+			let _flags = fvalue::read_lock();
+
+			// SAFETY: C++ exceptions are caught by the C++ glue, and never unwind into Rust
+			unsafe { gluau_frontend_lint(self.raw.as_ptr(), str2buf(module), options.mask()) }
+		};
+
+		let warnings = unsafe {
+			// SAFETY: the array was allocated by the glue
+			Vec::from_raw_parts(warnings.warnings, warnings.len as _, warnings.len as _)
+		}.into_iter().map(lint_warning_from_gluau).collect();
+
+		unsafe { self.bridge.as_mut() }.resume_panic();
+		warnings
+	}
+}
+
+impl<R: FileResolver> Drop for Frontend<R> {
+	fn drop(&mut self) {
+		unsafe {
+			gluau_frontend_free(self.raw.as_ptr());
+			drop(Box::from_raw(self.bridge.as_ptr()));
+		}
+	}
+}
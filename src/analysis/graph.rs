@@ -0,0 +1,179 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use luau_sys::glue::{gluau_ModuleGraph, gluau_ModuleNames};
+
+use crate::analysis::ModuleName;
+
+/// Which modules require which, as a set of `require` edges. Modules are kept
+/// in order of their names, so iterating the graph is deterministic.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct ModuleGraph {
+	requires: BTreeMap<ModuleName, BTreeSet<ModuleName>>
+}
+
+impl ModuleGraph {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a module to the graph, if it isn't already part of it.
+	pub fn add_module(&mut self, module: impl Into<ModuleName>) -> &mut Self {
+		self.requires.entry(module.into()).or_default();
+		self
+	}
+
+	/// Records that `from` requires `to`, adding both modules to the graph.
+	pub fn add_require(&mut self, from: impl Into<ModuleName>, to: impl Into<ModuleName>) -> &mut Self {
+		let to = to.into();
+		self.add_module(to.clone());
+		self.requires.entry(from.into()).or_default().insert(to);
+		self
+	}
+
+	pub fn contains(&self, module: &str) -> bool {
+		self.requires.contains_key(module)
+	}
+
+	pub fn modules(&self) -> impl Iterator<Item = &ModuleName> {
+		self.requires.keys()
+	}
+
+	/// Returns every `(from, to)` edge, where `from` requires `to`.
+	pub fn edges(&self) -> impl Iterator<Item = (&ModuleName, &ModuleName)> {
+		self.requires.iter().flat_map(|(from, requires)| requires.iter().map(move |to| (from, to)))
+	}
+
+	/// Returns the modules that a module requires directly.
+	pub fn requires(&self, module: &str) -> impl Iterator<Item = &ModuleName> {
+		self.requires.get(module).into_iter().flatten()
+	}
+
+	/// Returns the modules that require a module directly.
+	pub fn dependents<'a>(&'a self, module: &'a str) -> impl Iterator<Item = &'a ModuleName> {
+		self.requires.iter().filter(move |(_, requires)| requires.contains(module)).map(|(from, _)| from)
+	}
+
+	/// Returns every cycle of modules that require each other, directly or
+	/// indirectly. Each cycle is a strongly connected component of the graph,
+	/// listed from its alphabetically first module. Modules that require
+	/// themselves are cycles of one.
+	pub fn cycles(&self) -> Vec<Vec<ModuleName>> {
+		let mut tarjan = Tarjan {
+			graph: self,
+			index: BTreeMap::new(),
+			lowlink: BTreeMap::new(),
+			stack: Vec::new(),
+			on_stack: BTreeSet::new(),
+			cycles: Vec::new()
+		};
+
+		for module in self.modules() {
+			if !tarjan.index.contains_key(module.as_str()) {
+				tarjan.connect(module);
+			}
+		}
+
+		let mut cycles = tarjan.cycles;
+
+		for cycle in &mut cycles {
+			cycle.sort();
+		}
+
+		cycles.sort();
+		cycles
+	}
+
+	pub fn len(&self) -> usize {
+		self.requires.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.requires.is_empty()
+	}
+}
+
+/// Takes ownership of an array of names allocated by the glue.
+pub(crate) unsafe fn names_from_gluau(names: gluau_ModuleNames) -> Vec<ModuleName> {
+	Vec::from_raw_parts(names.names, names.len as _, names.len as _).into_iter()
+		.map(|name| String::from_raw_parts(name.data as _, name.len as _, name.len as _))
+		.collect()
+}
+
+pub(crate) unsafe fn graph_from_gluau(graph: gluau_ModuleGraph) -> ModuleGraph {
+	let mut converted = ModuleGraph::new();
+
+	for node in Vec::from_raw_parts(graph.nodes, graph.len as _, graph.len as _) {
+		let name = String::from_raw_parts(node.name.data as _, node.name.len as _, node.name.len as _);
+		converted.add_module(name.clone());
+
+		for required in names_from_gluau(node.required) {
+			converted.add_require(name.clone(), required);
+		}
+	}
+
+	converted
+}
+
+/// Tarjan's strongly connected components algorithm.
+struct Tarjan<'a> {
+	graph: &'a ModuleGraph,
+	index: BTreeMap<&'a str, usize>,
+	lowlink: BTreeMap<&'a str, usize>,
+	stack: Vec<&'a str>,
+	on_stack: BTreeSet<&'a str>,
+	cycles: Vec<Vec<ModuleName>>
+}
+
+impl<'a> Tarjan<'a> {
+	fn connect(&mut self, module: &'a str) {
+		let index = self.index.len();
+		self.index.insert(module, index);
+		self.lowlink.insert(module, index);
+		self.stack.push(module);
+		self.on_stack.insert(module);
+
+		for required in self.graph.requires(module) {
+			if !self.index.contains_key(required.as_str()) {
+				self.connect(required);
+				let lowlink = self.lowlink[module].min(self.lowlink[required.as_str()]);
+				self.lowlink.insert(module, lowlink);
+			} else if self.on_stack.contains(required.as_str()) {
+				let lowlink = self.lowlink[module].min(self.index[required.as_str()]);
+				self.lowlink.insert(module, lowlink);
+			}
+		}
+
+		if self.lowlink[module] == index {
+			let mut component = Vec::new();
+
+			loop {
+				let member = self.stack.pop().unwrap();
+				self.on_stack.remove(member);
+				component.push(member.to_owned());
+
+				if member == module {
+					break
+				}
+			}
+
+			if component.len() > 1 || self.graph.requires(module).any(|required| required == module) {
+				self.cycles.push(component);
+			}
+		}
+	}
+}
@@ -0,0 +1,222 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::{Display, Formatter};
+
+use luau_sys::glue::gluau_LintWarning;
+
+use crate::analysis::Frontend;
+use crate::ast::tree::{HotComment, HotCommentKind};
+use crate::ast::{Mode, Span};
+
+/// The lints that Luau knows about, numbered like `LintWarning::Code`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum LintCode {
+	Unknown,
+	UnknownGlobal,
+	DeprecatedGlobal,
+	GlobalUsedAsLocal,
+	LocalShadow,
+	SameLineStatement,
+	MultiLineStatement,
+	LocalUnused,
+	FunctionUnused,
+	ImportUnused,
+	BuiltinGlobalWrite,
+	PlaceholderRead,
+	UnreachableCode,
+	UnknownType,
+	ForRange,
+	UnbalancedAssignment,
+	ImplicitReturn,
+	DuplicateLocal,
+	FormatString,
+	TableLiteral,
+	UninitializedLocal,
+	DuplicateFunction,
+	DeprecatedApi,
+	TableOperations,
+	DuplicateCondition,
+	MisleadingAndOr,
+	CommentDirective,
+	IntegerParsing,
+	ComparisonPrecedence,
+	/// A lint that is not known to these bindings, by number.
+	Other(u8)
+}
+
+impl LintCode {
+	const KNOWN: [LintCode; 29] = [
+		Self::Unknown, Self::UnknownGlobal, Self::DeprecatedGlobal, Self::GlobalUsedAsLocal, Self::LocalShadow,
+		Self::SameLineStatement, Self::MultiLineStatement, Self::LocalUnused, Self::FunctionUnused, Self::ImportUnused,
+		Self::BuiltinGlobalWrite, Self::PlaceholderRead, Self::UnreachableCode, Self::UnknownType, Self::ForRange,
+		Self::UnbalancedAssignment, Self::ImplicitReturn, Self::DuplicateLocal, Self::FormatString, Self::TableLiteral,
+		Self::UninitializedLocal, Self::DuplicateFunction, Self::DeprecatedApi, Self::TableOperations,
+		Self::DuplicateCondition, Self::MisleadingAndOr, Self::CommentDirective, Self::IntegerParsing,
+		Self::ComparisonPrecedence
+	];
+
+	pub fn from_number(number: u8) -> Self {
+		Self::KNOWN.get(number as usize).copied().unwrap_or(Self::Other(number))
+	}
+
+	/// The number of the lint, which is how Luau refers to it in output like
+	/// `LocalUnused (7)`.
+	pub fn number(&self) -> u8 {
+		match self {
+			Self::Other(number) => *number,
+			known => Self::KNOWN.iter().position(|code| code == known).unwrap() as u8
+		}
+	}
+
+	/// The name of the lint, as used by `--!nolint` and `.luaurc` files.
+	pub fn name(&self) -> Option<&'static str> {
+		Some(match self {
+			Self::Unknown => "Unknown",
+			Self::UnknownGlobal => "UnknownGlobal",
+			Self::DeprecatedGlobal => "DeprecatedGlobal",
+			Self::GlobalUsedAsLocal => "GlobalUsedAsLocal",
+			Self::LocalShadow => "LocalShadow",
+			Self::SameLineStatement => "SameLineStatement",
+			Self::MultiLineStatement => "MultiLineStatement",
+			Self::LocalUnused => "LocalUnused",
+			Self::FunctionUnused => "FunctionUnused",
+			Self::ImportUnused => "ImportUnused",
+			Self::BuiltinGlobalWrite => "BuiltinGlobalWrite",
+			Self::PlaceholderRead => "PlaceholderRead",
+			Self::UnreachableCode => "UnreachableCode",
+			Self::UnknownType => "UnknownType",
+			Self::ForRange => "ForRange",
+			Self::UnbalancedAssignment => "UnbalancedAssignment",
+			Self::ImplicitReturn => "ImplicitReturn",
+			Self::DuplicateLocal => "DuplicateLocal",
+			Self::FormatString => "FormatString",
+			Self::TableLiteral => "TableLiteral",
+			Self::UninitializedLocal => "UninitializedLocal",
+			Self::DuplicateFunction => "DuplicateFunction",
+			Self::DeprecatedApi => "DeprecatedApi",
+			Self::TableOperations => "TableOperations",
+			Self::DuplicateCondition => "DuplicateCondition",
+			Self::MisleadingAndOr => "MisleadingAndOr",
+			Self::CommentDirective => "CommentDirective",
+			Self::IntegerParsing => "IntegerParsing",
+			Self::ComparisonPrecedence => "ComparisonPrecedence",
+			Self::Other(_) => return None
+		})
+	}
+
+	pub fn from_name(name: &str) -> Option<Self> {
+		Self::KNOWN.iter().copied().find(|code| code.name() == Some(name))
+	}
+}
+
+/// Selects which lints are reported. By default, every lint is enabled, like
+/// in Luau.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct LintOptions {
+	mask: u64
+}
+
+impl Default for LintOptions {
+	fn default() -> Self {
+		Self { mask: !0 }
+	}
+}
+
+impl LintOptions {
+	/// Returns options with every lint disabled.
+	pub fn none() -> Self {
+		Self { mask: 0 }
+	}
+
+	pub fn enable(&mut self, code: LintCode) -> &mut Self {
+		self.mask |= Self::bit(code);
+		self
+	}
+
+	pub fn disable(&mut self, code: LintCode) -> &mut Self {
+		self.mask &= !Self::bit(code);
+		self
+	}
+
+	pub fn set_enabled(&mut self, code: LintCode, enabled: bool) -> &mut Self {
+		if enabled { self.enable(code) } else { self.disable(code) }
+	}
+
+	pub fn is_enabled(&self, code: LintCode) -> bool {
+		self.mask & Self::bit(code) != 0
+	}
+
+	/// Disables the lints named by `--!nolint` hot comments, or every lint if a
+	/// `--!nolint` doesn't name one. Linting through a [`Frontend`] already does
+	/// this for the module being linted.
+	pub fn apply_hot_comments(&mut self, hot_comments: &[HotComment]) -> &mut Self {
+		for comment in hot_comments {
+			match &comment.kind {
+				HotCommentKind::NoLint(None) => self.mask = 0,
+
+				HotCommentKind::NoLint(Some(name)) => if let Some(code) = LintCode::from_name(name) {
+					self.disable(code);
+				}
+
+				_ => {}
+			}
+		}
+
+		self
+	}
+
+	pub(crate) fn mask(&self) -> u64 {
+		self.mask
+	}
+
+	fn bit(code: LintCode) -> u64 {
+		1u64.checked_shl(code.number() as u32).unwrap_or(0)
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct LintWarning {
+	pub code: LintCode,
+	pub message: String,
+	pub span: Span
+}
+
+impl Display for LintWarning {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match self.code.name() {
+			Some(name) => write!(f, "{}: {} ({}): {}", self.span, name, self.code.number(), self.message),
+			None => write!(f, "{}: {}: {}", self.span, self.code.number(), self.message)
+		}
+	}
+}
+
+pub(crate) fn lint_warning_from_gluau(gluau: gluau_LintWarning) -> LintWarning {
+	LintWarning {
+		code: LintCode::from_number(gluau.code),
+		// SAFETY: the buffer was allocated by the glue from a string
+		message: unsafe { String::from_raw_parts(gluau.message.data as _, gluau.message.len as _, gluau.message.len as _) },
+		span: Span::new(gluau.span.start_line, gluau.span.start_column, gluau.span.end_line, gluau.span.end_column)
+	}
+}
+
+/// Lints a single source on its own, against Luau's builtin globals. Use
+/// [`Frontend::lint`] to lint modules that require each other, or to lint many
+/// sources without setting up the builtin globals every time.
+pub fn lint(source: &str, options: &LintOptions) -> Vec<LintWarning> {
+	let mut frontend = Frontend::new(Mode::Nonstrict);
+	frontend.set_source("main", source);
+	frontend.lint("main", options)
+}
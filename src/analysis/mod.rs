@@ -12,3 +12,146 @@
 //
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::{Display, Formatter};
+
+use luau_sys::glue::{gluau_TypeError, gluau_TypeErrorKind};
+
+use crate::ast::Span;
+
+pub use autocomplete::{CompletionItem, CompletionKind};
+pub use config::{Config, ConfigError, CONFIG_FILE};
+pub use frontend::Frontend;
+pub use graph::ModuleGraph;
+pub use lint::{lint, LintCode, LintOptions, LintWarning};
+pub use require::{require_graph, RequireError, RequireErrorKind, RequireGraph};
+pub use resolver::{FileResolver, FsResolver, MemoryResolver, RequireExpr, Source, SourceKind};
+
+mod autocomplete;
+mod config;
+mod frontend;
+mod graph;
+mod lint;
+mod require;
+mod resolver;
+
+/// The name of a module, as passed to [`Frontend::check`] and produced when
+/// resolving `require` calls.
+pub type ModuleName = String;
+
+/// What kind of problem a [`TypeError`] reports. These mirror the error types
+/// in Luau's `Error.h`.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TypeErrorKind {
+	TypeMismatch,
+	UnknownSymbol,
+	UnknownProperty,
+	NotATable,
+	CannotExtendTable,
+	OnlyTablesCanHaveMethods,
+	DuplicateTypeDefinition,
+	CountMismatch,
+	FunctionDoesNotTakeSelf,
+	FunctionRequiresSelf,
+	OccursCheckFailed,
+	UnknownRequire,
+	IncorrectGenericParameterCount,
+	/// The module could not be parsed.
+	SyntaxError,
+	CodeTooComplex,
+	UnificationTooComplex,
+	UnknownPropButFoundLikeProp,
+	GenericError,
+	/// Luau failed internally while checking the module.
+	InternalError,
+	CannotCallNonFunction,
+	ExtraInformation,
+	DeprecatedApiUsed,
+	ModuleHasCyclicDependency,
+	IllegalRequire,
+	FunctionExitsWithoutReturning,
+	DuplicateGenericParameter,
+	CannotInferBinaryOperation,
+	MissingProperties,
+	SwappedGenericTypeParameter,
+	OptionalValueAccess,
+	MissingUnionProperty,
+	TypesAreUnrelated,
+	NormalizationTooComplex,
+	TypePackMismatch,
+	/// An error that is not known to these bindings.
+	Other
+}
+
+impl TypeErrorKind {
+	fn from_gluau(kind: gluau_TypeErrorKind) -> Self {
+		match kind {
+			gluau_TypeErrorKind::TYPE_ERROR_TYPE_MISMATCH => Self::TypeMismatch,
+			gluau_TypeErrorKind::TYPE_ERROR_UNKNOWN_SYMBOL => Self::UnknownSymbol,
+			gluau_TypeErrorKind::TYPE_ERROR_UNKNOWN_PROPERTY => Self::UnknownProperty,
+			gluau_TypeErrorKind::TYPE_ERROR_NOT_A_TABLE => Self::NotATable,
+			gluau_TypeErrorKind::TYPE_ERROR_CANNOT_EXTEND_TABLE => Self::CannotExtendTable,
+			gluau_TypeErrorKind::TYPE_ERROR_ONLY_TABLES_CAN_HAVE_METHODS => Self::OnlyTablesCanHaveMethods,
+			gluau_TypeErrorKind::TYPE_ERROR_DUPLICATE_TYPE_DEFINITION => Self::DuplicateTypeDefinition,
+			gluau_TypeErrorKind::TYPE_ERROR_COUNT_MISMATCH => Self::CountMismatch,
+			gluau_TypeErrorKind::TYPE_ERROR_FUNCTION_DOES_NOT_TAKE_SELF => Self::FunctionDoesNotTakeSelf,
+			gluau_TypeErrorKind::TYPE_ERROR_FUNCTION_REQUIRES_SELF => Self::FunctionRequiresSelf,
+			gluau_TypeErrorKind::TYPE_ERROR_OCCURS_CHECK_FAILED => Self::OccursCheckFailed,
+			gluau_TypeErrorKind::TYPE_ERROR_UNKNOWN_REQUIRE => Self::UnknownRequire,
+			gluau_TypeErrorKind::TYPE_ERROR_INCORRECT_GENERIC_PARAMETER_COUNT => Self::IncorrectGenericParameterCount,
+			gluau_TypeErrorKind::TYPE_ERROR_SYNTAX_ERROR => Self::SyntaxError,
+			gluau_TypeErrorKind::TYPE_ERROR_CODE_TOO_COMPLEX => Self::CodeTooComplex,
+			gluau_TypeErrorKind::TYPE_ERROR_UNIFICATION_TOO_COMPLEX => Self::UnificationTooComplex,
+			gluau_TypeErrorKind::TYPE_ERROR_UNKNOWN_PROP_BUT_FOUND_LIKE_PROP => Self::UnknownPropButFoundLikeProp,
+			gluau_TypeErrorKind::TYPE_ERROR_GENERIC_ERROR => Self::GenericError,
+			gluau_TypeErrorKind::TYPE_ERROR_INTERNAL_ERROR => Self::InternalError,
+			gluau_TypeErrorKind::TYPE_ERROR_CANNOT_CALL_NON_FUNCTION => Self::CannotCallNonFunction,
+			gluau_TypeErrorKind::TYPE_ERROR_EXTRA_INFORMATION => Self::ExtraInformation,
+			gluau_TypeErrorKind::TYPE_ERROR_DEPRECATED_API_USED => Self::DeprecatedApiUsed,
+			gluau_TypeErrorKind::TYPE_ERROR_MODULE_HAS_CYCLIC_DEPENDENCY => Self::ModuleHasCyclicDependency,
+			gluau_TypeErrorKind::TYPE_ERROR_ILLEGAL_REQUIRE => Self::IllegalRequire,
+			gluau_TypeErrorKind::TYPE_ERROR_FUNCTION_EXITS_WITHOUT_RETURNING => Self::FunctionExitsWithoutReturning,
+			gluau_TypeErrorKind::TYPE_ERROR_DUPLICATE_GENERIC_PARAMETER => Self::DuplicateGenericParameter,
+			gluau_TypeErrorKind::TYPE_ERROR_CANNOT_INFER_BINARY_OPERATION => Self::CannotInferBinaryOperation,
+			gluau_TypeErrorKind::TYPE_ERROR_MISSING_PROPERTIES => Self::MissingProperties,
+			gluau_TypeErrorKind::TYPE_ERROR_SWAPPED_GENERIC_TYPE_PARAMETER => Self::SwappedGenericTypeParameter,
+			gluau_TypeErrorKind::TYPE_ERROR_OPTIONAL_VALUE_ACCESS => Self::OptionalValueAccess,
+			gluau_TypeErrorKind::TYPE_ERROR_MISSING_UNION_PROPERTY => Self::MissingUnionProperty,
+			gluau_TypeErrorKind::TYPE_ERROR_TYPES_ARE_UNRELATED => Self::TypesAreUnrelated,
+			gluau_TypeErrorKind::TYPE_ERROR_NORMALIZATION_TOO_COMPLEX => Self::NormalizationTooComplex,
+			gluau_TypeErrorKind::TYPE_ERROR_TYPE_PACK_MISMATCH => Self::TypePackMismatch,
+			gluau_TypeErrorKind::TYPE_ERROR_OTHER => Self::Other
+		}
+	}
+}
+
+/// A problem found while type checking a module.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct TypeError {
+	pub kind: TypeErrorKind,
+	pub message: String,
+	pub span: Span,
+	/// The module that the error is in, which is not necessarily the module
+	/// that was checked, since its dependencies are checked too.
+	pub module: ModuleName
+}
+
+impl Display for TypeError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}:{}: {}", self.module, self.span, self.message)
+	}
+}
+
+impl std::error::Error for TypeError {}
+
+pub(crate) fn type_error_from_gluau(gluau: gluau_TypeError) -> TypeError {
+	unsafe {
+		TypeError {
+			kind: TypeErrorKind::from_gluau(gluau.kind),
+			// SAFETY: both buffers were allocated by the glue from strings
+			message: String::from_raw_parts(gluau.message.data as _, gluau.message.len as _, gluau.message.len as _),
+			module: String::from_raw_parts(gluau.module.data as _, gluau.module.len as _, gluau.module.len as _),
+			span: Span::new(gluau.span.start_line, gluau.span.start_column, gluau.span.end_line, gluau.span.end_column)
+		}
+	}
+}
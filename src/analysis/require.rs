@@ -0,0 +1,216 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+
+use crate::analysis::{FileResolver, ModuleGraph, ModuleName, RequireExpr};
+use crate::ast::tree::{Ast, Expr, ExprCall, ExprKind};
+use crate::ast::visit::{walk_expr_call, Visitor};
+use crate::ast::{parse_recovering, ParseOptions, Span};
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum RequireErrorKind {
+	/// The module could not be read by the resolver. This is reported with the
+	/// `require` call that refers to it, or with an empty span for the entry.
+	NotFound(ModuleName),
+	/// The resolver could not resolve a `require` call. The argument is
+	/// included as written.
+	Unresolved(String),
+	/// The argument of a `require` call is neither a string literal nor a path
+	/// of names, so it can't be resolved statically.
+	Dynamic,
+	/// The module contains a syntax error, so some of its requires may be
+	/// missing from the graph.
+	Syntax(String)
+}
+
+/// A problem found while building a [`RequireGraph`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RequireError {
+	pub kind: RequireErrorKind,
+	/// The module that the problem is in.
+	pub module: ModuleName,
+	pub span: Span
+}
+
+impl Display for RequireError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}:{}: ", self.module, self.span)?;
+
+		match &self.kind {
+			RequireErrorKind::NotFound(module) => write!(f, "module {} could not be read", module),
+			RequireErrorKind::Unresolved(expr) => write!(f, "unknown require {}", expr),
+			RequireErrorKind::Dynamic => write!(f, "require argument can't be resolved statically"),
+			RequireErrorKind::Syntax(message) => f.write_str(message)
+		}
+	}
+}
+
+impl std::error::Error for RequireError {}
+
+/// The modules reachable from an entry module through static requires.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RequireGraph {
+	pub graph: ModuleGraph,
+	/// The cycles in the graph, as returned by [`ModuleGraph::cycles`].
+	pub cycles: Vec<Vec<ModuleName>>,
+	pub errors: Vec<RequireError>
+}
+
+/// The argument of a `require` call, owned so that the AST can be dropped.
+enum Require {
+	String(String),
+	Path(Vec<String>),
+	Dynamic
+}
+
+struct RequireCollector<'a> {
+	ast: &'a Ast,
+	requires: Vec<(Span, Require)>
+}
+
+impl RequireCollector<'_> {
+	fn path(&self, expr: &Expr, path: &mut Vec<String>) -> bool {
+		match &expr.kind {
+			ExprKind::Global(name) => path.push(name.clone()),
+			ExprKind::Local(local) => path.push(self.ast.local(local.local).name.clone()),
+
+			ExprKind::IndexName(index) if index.op == '.' => {
+				if !self.path(&index.expr, path) {
+					return false
+				}
+
+				path.push(index.index.clone());
+			}
+
+			_ => return false
+		}
+
+		true
+	}
+}
+
+impl Visitor for RequireCollector<'_> {
+	fn visit_expr_call(&mut self, span: Span, expr: &ExprCall) {
+		if matches!(&expr.func.kind, ExprKind::Global(name) if name == "require") && !expr.is_self {
+			let require = match expr.args.first().map(|arg| &arg.kind) {
+				Some(ExprKind::String(string)) => match std::str::from_utf8(&string.value) {
+					Ok(string) => Require::String(string.to_owned()),
+					Err(_) => Require::Dynamic
+				},
+
+				Some(_) => {
+					let mut path = Vec::new();
+
+					if self.path(&expr.args[0], &mut path) {
+						Require::Path(path)
+					} else {
+						Require::Dynamic
+					}
+				}
+
+				None => Require::Dynamic
+			};
+
+			self.requires.push((span, require));
+		}
+
+		walk_expr_call(self, expr)
+	}
+}
+
+/// Builds the graph of modules reachable from `entry` by following static
+/// `require` calls, without type checking anything. Requires are found by
+/// parsing each module and resolved like the [`Frontend`](crate::analysis::Frontend)
+/// would, through the resolver.
+///
+/// Modules that fail to parse are still searched for requires as far as they
+/// could be parsed.
+pub fn require_graph(entry: &str, resolver: &impl FileResolver) -> RequireGraph {
+	let mut graph = ModuleGraph::new();
+	let mut errors = Vec::new();
+	let mut queue = VecDeque::new();
+
+	graph.add_module(entry);
+	queue.push_back((entry.to_owned(), None));
+
+	while let Some((module, required_at)) = queue.pop_front() {
+		let Some(source) = resolver.read_source(&module) else {
+			let (from, span) = required_at.unwrap_or_else(|| (module.clone(), Span::new(0, 0, 0, 0)));
+			errors.push(RequireError { kind: RequireErrorKind::NotFound(module), module: from, span });
+			continue
+		};
+
+		let ast = match parse_recovering(&source.source, &ParseOptions::default()) {
+			Ok(result) => {
+				errors.extend(result.errors.into_iter().map(|error| RequireError {
+					kind: RequireErrorKind::Syntax(error.message),
+					module: module.clone(),
+					span: error.span
+				}));
+
+				result.ast
+			}
+
+			Err(parse_errors) => {
+				errors.extend(parse_errors.into_iter().map(|error| RequireError {
+					kind: RequireErrorKind::Syntax(error.message),
+					module: module.clone(),
+					span: error.span
+				}));
+
+				continue
+			}
+		};
+
+		let mut collector = RequireCollector { ast: &ast, requires: Vec::new() };
+		collector.visit_block(&ast.root);
+
+		for (span, require) in collector.requires {
+			let (resolved, written) = match require {
+				Require::String(string) => (resolver.resolve_module(&module, RequireExpr::String(&string)), format!("{:?}", string)),
+
+				Require::Path(path) => {
+					let parts: Vec<&str> = path.iter().map(String::as_str).collect();
+					(resolver.resolve_module(&module, RequireExpr::Path(&parts)), path.join("."))
+				}
+
+				Require::Dynamic => {
+					errors.push(RequireError { kind: RequireErrorKind::Dynamic, module: module.clone(), span });
+					continue
+				}
+			};
+
+			let Some(resolved) = resolved else {
+				errors.push(RequireError { kind: RequireErrorKind::Unresolved(written), module: module.clone(), span });
+				continue
+			};
+
+			if !graph.contains(&resolved) {
+				queue.push_back((resolved.clone(), Some((module.clone(), span))));
+			}
+
+			graph.add_require(module.clone(), resolved);
+		}
+	}
+
+	RequireGraph {
+		cycles: graph.cycles(),
+		graph,
+		errors
+	}
+}
@@ -0,0 +1,229 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use crate::analysis::{Config, ModuleName};
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SourceKind {
+	/// A module, which returns a value to whoever requires it.
+	Module,
+	/// A script, which is run on its own rather than required.
+	Script
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Source {
+	pub source: String,
+	pub kind: SourceKind
+}
+
+impl Source {
+	pub fn module(source: impl Into<String>) -> Self {
+		Self {
+			source: source.into(),
+			kind: SourceKind::Module
+		}
+	}
+}
+
+/// The argument of a `require` call. Arguments that are neither of these can't
+/// be resolved.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum RequireExpr<'a> {
+	/// A string literal, like `require("./module")`.
+	String(&'a str),
+	/// A path of names starting at a global or local, like
+	/// `require(script.Parent.Module)`, which is `["script", "Parent", "Module"]`.
+	Path(&'a [&'a str])
+}
+
+/// Tells the [`Frontend`] where to find modules.
+///
+/// Panics inside of a resolver are caught, and resumed once Luau is done with
+/// the resolver.
+///
+/// [`Frontend`]: crate::analysis::Frontend
+pub trait FileResolver {
+	fn read_source(&self, module: &str) -> Option<Source>;
+
+	/// Returns the name of the module that a `require` call inside of `from`
+	/// refers to, if it exists.
+	fn resolve_module(&self, from: &str, expr: RequireExpr<'_>) -> Option<ModuleName>;
+
+	/// Called by [`Frontend::set_config`] so that the resolver can pick up
+	/// settings like [`Config::aliases`]. Does nothing by default.
+	///
+	/// [`Frontend::set_config`]: crate::analysis::Frontend::set_config
+	fn apply_config(&mut self, _config: &Config) {}
+}
+
+/// Makes a path absolute, resolving symlinks if it exists, so that paths
+/// written differently can be compared.
+pub(crate) fn absolute(path: &Path) -> PathBuf {
+	path.canonicalize().unwrap_or_else(|_| match std::env::current_dir() {
+		Ok(dir) => dir.join(path),
+		Err(_) => path.to_owned()
+	})
+}
+
+/// Keeps every module in memory, which is useful for tests and for checking
+/// code that doesn't live on disk. Modules are named however they are added,
+/// and `require("name")` refers to the module with that exact name.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct MemoryResolver {
+	modules: HashMap<ModuleName, Source>
+}
+
+impl MemoryResolver {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Adds a module, or replaces an existing one.
+	pub fn set_source(&mut self, name: impl Into<ModuleName>, source: Source) -> &mut Self {
+		self.modules.insert(name.into(), source);
+		self
+	}
+
+	pub fn remove(&mut self, name: &str) -> Option<Source> {
+		self.modules.remove(name)
+	}
+
+	pub fn source(&self, name: &str) -> Option<&Source> {
+		self.modules.get(name)
+	}
+}
+
+impl FileResolver for MemoryResolver {
+	fn read_source(&self, module: &str) -> Option<Source> {
+		self.modules.get(module).cloned()
+	}
+
+	fn resolve_module(&self, _from: &str, expr: RequireExpr<'_>) -> Option<ModuleName> {
+		match expr {
+			RequireExpr::String(name) if self.modules.contains_key(name) => Some(name.to_owned()),
+			_ => None
+		}
+	}
+}
+
+/// Reads modules from the filesystem, beneath a root directory. Modules are
+/// named by their path relative to the root, with `/` as the separator. Names
+/// that would leave the root, such as `../outside.luau` or absolute paths,
+/// can't be read.
+///
+/// String requires are resolved relative to the directory of the requiring
+/// module, like `require("./sibling")` or `require("../parent/module")`. The
+/// required path may leave out the `.luau` or `.lua` extension, and may name a
+/// directory containing an `init.luau` or `init.lua`.
+///
+/// Requires starting with an alias, like `require("@shared/module")`, are
+/// resolved relative to the directory of the alias instead.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct FsResolver {
+	root: PathBuf,
+	aliases: HashMap<String, PathBuf>
+}
+
+impl FsResolver {
+	pub fn new(root: impl Into<PathBuf>) -> Self {
+		Self { root: root.into(), aliases: HashMap::new() }
+	}
+
+	pub fn root(&self) -> &Path {
+		&self.root
+	}
+
+	/// Sets the aliases usable in requires, like the ones in
+	/// [`Config::aliases`]. Relative paths are relative to the root. Absolute
+	/// paths must be beneath the root, but both are canonicalized first, so
+	/// the root may be relative or reached through a symlink.
+	pub fn set_aliases(&mut self, aliases: HashMap<String, PathBuf>) -> &mut Self {
+		self.aliases = aliases.into_iter().map(|(name, path)| (name.to_ascii_lowercase(), path)).collect();
+		self
+	}
+
+	pub fn aliases(&self) -> &HashMap<String, PathBuf> {
+		&self.aliases
+	}
+
+	/// Returns the module name of the directory an alias refers to.
+	fn alias_dir(&self, alias: &str) -> Option<String> {
+		let path = self.aliases.get(&alias.to_ascii_lowercase())?;
+		let path = if path.is_absolute() { absolute(path).strip_prefix(absolute(&self.root)).ok()?.to_owned() } else { path.clone() };
+
+		let parts: Option<Vec<&str>> = path.components().map(|component| component.as_os_str().to_str()).collect();
+		Self::normalize(&parts?.join("/"))
+	}
+
+	/// Returns the module name of a path relative to the root, or `None` if the
+	/// path leaves the root.
+	fn normalize(path: &str) -> Option<String> {
+		let mut parts = Vec::new();
+
+		for part in path.split('/') {
+			match part {
+				"" | "." => {}
+				".." => { parts.pop()?; }
+				part => parts.push(part)
+			}
+		}
+
+		Some(parts.join("/"))
+	}
+}
+
+impl FileResolver for FsResolver {
+	fn read_source(&self, module: &str) -> Option<Source> {
+		// names can come from anywhere, so make sure they stay beneath the root
+		if module.contains('\\') || Path::new(module).components().any(|component| matches!(component, Component::Prefix(_) | Component::RootDir)) {
+			return None
+		}
+
+		let name = Self::normalize(module).filter(|name| !name.is_empty())?;
+		std::fs::read_to_string(self.root.join(name)).ok().map(Source::module)
+	}
+
+	fn resolve_module(&self, from: &str, expr: RequireExpr<'_>) -> Option<ModuleName> {
+		let RequireExpr::String(path) = expr else { return None };
+
+		let name = match path.strip_prefix('@') {
+			Some(aliased) => {
+				let (alias, rest) = aliased.split_once('/').unwrap_or((aliased, ""));
+				Self::normalize(&format!("{}/{}", self.alias_dir(alias)?, rest))?
+			}
+
+			None => {
+				let base = from.rsplit_once('/').map_or("", |(dir, _)| dir);
+				Self::normalize(&format!("{}/{}", base, path))?
+			}
+		};
+
+		[
+			name.clone(),
+			format!("{}.luau", name),
+			format!("{}.lua", name),
+			format!("{}/init.luau", name),
+			format!("{}/init.lua", name)
+		].iter().find(|candidate| !candidate.is_empty() && self.root.join(candidate).is_file()).cloned()
+	}
+
+	fn apply_config(&mut self, config: &Config) {
+		self.set_aliases(config.aliases.clone());
+	}
+}
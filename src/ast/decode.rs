@@ -0,0 +1,608 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Decodes the AST serialized by `gluau_parse`. The glue and this decoder are
+//! written together, so a malformed buffer is a bug and panics.
+
+use bstr::BString;
+
+use crate::ast::Span;
+use crate::ast::source_map::SourceMap;
+use crate::ast::tree::*;
+
+const STAT_BLOCK: u8 = 0;
+const STAT_IF: u8 = 1;
+const STAT_WHILE: u8 = 2;
+const STAT_REPEAT: u8 = 3;
+const STAT_BREAK: u8 = 4;
+const STAT_CONTINUE: u8 = 5;
+const STAT_RETURN: u8 = 6;
+const STAT_EXPR: u8 = 7;
+const STAT_LOCAL: u8 = 8;
+const STAT_FOR: u8 = 9;
+const STAT_FOR_IN: u8 = 10;
+const STAT_ASSIGN: u8 = 11;
+const STAT_COMPOUND_ASSIGN: u8 = 12;
+const STAT_FUNCTION: u8 = 13;
+const STAT_LOCAL_FUNCTION: u8 = 14;
+const STAT_TYPE_ALIAS: u8 = 15;
+const STAT_DECLARE_GLOBAL: u8 = 16;
+const STAT_DECLARE_FUNCTION: u8 = 17;
+const STAT_DECLARE_CLASS: u8 = 18;
+const STAT_ERROR: u8 = 19;
+
+const EXPR_GROUP: u8 = 0;
+const EXPR_NIL: u8 = 1;
+const EXPR_BOOL: u8 = 2;
+const EXPR_NUMBER: u8 = 3;
+const EXPR_STRING: u8 = 4;
+const EXPR_LOCAL: u8 = 5;
+const EXPR_GLOBAL: u8 = 6;
+const EXPR_VARARGS: u8 = 7;
+const EXPR_CALL: u8 = 8;
+const EXPR_INDEX_NAME: u8 = 9;
+const EXPR_INDEX_EXPR: u8 = 10;
+const EXPR_FUNCTION: u8 = 11;
+const EXPR_TABLE: u8 = 12;
+const EXPR_UNARY: u8 = 13;
+const EXPR_BINARY: u8 = 14;
+const EXPR_TYPE_ASSERTION: u8 = 15;
+const EXPR_IF_ELSE: u8 = 16;
+const EXPR_INTERP_STRING: u8 = 17;
+const EXPR_ERROR: u8 = 18;
+
+const TYPE_REFERENCE: u8 = 0;
+const TYPE_TABLE: u8 = 1;
+const TYPE_FUNCTION: u8 = 2;
+const TYPE_TYPEOF: u8 = 3;
+const TYPE_UNION: u8 = 4;
+const TYPE_INTERSECTION: u8 = 5;
+const TYPE_SINGLETON_BOOL: u8 = 6;
+const TYPE_SINGLETON_STRING: u8 = 7;
+const TYPE_ERROR: u8 = 8;
+
+const TYPE_PACK_EXPLICIT: u8 = 0;
+const TYPE_PACK_VARIADIC: u8 = 1;
+const TYPE_PACK_GENERIC: u8 = 2;
+
+/// Decodes an AST. Comment text isn't serialized, so it is sliced from `source`.
+pub(crate) fn decode(data: &[u8], source: &str) -> Ast {
+	let mut reader = Reader { data, offset: 0 };
+
+	let lines = reader.u32();
+	let root = reader.block();
+	let mut locals = Vec::new();
+
+	while reader.bool() {
+		locals.push(Local {
+			name: reader.string(),
+			span: reader.span(),
+			shadow: reader.u32().checked_sub(1).map(LocalId),
+			function_depth: reader.u32(),
+			loop_depth: reader.u32()
+		});
+	}
+
+	let map = SourceMap::new(source);
+
+	let comments = reader.vec(|reader| {
+		let span = reader.span();
+
+		let kind = match reader.u8() {
+			0 => CommentKind::Line,
+			1 => CommentKind::Block,
+			_ => CommentKind::BrokenBlock
+		};
+
+		Comment { kind, span, text: map.slice(span).unwrap_or_default().to_owned() }
+	});
+
+	let hot_comments = reader.vec(|reader| {
+		let span = reader.span();
+		let header = reader.bool();
+		let content = reader.string();
+		let kind = HotCommentKind::parse(&content);
+		HotComment { span, header, content, kind }
+	});
+
+	assert_eq!(reader.offset, data.len(), "trailing data after AST");
+	Ast { root, locals, lines, comments, hot_comments }
+}
+
+struct Reader<'a> {
+	data: &'a [u8],
+	offset: usize
+}
+
+impl<'a> Reader<'a> {
+	fn bytes(&mut self, len: usize) -> &'a [u8] {
+		let bytes = &self.data[self.offset..self.offset + len];
+		self.offset += len;
+		bytes
+	}
+
+	fn array<const N: usize>(&mut self) -> [u8; N] {
+		let mut array = [0u8; N];
+		array.copy_from_slice(self.bytes(N));
+		array
+	}
+
+	fn u8(&mut self) -> u8 { self.bytes(1)[0] }
+	fn bool(&mut self) -> bool { self.u8() != 0 }
+	fn u32(&mut self) -> u32 { u32::from_le_bytes(self.array()) }
+	fn f64(&mut self) -> f64 { f64::from_le_bytes(self.array()) }
+
+	fn bstring(&mut self) -> BString {
+		let len = self.u32() as usize;
+		BString::from(self.bytes(len))
+	}
+
+	fn string(&mut self) -> String {
+		// names come from the lexer, which only accepts ASCII identifiers
+		String::from_utf8_lossy(&self.bstring()).into_owned()
+	}
+
+	fn span(&mut self) -> Span {
+		Span::new(self.u32(), self.u32(), self.u32(), self.u32())
+	}
+
+	fn option<T>(&mut self, read: impl FnOnce(&mut Self) -> T) -> Option<T> {
+		if self.bool() { Some(read(self)) } else { None }
+	}
+
+	fn vec<T>(&mut self, mut read: impl FnMut(&mut Self) -> T) -> Vec<T> {
+		let len = self.u32() as usize;
+		(0..len).map(|_| read(self)).collect()
+	}
+
+	fn message_index(&mut self) -> Option<u32> {
+		Some(self.u32()).filter(|index| *index != u32::MAX)
+	}
+
+	fn local(&mut self) -> LocalId {
+		LocalId(self.u32())
+	}
+
+	fn binding(&mut self) -> Binding {
+		Binding {
+			local: self.local(),
+			annotation: self.option(Self::ty)
+		}
+	}
+
+	fn attr(&mut self) -> Attr {
+		Attr {
+			span: self.span(),
+			kind: match self.u8() {
+				0 => AttrKind::Checked,
+				1 => AttrKind::Native,
+				other => AttrKind::Unknown(other)
+			}
+		}
+	}
+
+	fn generic_type(&mut self) -> GenericType {
+		GenericType {
+			name: self.string(),
+			span: self.span(),
+			default: self.option(Self::ty)
+		}
+	}
+
+	fn generic_type_pack(&mut self) -> GenericTypePack {
+		GenericTypePack {
+			name: self.string(),
+			span: self.span(),
+			default: self.option(Self::type_pack)
+		}
+	}
+
+	fn argument_name(&mut self) -> ArgumentName {
+		ArgumentName {
+			name: self.string(),
+			span: self.span()
+		}
+	}
+
+	fn type_list(&mut self) -> TypeList {
+		TypeList {
+			types: self.vec(Self::ty),
+			tail: self.option(|reader| Box::new(reader.type_pack()))
+		}
+	}
+
+	fn table_access(&mut self) -> TableAccess {
+		match self.u8() {
+			1 => TableAccess::Read,
+			2 => TableAccess::Write,
+			_ => TableAccess::ReadWrite
+		}
+	}
+
+	fn table_indexer(&mut self) -> TableIndexer {
+		TableIndexer {
+			span: self.span(),
+			index_type: self.ty(),
+			result_type: self.ty(),
+			access: self.table_access(),
+			access_span: self.option(Self::span)
+		}
+	}
+
+	fn function(&mut self) -> (Span, ExprFunction) {
+		let span = self.span();
+
+		let func = ExprFunction {
+			attributes: self.vec(Self::attr),
+			generics: self.vec(Self::generic_type),
+			generic_packs: self.vec(Self::generic_type_pack),
+			self_local: self.option(Self::local),
+			args: self.vec(Self::binding),
+			return_annotation: self.option(Self::type_list),
+			vararg: self.bool(),
+			vararg_span: self.span(),
+			vararg_annotation: self.option(Self::type_pack),
+			body: self.block(),
+			function_depth: self.u32(),
+			debug_name: self.string(),
+			arg_span: self.option(Self::span)
+		};
+
+		(span, func)
+	}
+
+	fn block(&mut self) -> Block {
+		Block {
+			span: self.span(),
+			body: self.vec(Self::stat),
+			has_end: self.bool()
+		}
+	}
+
+	fn stat(&mut self) -> Stat {
+		let span = self.span();
+
+		let kind = match self.u8() {
+			STAT_BLOCK => StatKind::Block(self.block()),
+
+			STAT_IF => StatKind::If(StatIf {
+				condition: self.expr(),
+				then_body: self.block(),
+				else_body: self.option(|reader| Box::new(reader.stat())),
+				then_span: self.option(Self::span),
+				else_span: self.option(Self::span)
+			}),
+
+			STAT_WHILE => StatKind::While(StatWhile {
+				condition: self.expr(),
+				body: self.block(),
+				has_do: self.bool(),
+				do_span: self.span()
+			}),
+
+			STAT_REPEAT => StatKind::Repeat(StatRepeat {
+				body: self.block(),
+				condition: self.expr()
+			}),
+
+			STAT_BREAK => StatKind::Break,
+			STAT_CONTINUE => StatKind::Continue,
+			STAT_RETURN => StatKind::Return(StatReturn { values: self.vec(Self::expr) }),
+			STAT_EXPR => StatKind::Expr(self.expr()),
+
+			STAT_LOCAL => StatKind::Local(StatLocal {
+				vars: self.vec(Self::binding),
+				values: self.vec(Self::expr),
+				equals_span: self.option(Self::span)
+			}),
+
+			STAT_FOR => StatKind::For(StatFor {
+				var: self.binding(),
+				from: self.expr(),
+				to: self.expr(),
+				step: self.option(Self::expr),
+				body: self.block(),
+				has_do: self.bool(),
+				do_span: self.span()
+			}),
+
+			STAT_FOR_IN => StatKind::ForIn(StatForIn {
+				vars: self.vec(Self::binding),
+				values: self.vec(Self::expr),
+				body: self.block(),
+				has_in: self.bool(),
+				in_span: self.span(),
+				has_do: self.bool(),
+				do_span: self.span()
+			}),
+
+			STAT_ASSIGN => StatKind::Assign(StatAssign {
+				vars: self.vec(Self::expr),
+				values: self.vec(Self::expr)
+			}),
+
+			STAT_COMPOUND_ASSIGN => StatKind::CompoundAssign(StatCompoundAssign {
+				op: self.binary_op(),
+				var: self.expr(),
+				value: self.expr()
+			}),
+
+			STAT_FUNCTION => {
+				let name = self.expr();
+				let (func_span, func) = self.function();
+				StatKind::Function(StatFunction { name, func_span, func: Box::new(func) })
+			}
+
+			STAT_LOCAL_FUNCTION => {
+				let name = self.local();
+				let (func_span, func) = self.function();
+				StatKind::LocalFunction(StatLocalFunction { name, func_span, func: Box::new(func) })
+			}
+
+			STAT_TYPE_ALIAS => StatKind::TypeAlias(StatTypeAlias {
+				name: self.string(),
+				name_span: self.span(),
+				generics: self.vec(Self::generic_type),
+				generic_packs: self.vec(Self::generic_type_pack),
+				ty: self.ty(),
+				exported: self.bool()
+			}),
+
+			STAT_DECLARE_GLOBAL => StatKind::DeclareGlobal(StatDeclareGlobal {
+				name: self.string(),
+				name_span: self.span(),
+				ty: self.ty()
+			}),
+
+			STAT_DECLARE_FUNCTION => StatKind::DeclareFunction(StatDeclareFunction {
+				attributes: self.vec(Self::attr),
+				name: self.string(),
+				name_span: self.span(),
+				generics: self.vec(Self::generic_type),
+				generic_packs: self.vec(Self::generic_type_pack),
+				params: self.type_list(),
+				param_names: self.vec(Self::argument_name),
+				vararg: self.bool(),
+				vararg_span: self.span(),
+				return_types: self.type_list()
+			}),
+
+			STAT_DECLARE_CLASS => StatKind::DeclareClass(StatDeclareClass {
+				name: self.string(),
+				super_name: self.option(Self::string),
+				props: self.vec(|reader| DeclaredClassProp {
+					name: reader.string(),
+					name_span: reader.span(),
+					ty: reader.ty(),
+					is_method: reader.bool(),
+					span: reader.span()
+				}),
+				indexer: self.option(Self::table_indexer)
+			}),
+
+			STAT_ERROR => StatKind::Error(StatError {
+				expressions: self.vec(Self::expr),
+				statements: self.vec(Self::stat),
+				message_index: self.message_index()
+			}),
+
+			tag => panic!("unknown statement tag {}", tag)
+		};
+
+		Stat { span, kind }
+	}
+
+	fn unary_op(&mut self) -> UnaryOp {
+		match self.u8() {
+			0 => UnaryOp::Not,
+			1 => UnaryOp::Minus,
+			2 => UnaryOp::Len,
+			op => panic!("unknown unary operator {}", op)
+		}
+	}
+
+	fn binary_op(&mut self) -> BinaryOp {
+		match self.u8() {
+			0 => BinaryOp::Add,
+			1 => BinaryOp::Sub,
+			2 => BinaryOp::Mul,
+			3 => BinaryOp::Div,
+			4 => BinaryOp::FloorDiv,
+			5 => BinaryOp::Mod,
+			6 => BinaryOp::Pow,
+			7 => BinaryOp::Concat,
+			8 => BinaryOp::CompareNe,
+			9 => BinaryOp::CompareEq,
+			10 => BinaryOp::CompareLt,
+			11 => BinaryOp::CompareLe,
+			12 => BinaryOp::CompareGt,
+			13 => BinaryOp::CompareGe,
+			14 => BinaryOp::And,
+			15 => BinaryOp::Or,
+			op => panic!("unknown binary operator {}", op)
+		}
+	}
+
+	fn boxed_expr(&mut self) -> Box<Expr> {
+		Box::new(self.expr())
+	}
+
+	fn expr(&mut self) -> Expr {
+		let span = self.span();
+
+		let kind = match self.u8() {
+			EXPR_GROUP => ExprKind::Group(self.boxed_expr()),
+			EXPR_NIL => ExprKind::Nil,
+			EXPR_BOOL => ExprKind::Bool(self.bool()),
+			EXPR_NUMBER => ExprKind::Number(self.f64()),
+
+			EXPR_STRING => ExprKind::String(ExprString {
+				value: self.bstring(),
+				quote_style: match self.u8() {
+					0 => QuoteStyle::Simple,
+					1 => QuoteStyle::Raw,
+					_ => QuoteStyle::Unquoted
+				}
+			}),
+
+			EXPR_LOCAL => ExprKind::Local(ExprLocal {
+				local: self.local(),
+				upvalue: self.bool()
+			}),
+
+			EXPR_GLOBAL => ExprKind::Global(self.string()),
+			EXPR_VARARGS => ExprKind::Varargs,
+
+			EXPR_CALL => ExprKind::Call(ExprCall {
+				func: self.boxed_expr(),
+				args: self.vec(Self::expr),
+				is_self: self.bool(),
+				arg_span: self.span()
+			}),
+
+			EXPR_INDEX_NAME => ExprKind::IndexName(ExprIndexName {
+				expr: self.boxed_expr(),
+				index: self.string(),
+				index_span: self.span(),
+				op: self.u8() as char
+			}),
+
+			EXPR_INDEX_EXPR => ExprKind::IndexExpr(ExprIndexExpr {
+				expr: self.boxed_expr(),
+				index: self.boxed_expr()
+			}),
+
+			// the function's own span is the same as the expression's
+			EXPR_FUNCTION => ExprKind::Function(Box::new(self.function().1)),
+
+			EXPR_TABLE => ExprKind::Table(ExprTable {
+				items: self.vec(|reader| TableItem {
+					kind: match reader.u8() {
+						0 => TableItemKind::List,
+						1 => TableItemKind::Record,
+						_ => TableItemKind::General
+					},
+					key: reader.option(Self::expr),
+					value: reader.expr()
+				})
+			}),
+
+			EXPR_UNARY => ExprKind::Unary(ExprUnary {
+				op: self.unary_op(),
+				expr: self.boxed_expr()
+			}),
+
+			EXPR_BINARY => ExprKind::Binary(ExprBinary {
+				op: self.binary_op(),
+				left: self.boxed_expr(),
+				right: self.boxed_expr()
+			}),
+
+			EXPR_TYPE_ASSERTION => ExprKind::TypeAssertion(ExprTypeAssertion {
+				expr: self.boxed_expr(),
+				annotation: Box::new(self.ty())
+			}),
+
+			EXPR_IF_ELSE => ExprKind::IfElse(ExprIfElse {
+				condition: self.boxed_expr(),
+				has_then: self.bool(),
+				true_expr: self.boxed_expr(),
+				has_else: self.bool(),
+				false_expr: self.boxed_expr()
+			}),
+
+			EXPR_INTERP_STRING => ExprKind::InterpString(ExprInterpString {
+				strings: self.vec(Self::bstring),
+				expressions: self.vec(Self::expr)
+			}),
+
+			EXPR_ERROR => ExprKind::Error(ExprError {
+				expressions: self.vec(Self::expr),
+				message_index: self.message_index()
+			}),
+
+			tag => panic!("unknown expression tag {}", tag)
+		};
+
+		Expr { span, kind }
+	}
+
+	fn ty(&mut self) -> Type {
+		let span = self.span();
+
+		let kind = match self.u8() {
+			TYPE_REFERENCE => TypeKind::Reference(TypeReference {
+				prefix: self.option(Self::string),
+				prefix_span: self.option(Self::span),
+				name: self.string(),
+				name_span: self.span(),
+				has_parameter_list: self.bool(),
+				parameters: self.vec(|reader| match reader.u8() {
+					0 => TypeOrPack::Type(reader.ty()),
+					_ => TypeOrPack::Pack(reader.type_pack())
+				})
+			}),
+
+			TYPE_TABLE => TypeKind::Table(TypeTable {
+				props: self.vec(|reader| TableProp {
+					name: reader.string(),
+					span: reader.span(),
+					ty: reader.ty(),
+					access: reader.table_access(),
+					access_span: reader.option(Self::span)
+				}),
+				indexer: self.option(|reader| Box::new(reader.table_indexer()))
+			}),
+
+			TYPE_FUNCTION => TypeKind::Function(Box::new(TypeFunction {
+				attributes: self.vec(Self::attr),
+				generics: self.vec(Self::generic_type),
+				generic_packs: self.vec(Self::generic_type_pack),
+				arg_types: self.type_list(),
+				arg_names: self.vec(|reader| reader.option(Self::argument_name)),
+				return_types: self.type_list()
+			})),
+
+			TYPE_TYPEOF => TypeKind::Typeof(self.boxed_expr()),
+			TYPE_UNION => TypeKind::Union(TypeUnion { types: self.vec(Self::ty) }),
+			TYPE_INTERSECTION => TypeKind::Intersection(TypeIntersection { types: self.vec(Self::ty) }),
+			TYPE_SINGLETON_BOOL => TypeKind::SingletonBool(self.bool()),
+			TYPE_SINGLETON_STRING => TypeKind::SingletonString(self.bstring()),
+
+			TYPE_ERROR => TypeKind::Error(TypeError {
+				types: self.vec(Self::ty),
+				is_missing: self.bool(),
+				message_index: self.message_index()
+			}),
+
+			tag => panic!("unknown type tag {}", tag)
+		};
+
+		Type { span, kind }
+	}
+
+	fn type_pack(&mut self) -> TypePack {
+		let span = self.span();
+
+		let kind = match self.u8() {
+			TYPE_PACK_EXPLICIT => TypePackKind::Explicit(self.type_list()),
+			TYPE_PACK_VARIADIC => TypePackKind::Variadic(Box::new(self.ty())),
+			TYPE_PACK_GENERIC => TypePackKind::Generic(self.string()),
+			tag => panic!("unknown type pack tag {}", tag)
+		};
+
+		TypePack { span, kind }
+	}
+}
@@ -0,0 +1,771 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Serializes the tree in the same shape as Luau's `Luau.Ast.Json` encoder,
+//! which is what `luau-ast` prints. Every node is an object with a `type` such
+//! as `"AstExprCall"` and a `location` such as `"0,0 - 0,5"`, followed by its
+//! children. Locals are written out in full wherever they are referenced.
+
+use bstr::ByteSlice;
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+
+use crate::ast::{Position, Span, Visitor};
+use crate::ast::tree::*;
+use crate::ast::visit::walk_binding;
+
+impl Serialize for Position {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(&format_args!("{},{}", self.line, self.column))
+	}
+}
+
+impl Serialize for Span {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.collect_str(&format_args!("{},{} - {},{}", self.0.line, self.0.column, self.1.line, self.1.column))
+	}
+}
+
+/// Serializes as `{"root": ..., "commentLocations": [...]}`. Comments are only
+/// present if they were captured while parsing.
+impl Serialize for Ast {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let locals = Locals::new(self);
+		let mut map = serializer.serialize_map(Some(2))?;
+		map.serialize_entry("root", &Json(&locals, &self.root))?;
+		map.serialize_entry("commentLocations", &Json(&locals, self.comments.as_slice()))?;
+		map.end()
+	}
+}
+
+/// The locals of a tree along with the annotations from their declarations,
+/// since Luau writes the annotation wherever a local is referenced.
+struct Locals<'a> {
+	ast: &'a Ast,
+	annotations: Vec<Option<Type>>
+}
+
+impl<'a> Locals<'a> {
+	fn new(ast: &'a Ast) -> Self {
+		struct Annotations(Vec<Option<Type>>);
+
+		impl Visitor for Annotations {
+			fn visit_binding(&mut self, binding: &Binding) {
+				self.0[binding.local.0 as usize] = binding.annotation.clone();
+				walk_binding(self, binding)
+			}
+		}
+
+		let mut annotations = Annotations(vec![None; ast.locals.len()]);
+		annotations.visit_block(&ast.root);
+		Self { ast, annotations: annotations.0 }
+	}
+}
+
+/// A node along with the locals of the tree it belongs to.
+struct Json<'a, T: ?Sized>(&'a Locals<'a>, &'a T);
+
+impl<'a, T: ?Sized> Json<'a, T> {
+	fn with<U: ?Sized>(&self, node: &'a U) -> Json<'a, U> {
+		Json(self.0, node)
+	}
+}
+
+/// A function along with the span it was parsed from, since function
+/// statements store that outside of the function itself.
+struct Function<'a>(&'a Locals<'a>, Span, &'a ExprFunction);
+
+/// Starts a node with its type and location.
+fn node<S: Serializer>(serializer: S, ty: &str, span: Span) -> Result<S::SerializeMap, S::Error> {
+	let mut map = serializer.serialize_map(None)?;
+	map.serialize_entry("type", ty)?;
+	map.serialize_entry("location", &span)?;
+	Ok(map)
+}
+
+fn lossy(bytes: &[u8]) -> String {
+	bytes.to_str_lossy().into_owned()
+}
+
+impl<'a, T> Serialize for Json<'a, [T]> where for<'b> Json<'b, T>: Serialize {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut seq = serializer.serialize_seq(Some(self.1.len()))?;
+
+		for item in self.1 {
+			seq.serialize_element(&self.with(item))?;
+		}
+
+		seq.end()
+	}
+}
+
+impl<'a, T> Serialize for Json<'a, Vec<T>> where for<'b> Json<'b, T>: Serialize {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.with(self.1.as_slice()).serialize(serializer)
+	}
+}
+
+impl<'a, T> Serialize for Json<'a, Option<T>> where for<'b> Json<'b, T>: Serialize {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match self.1 {
+			Some(value) => serializer.serialize_some(&self.with(value)),
+			None => serializer.serialize_none()
+		}
+	}
+}
+
+impl<'a, T> Serialize for Json<'a, Box<T>> where for<'b> Json<'b, T>: Serialize {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.with(&**self.1).serialize(serializer)
+	}
+}
+
+impl<'a> Serialize for Json<'a, Comment> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let ty = match self.1.kind {
+			CommentKind::Line => "Comment",
+			CommentKind::Block => "BlockComment",
+			CommentKind::BrokenBlock => "BrokenComment"
+		};
+
+		node(serializer, ty, self.1.span)?.end()
+	}
+}
+
+impl<'a> Serialize for Json<'a, LocalId> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let local = self.0.ast.local(*self.1);
+
+		let mut map = serializer.serialize_map(None)?;
+		map.serialize_entry("luauType", &self.with(&self.0.annotations[self.1.0 as usize]))?;
+		map.serialize_entry("name", &local.name)?;
+		map.serialize_entry("type", "AstLocal")?;
+		map.serialize_entry("location", &local.span)?;
+		map.end()
+	}
+}
+
+impl<'a> Serialize for Json<'a, Binding> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		self.with(&self.1.local).serialize(serializer)
+	}
+}
+
+impl<'a> Serialize for Json<'a, Block> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = node(serializer, "AstStatBlock", self.1.span)?;
+		map.serialize_entry("hasEnd", &self.1.has_end)?;
+		map.serialize_entry("body", &self.with(&self.1.body))?;
+		map.end()
+	}
+}
+
+impl<'a> Serialize for Json<'a, Stat> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let span = self.1.span;
+
+		match &self.1.kind {
+			StatKind::Block(block) => self.with(block).serialize(serializer),
+
+			StatKind::If(stat) => {
+				let mut map = node(serializer, "AstStatIf", span)?;
+				map.serialize_entry("condition", &self.with(&stat.condition))?;
+				map.serialize_entry("thenbody", &self.with(&stat.then_body))?;
+
+				if let Some(else_body) = &stat.else_body {
+					map.serialize_entry("elsebody", &self.with(else_body))?;
+				}
+
+				map.serialize_entry("hasThen", &stat.then_span.is_some())?;
+				map.end()
+			}
+
+			StatKind::While(stat) => {
+				let mut map = node(serializer, "AstStatWhile", span)?;
+				map.serialize_entry("condition", &self.with(&stat.condition))?;
+				map.serialize_entry("body", &self.with(&stat.body))?;
+				map.serialize_entry("hasDo", &stat.has_do)?;
+				map.serialize_entry("doLocation", &stat.do_span)?;
+				map.end()
+			}
+
+			StatKind::Repeat(stat) => {
+				let mut map = node(serializer, "AstStatRepeat", span)?;
+				map.serialize_entry("condition", &self.with(&stat.condition))?;
+				map.serialize_entry("body", &self.with(&stat.body))?;
+				map.end()
+			}
+
+			StatKind::Break => node(serializer, "AstStatBreak", span)?.end(),
+			StatKind::Continue => node(serializer, "AstStatContinue", span)?.end(),
+
+			StatKind::Return(stat) => {
+				let mut map = node(serializer, "AstStatReturn", span)?;
+				map.serialize_entry("list", &self.with(&stat.values))?;
+				map.end()
+			}
+
+			StatKind::Expr(expr) => {
+				let mut map = node(serializer, "AstStatExpr", span)?;
+				map.serialize_entry("expr", &self.with(expr))?;
+				map.end()
+			}
+
+			StatKind::Local(stat) => {
+				let mut map = node(serializer, "AstStatLocal", span)?;
+				map.serialize_entry("vars", &self.with(&stat.vars))?;
+				map.serialize_entry("values", &self.with(&stat.values))?;
+				map.end()
+			}
+
+			StatKind::For(stat) => {
+				let mut map = node(serializer, "AstStatFor", span)?;
+				map.serialize_entry("var", &self.with(&stat.var))?;
+				map.serialize_entry("from", &self.with(&stat.from))?;
+				map.serialize_entry("to", &self.with(&stat.to))?;
+
+				if let Some(step) = &stat.step {
+					map.serialize_entry("step", &self.with(step))?;
+				}
+
+				map.serialize_entry("body", &self.with(&stat.body))?;
+				map.serialize_entry("hasDo", &stat.has_do)?;
+				map.serialize_entry("doLocation", &stat.do_span)?;
+				map.end()
+			}
+
+			StatKind::ForIn(stat) => {
+				let mut map = node(serializer, "AstStatForIn", span)?;
+				map.serialize_entry("vars", &self.with(&stat.vars))?;
+				map.serialize_entry("values", &self.with(&stat.values))?;
+				map.serialize_entry("body", &self.with(&stat.body))?;
+				map.serialize_entry("hasIn", &stat.has_in)?;
+				map.serialize_entry("inLocation", &stat.in_span)?;
+				map.serialize_entry("hasDo", &stat.has_do)?;
+				map.serialize_entry("doLocation", &stat.do_span)?;
+				map.end()
+			}
+
+			StatKind::Assign(stat) => {
+				let mut map = node(serializer, "AstStatAssign", span)?;
+				map.serialize_entry("vars", &self.with(&stat.vars))?;
+				map.serialize_entry("values", &self.with(&stat.values))?;
+				map.end()
+			}
+
+			StatKind::CompoundAssign(stat) => {
+				let mut map = node(serializer, "AstStatCompoundAssign", span)?;
+				map.serialize_entry("op", binary_op_name(stat.op))?;
+				map.serialize_entry("var", &self.with(&stat.var))?;
+				map.serialize_entry("value", &self.with(&stat.value))?;
+				map.end()
+			}
+
+			StatKind::Function(stat) => {
+				let mut map = node(serializer, "AstStatFunction", span)?;
+				map.serialize_entry("name", &self.with(&stat.name))?;
+				map.serialize_entry("func", &Function(self.0, stat.func_span, &stat.func))?;
+				map.end()
+			}
+
+			StatKind::LocalFunction(stat) => {
+				let mut map = node(serializer, "AstStatLocalFunction", span)?;
+				map.serialize_entry("name", &self.with(&stat.name))?;
+				map.serialize_entry("func", &Function(self.0, stat.func_span, &stat.func))?;
+				map.end()
+			}
+
+			StatKind::TypeAlias(stat) => {
+				let mut map = node(serializer, "AstStatTypeAlias", span)?;
+				map.serialize_entry("name", &stat.name)?;
+				map.serialize_entry("generics", &self.with(&stat.generics))?;
+				map.serialize_entry("genericPacks", &self.with(&stat.generic_packs))?;
+				map.serialize_entry("value", &self.with(&stat.ty))?;
+				map.serialize_entry("exported", &stat.exported)?;
+				map.end()
+			}
+
+			StatKind::DeclareGlobal(stat) => {
+				let mut map = node(serializer, "AstStatDeclareGlobal", span)?;
+				map.serialize_entry("name", &stat.name)?;
+				map.serialize_entry("nameLocation", &stat.name_span)?;
+				map.serialize_entry("type", &self.with(&stat.ty))?;
+				map.end()
+			}
+
+			StatKind::DeclareFunction(stat) => {
+				let mut map = node(serializer, "AstStatDeclareFunction", span)?;
+				map.serialize_entry("attributes", &self.with(&stat.attributes))?;
+				map.serialize_entry("name", &stat.name)?;
+				map.serialize_entry("nameLocation", &stat.name_span)?;
+				map.serialize_entry("params", &self.with(&stat.params))?;
+				map.serialize_entry("paramNames", &self.with(&stat.param_names))?;
+				map.serialize_entry("vararg", &stat.vararg)?;
+				map.serialize_entry("varargLocation", &stat.vararg_span)?;
+				map.serialize_entry("retTypes", &self.with(&stat.return_types))?;
+				map.serialize_entry("generics", &self.with(&stat.generics))?;
+				map.serialize_entry("genericPacks", &self.with(&stat.generic_packs))?;
+				map.end()
+			}
+
+			StatKind::DeclareClass(stat) => {
+				let mut map = node(serializer, "AstStatDeclareClass", span)?;
+				map.serialize_entry("name", &stat.name)?;
+
+				if let Some(super_name) = &stat.super_name {
+					map.serialize_entry("superName", super_name)?;
+				}
+
+				map.serialize_entry("props", &self.with(&stat.props))?;
+				map.serialize_entry("indexer", &self.with(&stat.indexer))?;
+				map.end()
+			}
+
+			StatKind::Error(stat) => {
+				let mut map = node(serializer, "AstStatError", span)?;
+				map.serialize_entry("expressions", &self.with(&stat.expressions))?;
+				map.serialize_entry("statements", &self.with(&stat.statements))?;
+				map.serialize_entry("messageIndex", &stat.message_index)?;
+				map.end()
+			}
+		}
+	}
+}
+
+impl<'a> Serialize for Json<'a, Expr> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let span = self.1.span;
+
+		match &self.1.kind {
+			ExprKind::Group(expr) => {
+				let mut map = node(serializer, "AstExprGroup", span)?;
+				map.serialize_entry("expr", &self.with(expr))?;
+				map.end()
+			}
+
+			ExprKind::Nil => node(serializer, "AstExprConstantNil", span)?.end(),
+
+			ExprKind::Bool(value) => {
+				let mut map = node(serializer, "AstExprConstantBool", span)?;
+				map.serialize_entry("value", value)?;
+				map.end()
+			}
+
+			ExprKind::Number(value) => {
+				let mut map = node(serializer, "AstExprConstantNumber", span)?;
+				map.serialize_entry("value", value)?;
+				map.end()
+			}
+
+			ExprKind::String(string) => {
+				let mut map = node(serializer, "AstExprConstantString", span)?;
+				map.serialize_entry("value", &lossy(&string.value))?;
+				map.end()
+			}
+
+			ExprKind::Local(expr) => {
+				let mut map = node(serializer, "AstExprLocal", span)?;
+				map.serialize_entry("local", &self.with(&expr.local))?;
+				map.end()
+			}
+
+			ExprKind::Global(name) => {
+				let mut map = node(serializer, "AstExprGlobal", span)?;
+				map.serialize_entry("global", name)?;
+				map.end()
+			}
+
+			ExprKind::Varargs => node(serializer, "AstExprVarargs", span)?.end(),
+
+			ExprKind::Call(expr) => {
+				let mut map = node(serializer, "AstExprCall", span)?;
+				map.serialize_entry("func", &self.with(&expr.func))?;
+				map.serialize_entry("args", &self.with(&expr.args))?;
+				map.serialize_entry("self", &expr.is_self)?;
+				map.serialize_entry("argLocation", &expr.arg_span)?;
+				map.end()
+			}
+
+			ExprKind::IndexName(expr) => {
+				let mut map = node(serializer, "AstExprIndexName", span)?;
+				map.serialize_entry("expr", &self.with(&expr.expr))?;
+				map.serialize_entry("index", &expr.index)?;
+				map.serialize_entry("indexLocation", &expr.index_span)?;
+				map.serialize_entry("op", &expr.op.to_string())?;
+				map.end()
+			}
+
+			ExprKind::IndexExpr(expr) => {
+				let mut map = node(serializer, "AstExprIndexExpr", span)?;
+				map.serialize_entry("expr", &self.with(&expr.expr))?;
+				map.serialize_entry("index", &self.with(&expr.index))?;
+				map.end()
+			}
+
+			ExprKind::Function(func) => Function(self.0, span, func).serialize(serializer),
+
+			ExprKind::Table(expr) => {
+				let mut map = node(serializer, "AstExprTable", span)?;
+				map.serialize_entry("items", &self.with(&expr.items))?;
+				map.end()
+			}
+
+			ExprKind::Unary(expr) => {
+				let op = match expr.op {
+					UnaryOp::Not => "Not",
+					UnaryOp::Minus => "Minus",
+					UnaryOp::Len => "Len"
+				};
+
+				let mut map = node(serializer, "AstExprUnary", span)?;
+				map.serialize_entry("op", op)?;
+				map.serialize_entry("expr", &self.with(&expr.expr))?;
+				map.end()
+			}
+
+			ExprKind::Binary(expr) => {
+				let mut map = node(serializer, "AstExprBinary", span)?;
+				map.serialize_entry("op", binary_op_name(expr.op))?;
+				map.serialize_entry("left", &self.with(&expr.left))?;
+				map.serialize_entry("right", &self.with(&expr.right))?;
+				map.end()
+			}
+
+			ExprKind::TypeAssertion(expr) => {
+				let mut map = node(serializer, "AstExprTypeAssertion", span)?;
+				map.serialize_entry("expr", &self.with(&expr.expr))?;
+				map.serialize_entry("annotation", &self.with(&expr.annotation))?;
+				map.end()
+			}
+
+			ExprKind::IfElse(expr) => {
+				let mut map = node(serializer, "AstExprIfElse", span)?;
+				map.serialize_entry("condition", &self.with(&expr.condition))?;
+				map.serialize_entry("hasThen", &expr.has_then)?;
+				map.serialize_entry("trueExpr", &self.with(&expr.true_expr))?;
+				map.serialize_entry("hasElse", &expr.has_else)?;
+				map.serialize_entry("falseExpr", &self.with(&expr.false_expr))?;
+				map.end()
+			}
+
+			ExprKind::InterpString(expr) => {
+				let strings: Vec<String> = expr.strings.iter().map(|string| lossy(string)).collect();
+
+				let mut map = node(serializer, "AstExprInterpString", span)?;
+				map.serialize_entry("strings", &strings)?;
+				map.serialize_entry("expressions", &self.with(&expr.expressions))?;
+				map.end()
+			}
+
+			ExprKind::Error(expr) => {
+				let mut map = node(serializer, "AstExprError", span)?;
+				map.serialize_entry("expressions", &self.with(&expr.expressions))?;
+				map.serialize_entry("messageIndex", &expr.message_index)?;
+				map.end()
+			}
+		}
+	}
+}
+
+impl<'a> Serialize for Function<'a> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let Self(locals, span, func) = *self;
+		let json = Json(locals, func);
+
+		let mut map = node(serializer, "AstExprFunction", span)?;
+		map.serialize_entry("attributes", &json.with(&func.attributes))?;
+
+		if !func.generics.is_empty() || !func.generic_packs.is_empty() {
+			map.serialize_entry("generics", &json.with(&func.generics))?;
+			map.serialize_entry("genericPacks", &json.with(&func.generic_packs))?;
+		}
+
+		if let Some(self_local) = &func.self_local {
+			map.serialize_entry("self", &json.with(self_local))?;
+		}
+
+		map.serialize_entry("args", &json.with(&func.args))?;
+
+		if let Some(return_annotation) = &func.return_annotation {
+			map.serialize_entry("returnAnnotation", &json.with(return_annotation))?;
+		}
+
+		map.serialize_entry("vararg", &func.vararg)?;
+		map.serialize_entry("varargLocation", &func.vararg_span)?;
+
+		if let Some(vararg_annotation) = &func.vararg_annotation {
+			map.serialize_entry("varargAnnotation", &json.with(vararg_annotation))?;
+		}
+
+		map.serialize_entry("body", &json.with(&func.body))?;
+		map.serialize_entry("functionDepth", &func.function_depth)?;
+		map.serialize_entry("debugname", &func.debug_name)?;
+		map.end()
+	}
+}
+
+impl<'a> Serialize for Json<'a, TableItem> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let kind = match self.1.kind {
+			TableItemKind::List => "item",
+			TableItemKind::Record => "record",
+			TableItemKind::General => "general"
+		};
+
+		let mut map = serializer.serialize_map(None)?;
+		map.serialize_entry("type", "AstExprTableItem")?;
+		map.serialize_entry("kind", kind)?;
+
+		if let Some(key) = &self.1.key {
+			map.serialize_entry("key", &self.with(key))?;
+		}
+
+		map.serialize_entry("value", &self.with(&self.1.value))?;
+		map.end()
+	}
+}
+
+impl<'a> Serialize for Json<'a, Type> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let span = self.1.span;
+
+		match &self.1.kind {
+			TypeKind::Reference(ty) => {
+				let mut map = node(serializer, "AstTypeReference", span)?;
+
+				if let Some(prefix) = &ty.prefix {
+					map.serialize_entry("prefix", prefix)?;
+				}
+
+				if let Some(prefix_span) = &ty.prefix_span {
+					map.serialize_entry("prefixLocation", prefix_span)?;
+				}
+
+				map.serialize_entry("name", &ty.name)?;
+				map.serialize_entry("nameLocation", &ty.name_span)?;
+				map.serialize_entry("parameters", &self.with(&ty.parameters))?;
+				map.end()
+			}
+
+			TypeKind::Table(ty) => {
+				let mut map = node(serializer, "AstTypeTable", span)?;
+				map.serialize_entry("props", &self.with(&ty.props))?;
+				map.serialize_entry("indexer", &self.with(&ty.indexer))?;
+				map.end()
+			}
+
+			TypeKind::Function(ty) => {
+				let mut map = node(serializer, "AstTypeFunction", span)?;
+				map.serialize_entry("attributes", &self.with(&ty.attributes))?;
+				map.serialize_entry("generics", &self.with(&ty.generics))?;
+				map.serialize_entry("genericPacks", &self.with(&ty.generic_packs))?;
+				map.serialize_entry("argTypes", &self.with(&ty.arg_types))?;
+				map.serialize_entry("argNames", &self.with(&ty.arg_names))?;
+				map.serialize_entry("returnTypes", &self.with(&ty.return_types))?;
+				map.end()
+			}
+
+			TypeKind::Typeof(expr) => {
+				let mut map = node(serializer, "AstTypeTypeof", span)?;
+				map.serialize_entry("expr", &self.with(expr))?;
+				map.end()
+			}
+
+			TypeKind::Union(ty) => {
+				let mut map = node(serializer, "AstTypeUnion", span)?;
+				map.serialize_entry("types", &self.with(&ty.types))?;
+				map.end()
+			}
+
+			TypeKind::Intersection(ty) => {
+				let mut map = node(serializer, "AstTypeIntersection", span)?;
+				map.serialize_entry("types", &self.with(&ty.types))?;
+				map.end()
+			}
+
+			TypeKind::SingletonBool(value) => {
+				let mut map = node(serializer, "AstTypeSingletonBool", span)?;
+				map.serialize_entry("value", value)?;
+				map.end()
+			}
+
+			TypeKind::SingletonString(value) => {
+				let mut map = node(serializer, "AstTypeSingletonString", span)?;
+				map.serialize_entry("value", &lossy(value))?;
+				map.end()
+			}
+
+			TypeKind::Error(ty) => {
+				let mut map = node(serializer, "AstTypeError", span)?;
+				map.serialize_entry("types", &self.with(&ty.types))?;
+				map.serialize_entry("messageIndex", &ty.message_index)?;
+				map.end()
+			}
+		}
+	}
+}
+
+impl<'a> Serialize for Json<'a, TypePack> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let span = self.1.span;
+
+		match &self.1.kind {
+			TypePackKind::Explicit(list) => {
+				let mut map = node(serializer, "AstTypePackExplicit", span)?;
+				map.serialize_entry("typeList", &self.with(list))?;
+				map.end()
+			}
+
+			TypePackKind::Variadic(ty) => {
+				let mut map = node(serializer, "AstTypePackVariadic", span)?;
+				map.serialize_entry("variadicType", &self.with(ty))?;
+				map.end()
+			}
+
+			TypePackKind::Generic(name) => {
+				let mut map = node(serializer, "AstTypePackGeneric", span)?;
+				map.serialize_entry("genericName", name)?;
+				map.end()
+			}
+		}
+	}
+}
+
+impl<'a> Serialize for Json<'a, TypeList> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(None)?;
+		map.serialize_entry("type", "AstTypeList")?;
+		map.serialize_entry("types", &self.with(&self.1.types))?;
+
+		if let Some(tail) = &self.1.tail {
+			map.serialize_entry("tailType", &self.with(tail))?;
+		}
+
+		map.end()
+	}
+}
+
+impl<'a> Serialize for Json<'a, TypeOrPack> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match self.1 {
+			TypeOrPack::Type(ty) => self.with(ty).serialize(serializer),
+			TypeOrPack::Pack(pack) => self.with(pack).serialize(serializer)
+		}
+	}
+}
+
+impl<'a> Serialize for Json<'a, TableProp> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(None)?;
+		map.serialize_entry("name", &self.1.name)?;
+		map.serialize_entry("type", "AstTableProp")?;
+		map.serialize_entry("location", &self.1.span)?;
+		map.serialize_entry("propType", &self.with(&self.1.ty))?;
+		map.end()
+	}
+}
+
+impl<'a> Serialize for Json<'a, TableIndexer> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(None)?;
+		map.serialize_entry("location", &self.1.span)?;
+		map.serialize_entry("indexType", &self.with(&self.1.index_type))?;
+		map.serialize_entry("resultType", &self.with(&self.1.result_type))?;
+		map.end()
+	}
+}
+
+impl<'a> Serialize for Json<'a, GenericType> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(None)?;
+		map.serialize_entry("type", "AstGenericType")?;
+		map.serialize_entry("name", &self.1.name)?;
+
+		if let Some(default) = &self.1.default {
+			map.serialize_entry("luauType", &self.with(default))?;
+		}
+
+		map.end()
+	}
+}
+
+impl<'a> Serialize for Json<'a, GenericTypePack> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(None)?;
+		map.serialize_entry("type", "AstGenericTypePack")?;
+		map.serialize_entry("name", &self.1.name)?;
+
+		if let Some(default) = &self.1.default {
+			map.serialize_entry("luauType", &self.with(default))?;
+		}
+
+		map.end()
+	}
+}
+
+impl<'a> Serialize for Json<'a, ArgumentName> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = node(serializer, "AstArgumentName", self.1.span)?;
+		map.serialize_entry("name", &self.1.name)?;
+		map.end()
+	}
+}
+
+impl<'a> Serialize for Json<'a, DeclaredClassProp> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let mut map = serializer.serialize_map(None)?;
+		map.serialize_entry("name", &self.1.name)?;
+		map.serialize_entry("nameLocation", &self.1.name_span)?;
+		map.serialize_entry("type", "AstDeclaredClassProp")?;
+		map.serialize_entry("luauType", &self.with(&self.1.ty))?;
+		map.serialize_entry("location", &self.1.span)?;
+		map.end()
+	}
+}
+
+impl<'a> Serialize for Json<'a, Attr> {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let name = match self.1.kind {
+			AttrKind::Checked => "checked",
+			AttrKind::Native => "native",
+			AttrKind::Unknown(_) => "unknown"
+		};
+
+		let mut map = node(serializer, "AstAttr", self.1.span)?;
+		map.serialize_entry("name", name)?;
+		map.end()
+	}
+}
+
+fn binary_op_name(op: BinaryOp) -> &'static str {
+	match op {
+		BinaryOp::Add => "Add",
+		BinaryOp::Sub => "Sub",
+		BinaryOp::Mul => "Mul",
+		BinaryOp::Div => "Div",
+		BinaryOp::FloorDiv => "FloorDiv",
+		BinaryOp::Mod => "Mod",
+		BinaryOp::Pow => "Pow",
+		BinaryOp::Concat => "Concat",
+		BinaryOp::CompareNe => "CompareNe",
+		BinaryOp::CompareEq => "CompareEq",
+		BinaryOp::CompareLt => "CompareLt",
+		BinaryOp::CompareLe => "CompareLe",
+		BinaryOp::CompareGt => "CompareGt",
+		BinaryOp::CompareGe => "CompareGe",
+		BinaryOp::And => "And",
+		BinaryOp::Or => "Or"
+	}
+}
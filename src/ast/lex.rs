@@ -0,0 +1,287 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::ptr::NonNull;
+
+use luau_sys::glue::{gluau_Buffer, gluau_Lexer, gluau_lexer_free, gluau_lexer_new, gluau_lexer_next, gluau_TokenKind};
+
+use crate::ast::source_map::SourceMap;
+use crate::ast::Span;
+use crate::fvalue;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Keyword {
+	And,
+	Break,
+	Do,
+	Else,
+	ElseIf,
+	End,
+	False,
+	For,
+	Function,
+	If,
+	In,
+	Local,
+	Nil,
+	Not,
+	Or,
+	Repeat,
+	Return,
+	Then,
+	True,
+	Until,
+	While
+}
+
+impl Keyword {
+	// in the same order as Luau's reserved words
+	const ALL: [Keyword; 21] = [
+		Self::And, Self::Break, Self::Do, Self::Else, Self::ElseIf, Self::End, Self::False, Self::For, Self::Function,
+		Self::If, Self::In, Self::Local, Self::Nil, Self::Not, Self::Or, Self::Repeat, Self::Return, Self::Then,
+		Self::True, Self::Until, Self::While
+	];
+
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::And => "and",
+			Self::Break => "break",
+			Self::Do => "do",
+			Self::Else => "else",
+			Self::ElseIf => "elseif",
+			Self::End => "end",
+			Self::False => "false",
+			Self::For => "for",
+			Self::Function => "function",
+			Self::If => "if",
+			Self::In => "in",
+			Self::Local => "local",
+			Self::Nil => "nil",
+			Self::Not => "not",
+			Self::Or => "or",
+			Self::Repeat => "repeat",
+			Self::Return => "return",
+			Self::Then => "then",
+			Self::True => "true",
+			Self::Until => "until",
+			Self::While => "while"
+		}
+	}
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TokenKind {
+	/// A single character of punctuation, such as `(`, `+` or `#`.
+	Char(char),
+	/// `==`
+	Equal,
+	/// `<=`
+	LessEqual,
+	/// `>=`
+	GreaterEqual,
+	/// `~=`
+	NotEqual,
+	/// `..`
+	Dot2,
+	/// `...`
+	Dot3,
+	/// `->`
+	SkinnyArrow,
+	/// `::`
+	DoubleColon,
+	/// `//`
+	FloorDiv,
+	/// The start of an interpolated string up to its first `{`, such as
+	/// `` `a{ ``.
+	InterpStringBegin,
+	/// The part of an interpolated string between two expressions, such as
+	/// `}b{`.
+	InterpStringMid,
+	/// The end of an interpolated string after its last expression, such as
+	/// `` }c` ``.
+	InterpStringEnd,
+	/// An interpolated string without any expressions.
+	InterpStringSimple,
+	AddAssign,
+	SubAssign,
+	MulAssign,
+	DivAssign,
+	FloorDivAssign,
+	ModAssign,
+	PowAssign,
+	ConcatAssign,
+	/// A long string, such as `[[text]]`.
+	RawString,
+	QuotedString,
+	Number,
+	Name,
+	Keyword(Keyword),
+	Comment,
+	BlockComment,
+	/// An attribute, such as `@native`.
+	Attribute,
+	/// A quoted string that is missing its closing quote or contains a line
+	/// break.
+	BrokenString,
+	/// A block comment that is never closed.
+	BrokenComment,
+	/// A `\u{...}` escape that is not a valid code point.
+	BrokenUnicode,
+	/// A `{{` inside an interpolated string.
+	BrokenInterpDoubleBrace,
+	/// Anything else Luau does not understand, such as a malformed long string.
+	Error,
+	/// A token that is not known to these bindings.
+	Unknown
+}
+
+impl TokenKind {
+	fn from_gluau(kind: gluau_TokenKind, data: u8) -> Option<Self> {
+		Some(match kind {
+			gluau_TokenKind::TOKEN_EOF => return None,
+			gluau_TokenKind::TOKEN_CHAR => Self::Char(data as char),
+			gluau_TokenKind::TOKEN_EQUAL => Self::Equal,
+			gluau_TokenKind::TOKEN_LESS_EQUAL => Self::LessEqual,
+			gluau_TokenKind::TOKEN_GREATER_EQUAL => Self::GreaterEqual,
+			gluau_TokenKind::TOKEN_NOT_EQUAL => Self::NotEqual,
+			gluau_TokenKind::TOKEN_DOT2 => Self::Dot2,
+			gluau_TokenKind::TOKEN_DOT3 => Self::Dot3,
+			gluau_TokenKind::TOKEN_SKINNY_ARROW => Self::SkinnyArrow,
+			gluau_TokenKind::TOKEN_DOUBLE_COLON => Self::DoubleColon,
+			gluau_TokenKind::TOKEN_FLOOR_DIV => Self::FloorDiv,
+			gluau_TokenKind::TOKEN_INTERP_STRING_BEGIN => Self::InterpStringBegin,
+			gluau_TokenKind::TOKEN_INTERP_STRING_MID => Self::InterpStringMid,
+			gluau_TokenKind::TOKEN_INTERP_STRING_END => Self::InterpStringEnd,
+			gluau_TokenKind::TOKEN_INTERP_STRING_SIMPLE => Self::InterpStringSimple,
+			gluau_TokenKind::TOKEN_ADD_ASSIGN => Self::AddAssign,
+			gluau_TokenKind::TOKEN_SUB_ASSIGN => Self::SubAssign,
+			gluau_TokenKind::TOKEN_MUL_ASSIGN => Self::MulAssign,
+			gluau_TokenKind::TOKEN_DIV_ASSIGN => Self::DivAssign,
+			gluau_TokenKind::TOKEN_FLOOR_DIV_ASSIGN => Self::FloorDivAssign,
+			gluau_TokenKind::TOKEN_MOD_ASSIGN => Self::ModAssign,
+			gluau_TokenKind::TOKEN_POW_ASSIGN => Self::PowAssign,
+			gluau_TokenKind::TOKEN_CONCAT_ASSIGN => Self::ConcatAssign,
+			gluau_TokenKind::TOKEN_RAW_STRING => Self::RawString,
+			gluau_TokenKind::TOKEN_QUOTED_STRING => Self::QuotedString,
+			gluau_TokenKind::TOKEN_NUMBER => Self::Number,
+			gluau_TokenKind::TOKEN_NAME => Self::Name,
+			gluau_TokenKind::TOKEN_COMMENT => Self::Comment,
+			gluau_TokenKind::TOKEN_BLOCK_COMMENT => Self::BlockComment,
+			gluau_TokenKind::TOKEN_ATTRIBUTE => Self::Attribute,
+			gluau_TokenKind::TOKEN_BROKEN_STRING => Self::BrokenString,
+			gluau_TokenKind::TOKEN_BROKEN_COMMENT => Self::BrokenComment,
+			gluau_TokenKind::TOKEN_BROKEN_UNICODE => Self::BrokenUnicode,
+			gluau_TokenKind::TOKEN_BROKEN_INTERP_DOUBLE_BRACE => Self::BrokenInterpDoubleBrace,
+			gluau_TokenKind::TOKEN_ERROR => Self::Error,
+			gluau_TokenKind::TOKEN_KEYWORD => Keyword::ALL.get(data as usize).map_or(Self::Unknown, |keyword| Self::Keyword(*keyword)),
+			gluau_TokenKind::TOKEN_UNKNOWN => Self::Unknown
+		})
+	}
+
+	/// Returns whether this token is a comment, including unterminated block
+	/// comments.
+	pub fn is_comment(&self) -> bool {
+		matches!(self, Self::Comment | Self::BlockComment | Self::BrokenComment)
+	}
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Token<'a> {
+	pub kind: TokenKind,
+	pub span: Span,
+	/// The source text covered by the token, including any quotes, brackets
+	/// or comment markers.
+	pub text: &'a str
+}
+
+/// An iterator over the tokens of a source. See [`lex`].
+pub struct Tokens<'a> {
+	lexer: Option<NonNull<gluau_Lexer>>,
+	source: SourceMap<'a>
+}
+
+impl<'a> Iterator for Tokens<'a> {
+	type Item = Token<'a>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let lexer = self.lexer?;
+
+		// the lexer consults FFlags as it goes, so lock them per token rather
+		// than for the lifetime of the iterator
+		let flags = fvalue::read_lock();
+
+		// SAFETY: the lexer is alive until it is freed below, and the source it
+		// points into is borrowed for 'a
+		let token = unsafe { gluau_lexer_next(lexer.as_ptr()) };
+
+		drop(flags);
+
+		let Some(kind) = TokenKind::from_gluau(token.kind, token.data) else {
+			self.free();
+			return None
+		};
+
+		let span = Span::new(token.span.start_line, token.span.start_column, token.span.end_line, token.span.end_column);
+
+		Some(Token {
+			kind,
+			span,
+			text: self.source.slice(span).unwrap_or("")
+		})
+	}
+}
+
+impl<'a> Tokens<'a> {
+	fn free(&mut self) {
+		if let Some(lexer) = self.lexer.take() {
+			// SAFETY: the lexer was allocated by gluau_lexer_new and is only
+			// freed once, since it has been taken
+			unsafe { gluau_lexer_free(lexer.as_ptr()) }
+		}
+	}
+}
+
+impl<'a> Drop for Tokens<'a> {
+	fn drop(&mut self) {
+		self.free();
+	}
+}
+
+/// Splits a source into tokens, the same way Luau's parser sees them, except
+/// that comments are included. Lexing never fails; invalid input is reported
+/// as tokens such as [`TokenKind::BrokenString`] or [`TokenKind::Error`].
+///
+/// Interpolated strings are split into segments, with the tokens of each
+/// embedded expression in between:
+///
+/// ```text
+/// `a{b}c`  =>  InterpStringBegin "`a{", Name "b", InterpStringEnd "}c`"
+/// ```
+pub fn lex(source: &str) -> Tokens<'_> {
+	let buffer = gluau_Buffer {
+		data: source.as_ptr() as _,
+		len: source.len() as _
+	};
+
+	let _flags = fvalue::read_lock();
+
+	// SAFETY: the lexer only reads from the buffer, which outlives it
+	let lexer = unsafe { gluau_lexer_new(buffer) };
+
+	Tokens {
+		lexer: NonNull::new(lexer),
+		source: SourceMap::new(source)
+	}
+}
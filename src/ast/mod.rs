@@ -13,9 +13,33 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::ffi::CStr;
 use std::fmt::{Debug, Display, Formatter, Write};
 
-use luau_sys::glue::gluau_ParseOpts;
+use luau_sys::glue::{gluau_Buffer, gluau_Error, gluau_ErrorCode, gluau_find_confusable, gluau_Optionality, gluau_parse, gluau_ParseOpts, gluau_Span};
+
+use crate::ast::source_map::SourceMap;
+use crate::ast::tree::Ast;
+use crate::fvalue;
+
+pub use lex::{lex, Keyword, Token, TokenKind, Tokens};
+pub use print::{print, print_with_options, PrintOptions};
+pub use visit::Visitor;
+pub use visit_mut::VisitorMut;
+
+#[macro_use]
+mod visitor;
+
+pub mod source_map;
+pub mod tree;
+pub mod visit;
+pub mod visit_mut;
+
+mod decode;
+#[cfg(feature = "serde")]
+mod json;
+mod lex;
+mod print;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum Mode {
@@ -105,4 +129,252 @@ impl Span {
 			column: end_column
 		})
 	}
+
+	/// Renders the lines of `source` covered by this span, underlining the span
+	/// and labelling it with `message`. See [`SourceMap::render_snippet`].
+	///
+	/// If many spans will be rendered against the same source, build a
+	/// [`SourceMap`] once and use that instead.
+	pub fn render(&self, source: &str, message: &str) -> String {
+		SourceMap::new(source).render_snippet(*self, Some(message), &[])
+	}
+}
+
+/// A replacement for part of the source that would likely fix an error.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Suggestion {
+	pub span: Span,
+	pub replacement: String
+}
+
+impl Display for Suggestion {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "did you mean `{}`?", self.replacement)
+	}
+}
+
+/// A span with an attached explanation, pointing at code related to the
+/// primary span of an error.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Label {
+	pub span: Span,
+	pub message: String
+}
+
+/// Identifies what went wrong, independently of the wording of the message.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum ErrorCode {
+	/// An error that isn't recognized by these bindings.
+	Unknown,
+	/// Luau failed internally, for example by running out of memory.
+	Internal,
+	UnexpectedToken,
+	/// A block or bracket was never closed. [`Error::related`] points at where
+	/// it was opened.
+	UnclosedBlock,
+	IncompleteStatement,
+	AmbiguousSyntax,
+	MalformedNumber,
+	MalformedString,
+	UnfinishedComment,
+	UnexpectedCharacter,
+	InvalidVararg,
+	RecursionLimit,
+	RegisterLimit,
+	UpvalueLimit,
+	LocalLimit,
+	ConstantLimit
+}
+
+impl ErrorCode {
+	/// A short, stable name for the code, for use in machine readable output.
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Unknown => "unknown",
+			Self::Internal => "internal",
+			Self::UnexpectedToken => "unexpected-token",
+			Self::UnclosedBlock => "unclosed-block",
+			Self::IncompleteStatement => "incomplete-statement",
+			Self::AmbiguousSyntax => "ambiguous-syntax",
+			Self::MalformedNumber => "malformed-number",
+			Self::MalformedString => "malformed-string",
+			Self::UnfinishedComment => "unfinished-comment",
+			Self::UnexpectedCharacter => "unexpected-character",
+			Self::InvalidVararg => "invalid-vararg",
+			Self::RecursionLimit => "recursion-limit",
+			Self::RegisterLimit => "register-limit",
+			Self::UpvalueLimit => "upvalue-limit",
+			Self::LocalLimit => "local-limit",
+			Self::ConstantLimit => "constant-limit"
+		}
+	}
+
+	fn from_gluau(code: gluau_ErrorCode) -> Self {
+		match code {
+			gluau_ErrorCode::ERROR_CODE_UNKNOWN => Self::Unknown,
+			gluau_ErrorCode::ERROR_CODE_INTERNAL => Self::Internal,
+			gluau_ErrorCode::ERROR_CODE_UNEXPECTED_TOKEN => Self::UnexpectedToken,
+			gluau_ErrorCode::ERROR_CODE_UNCLOSED_BLOCK => Self::UnclosedBlock,
+			gluau_ErrorCode::ERROR_CODE_INCOMPLETE_STATEMENT => Self::IncompleteStatement,
+			gluau_ErrorCode::ERROR_CODE_AMBIGUOUS_SYNTAX => Self::AmbiguousSyntax,
+			gluau_ErrorCode::ERROR_CODE_MALFORMED_NUMBER => Self::MalformedNumber,
+			gluau_ErrorCode::ERROR_CODE_MALFORMED_STRING => Self::MalformedString,
+			gluau_ErrorCode::ERROR_CODE_UNFINISHED_COMMENT => Self::UnfinishedComment,
+			gluau_ErrorCode::ERROR_CODE_UNEXPECTED_CHARACTER => Self::UnexpectedCharacter,
+			gluau_ErrorCode::ERROR_CODE_INVALID_VARARG => Self::InvalidVararg,
+			gluau_ErrorCode::ERROR_CODE_RECURSION_LIMIT => Self::RecursionLimit,
+			gluau_ErrorCode::ERROR_CODE_REGISTER_LIMIT => Self::RegisterLimit,
+			gluau_ErrorCode::ERROR_CODE_UPVALUE_LIMIT => Self::UpvalueLimit,
+			gluau_ErrorCode::ERROR_CODE_LOCAL_LIMIT => Self::LocalLimit,
+			gluau_ErrorCode::ERROR_CODE_CONSTANT_LIMIT => Self::ConstantLimit
+		}
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Error {
+	pub message: String,
+	pub span: Span,
+	pub code: ErrorCode,
+	/// Code related to the error, such as the start of a block that was never
+	/// closed.
+	pub related: Option<Label>,
+	/// A fix for the error, if one is known. This is currently only provided
+	/// for Unicode characters that look like ASCII ones, such as a Greek
+	/// question mark in place of a semicolon, or smart quotes.
+	pub suggestion: Option<Suggestion>
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		Display::fmt(&self.span, f)?;
+		f.write_str(": ")?;
+		Display::fmt(&self.message, f)
+	}
+}
+
+impl std::error::Error for Error {}
+
+fn span_from_gluau(span: gluau_Span) -> Span {
+	Span::new(span.start_line as _, span.start_column as _, span.end_line as _, span.end_column as _)
+}
+
+pub(crate) fn error_from_gluau(gluau: gluau_Error) -> Error {
+	let related = match gluau.related.presence {
+		gluau_Optionality::Some => Some(Label {
+			span: span_from_gluau(gluau.related.span),
+			// SAFETY: the buffer was allocated by the glue from a string
+			message: unsafe { String::from_raw_parts(gluau.related.message.data as _, gluau.related.message.len as _, gluau.related.message.len as _) }
+		}),
+
+		gluau_Optionality::None => None
+	};
+
+	Error {
+		// SAFETY: the buffer was allocated by the glue from a string
+		message: unsafe { String::from_raw_parts(gluau.message.data as _, gluau.message.len as _, gluau.message.len as _) },
+		span: span_from_gluau(gluau.span),
+		code: ErrorCode::from_gluau(gluau.code),
+		related,
+		suggestion: None
+	}
+}
+
+/// Returns the ASCII character that `char` looks like, if any.
+fn confusable(char: char) -> Option<&'static str> {
+	// SAFETY: the glue returns either null or a static, nul-terminated string
+	let replacement = unsafe { gluau_find_confusable(char as u32) };
+
+	if replacement.is_null() {
+		return None
+	}
+
+	unsafe { CStr::from_ptr(replacement) }.to_str().ok()
+}
+
+/// Attaches a [`Suggestion`] to every error that points at a Unicode character
+/// that looks like an ASCII one.
+pub(crate) fn suggest_confusables(errors: &mut [Error], source: &str) {
+	if errors.is_empty() {
+		return
+	}
+
+	let map = SourceMap::new(source);
+
+	for error in errors {
+		let Some(offset) = map.offset(error.span.0) else { continue };
+		let Some(char) = source.get(offset..).and_then(|rest| rest.chars().next()) else { continue };
+
+		if char.is_ascii() {
+			continue
+		}
+
+		let Some(replacement) = confusable(char) else { continue };
+		let Some(span) = map.span(offset..offset + char.len_utf8()) else { continue };
+
+		error.suggestion = Some(Suggestion {
+			span,
+			replacement: replacement.to_owned()
+		});
+	}
+}
+
+/// A tree produced by [`parse_recovering`], along with every syntax error that
+/// was recovered from while producing it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParseResult {
+	/// The best-effort tree. Wherever the source could not be parsed, it
+	/// contains error nodes such as [`tree::StatKind::Error`], whose
+	/// `message_index` points into `errors`.
+	pub ast: Ast,
+	pub errors: Vec<Error>
+}
+
+/// Parses a source into an [`Ast`]. If the source contains any syntax errors,
+/// all of them are returned instead.
+pub fn parse(source: &str, opts: &ParseOptions) -> Result<Ast, Vec<Error>> {
+	let result = parse_recovering(source, opts)?;
+
+	if result.errors.is_empty() {
+		Ok(result.ast)
+	} else {
+		Err(result.errors)
+	}
+}
+
+/// Parses a source into an [`Ast`], recovering from syntax errors the same way
+/// Luau does, so that tooling can work with code that is still being typed.
+///
+/// An error is only returned if Luau could not produce a tree at all.
+pub fn parse_recovering(source: &str, opts: &ParseOptions) -> Result<ParseResult, Vec<Error>> {
+	let buffer = gluau_Buffer {
+		data: source.as_ptr() as _,
+		len: source.len() as _
+	};
+
+	let flags = fvalue::read_lock();
+
+	// SAFETY: C++ exceptions are caught by the C++ glue, and never unwind into Rust
+	let result = unsafe { gluau_parse(buffer, opts.0) };
+
+	drop(flags);
+
+	let mut errors: Vec<Error> = unsafe {
+		// SAFETY: this is also fine
+		Vec::from_raw_parts(result.errors.errors, result.errors.len as _, result.errors.len as _)
+	}.into_iter().map(error_from_gluau).collect();
+
+	suggest_confusables(&mut errors, source);
+
+	if result.ast.data.is_null() {
+		return Err(errors)
+	}
+
+	// SAFETY: the buffer is non-null, so it was allocated by the glue
+	let ast = unsafe { Vec::from_raw_parts(result.ast.data as *mut u8, result.ast.len as _, result.ast.len as _) };
+
+	Ok(ParseResult {
+		ast: decode::decode(&ast, source),
+		errors
+	})
 }
@@ -0,0 +1,1049 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Turns an [`Ast`] back into Luau source.
+
+use bstr::ByteSlice;
+
+use crate::ast::Position;
+use crate::ast::tree::*;
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct PrintOptions {
+	/// Reinsert [`Ast::comments`] before the statements they preceded. Comments
+	/// within a statement are moved after it.
+	pub comments: bool
+}
+
+/// Prints an AST as Luau source. Parsing the output produces an equivalent
+/// AST, except for spans and error nodes.
+///
+/// Formatting is not preserved: the output is indented with tabs, and only
+/// has parentheses where the AST has [`ExprKind::Group`] nodes, or where they
+/// are needed to keep the structure of the tree. Error nodes have no source
+/// representation, so statements are omitted, expressions are printed as
+/// `nil` and types as `any`.
+pub fn print(ast: &Ast) -> String {
+	print_with_options(ast, &PrintOptions::default())
+}
+
+pub fn print_with_options(ast: &Ast, opts: &PrintOptions) -> String {
+	let mut printer = Printer {
+		ast,
+		out: String::new(),
+		indent: 0,
+		comments: if opts.comments { &ast.comments } else { &[] },
+		last_line: None
+	};
+
+	printer.body(&ast.root.body);
+	printer.flush_comments(None);
+	printer.out
+}
+
+const UNARY_PRIORITY: u8 = 8;
+
+/// The left and right priorities of a binary operator, as used by Luau's
+/// parser. Right-associative operators have a lower right priority.
+fn binary_priority(op: BinaryOp) -> (u8, u8) {
+	match op {
+		BinaryOp::Add | BinaryOp::Sub => (6, 6),
+		BinaryOp::Mul | BinaryOp::Div | BinaryOp::FloorDiv | BinaryOp::Mod => (7, 7),
+		BinaryOp::Pow => (10, 9),
+		BinaryOp::Concat => (5, 4),
+		BinaryOp::CompareNe | BinaryOp::CompareEq | BinaryOp::CompareLt | BinaryOp::CompareLe | BinaryOp::CompareGt | BinaryOp::CompareGe => (3, 3),
+		BinaryOp::And => (2, 2),
+		BinaryOp::Or => (1, 1)
+	}
+}
+
+pub(crate) fn binary_op_str(op: BinaryOp) -> &'static str {
+	match op {
+		BinaryOp::Add => "+",
+		BinaryOp::Sub => "-",
+		BinaryOp::Mul => "*",
+		BinaryOp::Div => "/",
+		BinaryOp::FloorDiv => "//",
+		BinaryOp::Mod => "%",
+		BinaryOp::Pow => "^",
+		BinaryOp::Concat => "..",
+		BinaryOp::CompareNe => "~=",
+		BinaryOp::CompareEq => "==",
+		BinaryOp::CompareLt => "<",
+		BinaryOp::CompareLe => "<=",
+		BinaryOp::CompareGt => ">",
+		BinaryOp::CompareGe => ">=",
+		BinaryOp::And => "and",
+		BinaryOp::Or => "or"
+	}
+}
+
+const KEYWORDS: &[&str] = &[
+	"and", "break", "do", "else", "elseif", "end", "false", "for", "function", "if", "in", "local", "nil", "not", "or",
+	"repeat", "return", "then", "true", "until", "while"
+];
+
+fn is_identifier(name: &[u8]) -> bool {
+	match name.split_first() {
+		Some((first, rest)) => (first.is_ascii_alphabetic() || *first == b'_')
+			&& rest.iter().all(|byte| byte.is_ascii_alphanumeric() || *byte == b'_')
+			&& !KEYWORDS.iter().any(|keyword| keyword.as_bytes() == name),
+		None => false
+	}
+}
+
+fn is_before(a: Position, b: Position) -> bool {
+	(a.line, a.column) < (b.line, b.column)
+}
+
+/// Whether more operators after this expression would be absorbed into it, as
+/// happens with the `else` branch of an if-else expression.
+fn ends_greedily(expr: &Expr) -> bool {
+	match &expr.kind {
+		ExprKind::IfElse(_) => true,
+		ExprKind::Binary(binary) => ends_greedily(&binary.right),
+		ExprKind::Unary(unary) => ends_greedily(&unary.expr),
+		_ => false
+	}
+}
+
+/// Whether the expression starts with a unary minus once printed.
+fn is_negation(expr: &Expr) -> bool {
+	match &expr.kind {
+		ExprKind::Unary(unary) => unary.op == UnaryOp::Minus,
+		ExprKind::Number(value) => value.is_sign_negative(),
+		_ => false
+	}
+}
+
+fn is_prefix_expr(expr: &Expr) -> bool {
+	matches!(expr.kind, ExprKind::Local(_) | ExprKind::Global(_) | ExprKind::Call(_) | ExprKind::IndexName(_) | ExprKind::IndexExpr(_) | ExprKind::Group(_))
+}
+
+/// Whether the statement starts with a parenthesis once printed, in which case
+/// the previous statement needs a semicolon to not be mistaken for a call.
+fn starts_with_paren(stat: &Stat) -> bool {
+	let mut expr = match &stat.kind {
+		StatKind::Expr(expr) => expr,
+		StatKind::Assign(assign) => match assign.vars.first() { Some(var) => var, None => return false },
+		StatKind::CompoundAssign(assign) => &assign.var,
+		_ => return false
+	};
+
+	loop {
+		expr = match &expr.kind {
+			ExprKind::Call(call) => &call.func,
+			ExprKind::IndexName(index) => &index.expr,
+			ExprKind::IndexExpr(index) => &index.expr,
+			ExprKind::Local(_) | ExprKind::Global(_) => return false,
+			_ => return true
+		}
+	}
+}
+
+pub(crate) fn quote(value: &[u8], quote: char) -> String {
+	let mut out = String::new();
+	out.push(quote);
+	escape_into(&mut out, value, &[quote]);
+	out.push(quote);
+	out
+}
+
+/// Escapes a string for use between quotes. Invalid UTF-8 and control
+/// characters are written as decimal escapes, which are always three digits
+/// long so that they can't run into a following digit.
+fn escape_into(out: &mut String, value: &[u8], special: &[char]) {
+	for chunk in value.utf8_chunks() {
+		for char in chunk.valid().chars() {
+			match char {
+				'\\' => out.push_str("\\\\"),
+				'\n' => out.push_str("\\n"),
+				'\r' => out.push_str("\\r"),
+				'\t' => out.push_str("\\t"),
+				char if special.contains(&char) => { out.push('\\'); out.push(char) }
+				char if char.is_ascii_control() => out.push_str(&format!("\\{:03}", char as u8)),
+				char => out.push(char)
+			}
+		}
+
+		for byte in chunk.invalid() {
+			out.push_str(&format!("\\{:03}", byte));
+		}
+	}
+}
+
+/// Writes a string as a long string like `[==[...]==]`, if it can be written
+/// as one without changing its value.
+fn long_string(value: &[u8]) -> Option<String> {
+	// long strings can't contain escapes, and newlines in them are
+	// normalized, so a lone \r can't survive
+	let text = value.to_str().ok().filter(|text| !text.contains('\r'))?;
+
+	let level = (0..).find(|level| {
+		let close = format!("]{}]", "=".repeat(*level));
+		!text.contains(&close) && !text.ends_with(&close[..close.len() - 1])
+	})?;
+
+	let mut out = format!("[{}[", "=".repeat(level));
+
+	// the first newline of a long string is skipped
+	if value.first() == Some(&b'\n') {
+		out.push('\n');
+	}
+
+	out.push_str(text);
+	out.push_str(&format!("]{}]", "=".repeat(level)));
+	Some(out)
+}
+
+fn number(value: f64) -> String {
+	if value.is_nan() {
+		"(0/0)".to_owned()
+	} else if value.is_infinite() {
+		if value > 0.0 { "1e999".to_owned() } else { "-1e999".to_owned() }
+	} else {
+		format!("{}", value)
+	}
+}
+
+struct Printer<'a> {
+	ast: &'a Ast,
+	out: String,
+	indent: usize,
+	comments: &'a [Comment],
+	/// The source line that the output currently ends on, if known. Comments
+	/// on this line are appended to it rather than put on their own line.
+	last_line: Option<u32>
+}
+
+impl<'a> Printer<'a> {
+	fn line_start(&mut self) {
+		for _ in 0..self.indent {
+			self.out.push('\t');
+		}
+	}
+
+	/// Writes every remaining comment that starts before `limit`, or every
+	/// remaining comment if there is no limit.
+	fn flush_comments(&mut self, limit: Option<Position>) {
+		while let Some((comment, rest)) = self.comments.split_first() {
+			if limit.is_some_and(|limit| !is_before(comment.span.0, limit)) {
+				break
+			}
+
+			if self.last_line == Some(comment.span.0.line) && self.out.ends_with('\n') {
+				self.out.pop();
+				self.out.push(' ');
+			} else {
+				self.line_start();
+			}
+
+			self.out.push_str(&comment.text);
+			self.out.push('\n');
+			self.last_line = Some(comment.span.1.line);
+			self.comments = rest;
+		}
+	}
+
+	fn body(&mut self, stats: &[Stat]) {
+		for (index, stat) in stats.iter().enumerate() {
+			self.flush_comments(Some(stat.span.0));
+			self.line_start();
+			self.stat(stat);
+
+			if stats.get(index + 1).is_some_and(starts_with_paren) {
+				self.out.push(';');
+			}
+
+			self.out.push('\n');
+			self.last_line = Some(stat.span.1.line);
+		}
+	}
+
+	/// Writes an indented block on new lines, leaving the output ready for the
+	/// keyword that closes it. `header` is the source line that the block was
+	/// opened on, and comments are written up to `limit`.
+	fn block(&mut self, stats: &[Stat], header: Option<u32>, limit: Position) {
+		self.out.push('\n');
+		self.last_line = header;
+		self.indent += 1;
+		self.body(stats);
+		self.flush_comments(Some(limit));
+		self.indent -= 1;
+		self.line_start();
+	}
+
+	fn binding(&mut self, binding: &Binding) {
+		self.out.push_str(&self.ast.local(binding.local).name);
+
+		if let Some(annotation) = &binding.annotation {
+			self.out.push_str(": ");
+			self.ty(annotation);
+		}
+	}
+
+	fn list<T>(&mut self, items: &[T], mut write: impl FnMut(&mut Self, &T)) {
+		for (index, item) in items.iter().enumerate() {
+			if index > 0 {
+				self.out.push_str(", ");
+			}
+
+			write(self, item);
+		}
+	}
+
+	fn exprs(&mut self, exprs: &[Expr]) {
+		self.list(exprs, Self::expr);
+	}
+
+	fn attributes(&mut self, attributes: &[Attr]) {
+		for attr in attributes {
+			match attr.kind {
+				AttrKind::Checked => self.out.push_str("@checked "),
+				AttrKind::Native => self.out.push_str("@native "),
+				AttrKind::Unknown(_) => {}
+			}
+		}
+	}
+
+	fn stat(&mut self, stat: &Stat) {
+		let end = stat.span.1;
+		let line = Some(stat.span.0.line);
+
+		match &stat.kind {
+			StatKind::Block(block) => {
+				self.out.push_str("do");
+				self.block(&block.body, line, end);
+				self.out.push_str("end");
+			}
+
+			StatKind::If(stat_if) => self.stat_if(stat_if, line, end),
+
+			StatKind::While(stat) => {
+				self.out.push_str("while ");
+				self.expr(&stat.condition);
+				self.out.push_str(" do");
+				self.block(&stat.body.body, line, end);
+				self.out.push_str("end");
+			}
+
+			StatKind::Repeat(stat) => {
+				self.out.push_str("repeat");
+				self.block(&stat.body.body, line, stat.condition.span.0);
+				self.out.push_str("until ");
+				self.expr(&stat.condition);
+			}
+
+			StatKind::Break => self.out.push_str("break"),
+			StatKind::Continue => self.out.push_str("continue"),
+
+			StatKind::Return(stat) => {
+				self.out.push_str("return");
+
+				if !stat.values.is_empty() {
+					self.out.push(' ');
+					self.exprs(&stat.values);
+				}
+			}
+
+			StatKind::Expr(expr) => self.expr(expr),
+
+			StatKind::Local(stat) => {
+				self.out.push_str("local ");
+				self.list(&stat.vars, |printer, var| printer.binding(var));
+
+				if !stat.values.is_empty() {
+					self.out.push_str(" = ");
+					self.exprs(&stat.values);
+				}
+			}
+
+			StatKind::For(stat) => {
+				self.out.push_str("for ");
+				self.binding(&stat.var);
+				self.out.push_str(" = ");
+				self.expr(&stat.from);
+				self.out.push_str(", ");
+				self.expr(&stat.to);
+
+				if let Some(step) = &stat.step {
+					self.out.push_str(", ");
+					self.expr(step);
+				}
+
+				self.out.push_str(" do");
+				self.block(&stat.body.body, line, end);
+				self.out.push_str("end");
+			}
+
+			StatKind::ForIn(stat) => {
+				self.out.push_str("for ");
+				self.list(&stat.vars, |printer, var| printer.binding(var));
+				self.out.push_str(" in ");
+				self.exprs(&stat.values);
+				self.out.push_str(" do");
+				self.block(&stat.body.body, line, end);
+				self.out.push_str("end");
+			}
+
+			StatKind::Assign(stat) => {
+				self.exprs(&stat.vars);
+				self.out.push_str(" = ");
+				self.exprs(&stat.values);
+			}
+
+			StatKind::CompoundAssign(stat) => {
+				self.expr(&stat.var);
+				self.out.push(' ');
+				self.out.push_str(binary_op_str(stat.op));
+				self.out.push_str("= ");
+				self.expr(&stat.value);
+			}
+
+			StatKind::Function(stat) => {
+				self.attributes(&stat.func.attributes);
+				self.out.push_str("function ");
+				self.expr(&stat.name);
+				self.function_body(&stat.func, end);
+			}
+
+			StatKind::LocalFunction(stat) => {
+				self.attributes(&stat.func.attributes);
+				self.out.push_str("local function ");
+				self.out.push_str(&self.ast.local(stat.name).name);
+				self.function_body(&stat.func, end);
+			}
+
+			StatKind::TypeAlias(stat) => {
+				if stat.exported {
+					self.out.push_str("export ");
+				}
+
+				self.out.push_str("type ");
+				self.out.push_str(&stat.name);
+				self.generics(&stat.generics, &stat.generic_packs);
+				self.out.push_str(" = ");
+				self.ty(&stat.ty);
+			}
+
+			StatKind::DeclareGlobal(stat) => {
+				self.out.push_str("declare ");
+				self.out.push_str(&stat.name);
+				self.out.push_str(": ");
+				self.ty(&stat.ty);
+			}
+
+			StatKind::DeclareFunction(stat) => {
+				self.attributes(&stat.attributes);
+				self.out.push_str("declare function ");
+				self.out.push_str(&stat.name);
+				self.generics(&stat.generics, &stat.generic_packs);
+				self.out.push('(');
+
+				for (index, ty) in stat.params.types.iter().enumerate() {
+					if index > 0 {
+						self.out.push_str(", ");
+					}
+
+					if let Some(name) = stat.param_names.get(index) {
+						self.out.push_str(&name.name);
+						self.out.push_str(": ");
+					}
+
+					self.ty(ty);
+				}
+
+				if let Some(tail) = &stat.params.tail {
+					if !stat.params.types.is_empty() {
+						self.out.push_str(", ");
+					}
+
+					self.out.push_str("...: ");
+					self.vararg_pack(tail);
+				}
+
+				self.out.push(')');
+
+				if !stat.return_types.types.is_empty() || stat.return_types.tail.is_some() {
+					self.out.push_str(": ");
+					self.return_types(&stat.return_types);
+				}
+			}
+
+			StatKind::DeclareClass(stat) => {
+				self.out.push_str("declare class ");
+				self.out.push_str(&stat.name);
+
+				if let Some(super_name) = &stat.super_name {
+					self.out.push_str(" extends ");
+					self.out.push_str(super_name);
+				}
+
+				self.out.push('\n');
+				self.indent += 1;
+
+				for prop in &stat.props {
+					self.line_start();
+
+					match &prop.ty.kind {
+						TypeKind::Function(func) if prop.is_method => {
+							self.out.push_str("function ");
+							self.out.push_str(&prop.name);
+							self.declared_method(func);
+						}
+
+						_ => {
+							self.prop_name(&prop.name);
+							self.out.push_str(": ");
+							self.ty(&prop.ty);
+						}
+					}
+
+					self.out.push('\n');
+				}
+
+				if let Some(indexer) = &stat.indexer {
+					self.line_start();
+					self.table_indexer(indexer);
+					self.out.push('\n');
+				}
+
+				self.indent -= 1;
+				self.line_start();
+				self.out.push_str("end");
+			}
+
+			// there's nothing to print, but the statements it contains may
+			// still be intact
+			StatKind::Error(stat) => {
+				for (index, inner) in stat.statements.iter().enumerate() {
+					if index > 0 {
+						self.out.push('\n');
+						self.line_start();
+					}
+
+					self.stat(inner);
+				}
+			}
+		}
+	}
+
+	fn stat_if(&mut self, mut stat: &StatIf, mut line: Option<u32>, end: Position) {
+		self.out.push_str("if ");
+
+		loop {
+			self.expr(&stat.condition);
+			self.out.push_str(" then");
+
+			let else_start = stat.else_span.map(|span| span.0)
+				.or_else(|| stat.else_body.as_ref().map(|body| body.span.0))
+				.unwrap_or(end);
+
+			self.block(&stat.then_body.body, line, else_start);
+			line = stat.else_span.map(|span| span.0.line);
+
+			match stat.else_body.as_deref() {
+				Some(Stat { kind: StatKind::If(next), .. }) => {
+					self.out.push_str("elseif ");
+					stat = next;
+				}
+
+				Some(Stat { kind: StatKind::Block(block), .. }) => {
+					self.out.push_str("else");
+					self.block(&block.body, line, end);
+					break
+				}
+
+				Some(other) => {
+					self.out.push_str("else");
+					self.block(std::slice::from_ref(other), line, end);
+					break
+				}
+
+				None => break
+			}
+		}
+
+		self.out.push_str("end");
+	}
+
+	/// Writes everything after the name of a function: its generics,
+	/// parameters, return type, body and the closing `end`.
+	fn function_body(&mut self, func: &ExprFunction, end: Position) {
+		self.generics(&func.generics, &func.generic_packs);
+		self.out.push('(');
+		self.list(&func.args, |printer, arg| printer.binding(arg));
+
+		if func.vararg {
+			if !func.args.is_empty() {
+				self.out.push_str(", ");
+			}
+
+			self.out.push_str("...");
+
+			if let Some(annotation) = &func.vararg_annotation {
+				self.out.push_str(": ");
+				self.vararg_pack(annotation);
+			}
+		}
+
+		self.out.push(')');
+
+		if let Some(annotation) = &func.return_annotation {
+			self.out.push_str(": ");
+			self.return_types(annotation);
+		}
+
+		self.block(&func.body.body, Some(func.body.span.0.line), end);
+		self.out.push_str("end");
+	}
+
+	/// Writes the parameters and return type of a method of a declared class.
+	/// The implicit `self` parameter isn't part of the function type.
+	fn declared_method(&mut self, func: &TypeFunction) {
+		self.generics(&func.generics, &func.generic_packs);
+		self.out.push_str("(self");
+
+		for (index, ty) in func.arg_types.types.iter().enumerate() {
+			self.out.push_str(", ");
+
+			if let Some(Some(name)) = func.arg_names.get(index) {
+				self.out.push_str(&name.name);
+				self.out.push_str(": ");
+			}
+
+			self.ty(ty);
+		}
+
+		if let Some(tail) = &func.arg_types.tail {
+			self.out.push_str(", ...: ");
+			self.vararg_pack(tail);
+		}
+
+		self.out.push(')');
+
+		if !func.return_types.types.is_empty() || func.return_types.tail.is_some() {
+			self.out.push_str(": ");
+			self.return_types(&func.return_types);
+		}
+	}
+
+	fn expr(&mut self, expr: &Expr) {
+		match &expr.kind {
+			ExprKind::Group(inner) => {
+				self.out.push('(');
+				self.expr(inner);
+				self.out.push(')');
+			}
+
+			ExprKind::Nil => self.out.push_str("nil"),
+			ExprKind::Bool(value) => self.out.push_str(if *value { "true" } else { "false" }),
+			ExprKind::Number(value) => self.out.push_str(&number(*value)),
+
+			ExprKind::String(string) => {
+				let long = match string.quote_style {
+					QuoteStyle::Raw => long_string(&string.value),
+					_ => None
+				};
+
+				self.out.push_str(&long.unwrap_or_else(|| quote(&string.value, '"')));
+			}
+
+			ExprKind::Local(local) => self.out.push_str(&self.ast.local(local.local).name),
+			ExprKind::Global(name) => self.out.push_str(name),
+			ExprKind::Varargs => self.out.push_str("..."),
+
+			ExprKind::Call(call) => {
+				self.prefix_expr(&call.func);
+				self.out.push('(');
+				self.exprs(&call.args);
+				self.out.push(')');
+			}
+
+			ExprKind::IndexName(index) => {
+				self.prefix_expr(&index.expr);
+				self.out.push(index.op);
+				self.out.push_str(&index.index);
+			}
+
+			ExprKind::IndexExpr(index) => {
+				self.prefix_expr(&index.expr);
+				self.out.push('[');
+				self.expr(&index.index);
+				self.out.push(']');
+			}
+
+			ExprKind::Function(func) => {
+				self.attributes(&func.attributes);
+				self.out.push_str("function");
+				self.function_body(func, expr.span.1);
+			}
+
+			ExprKind::Table(table) => {
+				self.out.push('{');
+
+				self.list(&table.items, |printer, item| {
+					match (item.kind, &item.key) {
+						(TableItemKind::Record, Some(Expr { kind: ExprKind::String(key), .. })) if is_identifier(&key.value) => {
+							printer.out.push_str(&key.value.to_str_lossy());
+							printer.out.push_str(" = ");
+						}
+
+						(_, Some(key)) => {
+							printer.out.push('[');
+							printer.expr(key);
+							printer.out.push_str("] = ");
+						}
+
+						(_, None) => {}
+					}
+
+					printer.expr(&item.value);
+				});
+
+				self.out.push('}');
+			}
+
+			ExprKind::Unary(unary) => {
+				self.out.push_str(match unary.op {
+					UnaryOp::Not => "not ",
+					UnaryOp::Minus => "-",
+					UnaryOp::Len => "#"
+				});
+
+				// `- -x` must not become a comment
+				if unary.op == UnaryOp::Minus && is_negation(&unary.expr) {
+					self.out.push(' ');
+				}
+
+				let parens = match &unary.expr.kind {
+					ExprKind::Binary(binary) => binary_priority(binary.op).0 <= UNARY_PRIORITY,
+					_ => false
+				};
+
+				self.operand(&unary.expr, parens);
+			}
+
+			ExprKind::Binary(binary) => {
+				let (left, right) = binary_priority(binary.op);
+
+				let left_parens = ends_greedily(&binary.left) || match &binary.left.kind {
+					ExprKind::Binary(inner) => left > binary_priority(inner.op).1,
+					ExprKind::Unary(_) => left > UNARY_PRIORITY,
+					ExprKind::Number(_) => left > UNARY_PRIORITY && is_negation(&binary.left),
+					// `a :: T < b` would parse as the start of a generic type
+					ExprKind::TypeAssertion(_) => binary.op == BinaryOp::CompareLt,
+					_ => false
+				};
+
+				let right_parens = match &binary.right.kind {
+					ExprKind::Binary(inner) => binary_priority(inner.op).0 <= right,
+					_ => false
+				};
+
+				self.operand(&binary.left, left_parens);
+				self.out.push(' ');
+				self.out.push_str(binary_op_str(binary.op));
+				self.out.push(' ');
+				self.operand(&binary.right, right_parens);
+			}
+
+			ExprKind::TypeAssertion(assertion) => {
+				let parens = matches!(assertion.expr.kind, ExprKind::Binary(_) | ExprKind::Unary(_) | ExprKind::IfElse(_)) || is_negation(&assertion.expr);
+				self.operand(&assertion.expr, parens);
+				self.out.push_str(" :: ");
+				self.ty(&assertion.annotation);
+			}
+
+			ExprKind::IfElse(if_else) => {
+				self.out.push_str("if ");
+				self.expr(&if_else.condition);
+				self.out.push_str(" then ");
+				self.expr(&if_else.true_expr);
+				self.out.push_str(" else ");
+				self.expr(&if_else.false_expr);
+			}
+
+			ExprKind::InterpString(interp) => {
+				self.out.push('`');
+
+				for (index, string) in interp.strings.iter().enumerate() {
+					escape_into(&mut self.out, string, &['`', '{']);
+
+					if let Some(expr) = interp.expressions.get(index) {
+						self.out.push('{');
+						let start = self.out.len();
+						self.expr(expr);
+
+						// `{{` is rejected as a likely mistake
+						if self.out[start..].starts_with('{') {
+							self.out.insert(start, ' ');
+						}
+
+						self.out.push('}');
+					}
+				}
+
+				self.out.push('`');
+			}
+
+			ExprKind::Error(_) => self.out.push_str("nil")
+		}
+	}
+
+	fn operand(&mut self, expr: &Expr, parens: bool) {
+		if parens {
+			self.out.push('(');
+			self.expr(expr);
+			self.out.push(')');
+		} else {
+			self.expr(expr);
+		}
+	}
+
+	/// Writes an expression that is called or indexed, which has to be a name,
+	/// call, index or parenthesized expression.
+	fn prefix_expr(&mut self, expr: &Expr) {
+		self.operand(expr, !is_prefix_expr(expr));
+	}
+
+	fn generics(&mut self, generics: &[GenericType], packs: &[GenericTypePack]) {
+		if generics.is_empty() && packs.is_empty() {
+			return
+		}
+
+		self.out.push('<');
+
+		self.list(generics, |printer, generic| {
+			printer.out.push_str(&generic.name);
+
+			if let Some(default) = &generic.default {
+				printer.out.push_str(" = ");
+				printer.ty(default);
+			}
+		});
+
+		if !generics.is_empty() && !packs.is_empty() {
+			self.out.push_str(", ");
+		}
+
+		self.list(packs, |printer, pack| {
+			printer.out.push_str(&pack.name);
+			printer.out.push_str("...");
+
+			if let Some(default) = &pack.default {
+				printer.out.push_str(" = ");
+				printer.type_pack(default);
+			}
+		});
+
+		self.out.push('>');
+	}
+
+	fn prop_name(&mut self, name: &str) {
+		if is_identifier(name.as_bytes()) {
+			self.out.push_str(name);
+		} else {
+			self.out.push('[');
+			self.out.push_str(&quote(name.as_bytes(), '"'));
+			self.out.push(']');
+		}
+	}
+
+	fn access(&mut self, access: TableAccess, explicit: bool) {
+		if explicit {
+			match access {
+				TableAccess::Read => self.out.push_str("read "),
+				TableAccess::Write => self.out.push_str("write "),
+				TableAccess::ReadWrite => {}
+			}
+		}
+	}
+
+	fn table_indexer(&mut self, indexer: &TableIndexer) {
+		self.access(indexer.access, indexer.access_span.is_some());
+		self.out.push('[');
+		self.ty(&indexer.index_type);
+		self.out.push_str("]: ");
+		self.ty(&indexer.result_type);
+	}
+
+	fn ty(&mut self, ty: &Type) {
+		match &ty.kind {
+			TypeKind::Reference(reference) => {
+				if let Some(prefix) = &reference.prefix {
+					self.out.push_str(prefix);
+					self.out.push('.');
+				}
+
+				self.out.push_str(&reference.name);
+
+				if reference.has_parameter_list {
+					self.out.push('<');
+
+					self.list(&reference.parameters, |printer, parameter| match parameter {
+						TypeOrPack::Type(ty) => printer.ty(ty),
+						TypeOrPack::Pack(pack) => printer.type_pack(pack)
+					});
+
+					self.out.push('>');
+				}
+			}
+
+			TypeKind::Table(table) => {
+				if table.props.is_empty() && table.indexer.is_none() {
+					self.out.push_str("{}");
+					return
+				}
+
+				self.out.push_str("{ ");
+
+				self.list(&table.props, |printer, prop| {
+					printer.access(prop.access, prop.access_span.is_some());
+					printer.prop_name(&prop.name);
+					printer.out.push_str(": ");
+					printer.ty(&prop.ty);
+				});
+
+				if let Some(indexer) = &table.indexer {
+					if !table.props.is_empty() {
+						self.out.push_str(", ");
+					}
+
+					self.table_indexer(indexer);
+				}
+
+				self.out.push_str(" }");
+			}
+
+			TypeKind::Function(func) => {
+				self.attributes(&func.attributes);
+				self.generics(&func.generics, &func.generic_packs);
+				self.out.push('(');
+
+				for (index, ty) in func.arg_types.types.iter().enumerate() {
+					if index > 0 {
+						self.out.push_str(", ");
+					}
+
+					if let Some(Some(name)) = func.arg_names.get(index) {
+						self.out.push_str(&name.name);
+						self.out.push_str(": ");
+					}
+
+					self.ty(ty);
+				}
+
+				if let Some(tail) = &func.arg_types.tail {
+					if !func.arg_types.types.is_empty() {
+						self.out.push_str(", ");
+					}
+
+					self.type_pack(tail);
+				}
+
+				self.out.push_str(") -> ");
+				self.return_types(&func.return_types);
+			}
+
+			TypeKind::Typeof(expr) => {
+				self.out.push_str("typeof(");
+				self.expr(expr);
+				self.out.push(')');
+			}
+
+			TypeKind::Union(union) => self.type_operands(&union.types, " | "),
+			TypeKind::Intersection(intersection) => self.type_operands(&intersection.types, " & "),
+			TypeKind::SingletonBool(value) => self.out.push_str(if *value { "true" } else { "false" }),
+			TypeKind::SingletonString(value) => self.out.push_str(&quote(value, '"')),
+			TypeKind::Error(_) => self.out.push_str("any")
+		}
+	}
+
+	/// Writes the members of a union or intersection. Function types would
+	/// absorb the operator into their return type, and unions and
+	/// intersections can't be mixed without parentheses.
+	fn type_operands(&mut self, types: &[Type], separator: &str) {
+		for (index, ty) in types.iter().enumerate() {
+			if index > 0 {
+				self.out.push_str(separator);
+			}
+
+			if matches!(ty.kind, TypeKind::Function(_) | TypeKind::Union(_) | TypeKind::Intersection(_)) {
+				self.out.push('(');
+				self.ty(ty);
+				self.out.push(')');
+			} else {
+				self.ty(ty);
+			}
+		}
+	}
+
+	fn type_list(&mut self, list: &TypeList) {
+		self.list(&list.types, Self::ty);
+
+		if let Some(tail) = &list.tail {
+			if !list.types.is_empty() {
+				self.out.push_str(", ");
+			}
+
+			self.type_pack(tail);
+		}
+	}
+
+	/// Writes a return type, which only needs parentheses if it isn't a single
+	/// type or a single type pack.
+	fn return_types(&mut self, list: &TypeList) {
+		match (list.types.as_slice(), &list.tail) {
+			([ty], None) => self.ty(ty),
+			([], Some(tail)) if !matches!(tail.kind, TypePackKind::Explicit(_)) => self.type_pack(tail),
+
+			_ => {
+				self.out.push('(');
+				self.type_list(list);
+				self.out.push(')');
+			}
+		}
+	}
+
+	fn type_pack(&mut self, pack: &TypePack) {
+		match &pack.kind {
+			TypePackKind::Explicit(list) => {
+				self.out.push('(');
+				self.type_list(list);
+				self.out.push(')');
+			}
+
+			TypePackKind::Variadic(ty) => {
+				self.out.push_str("...");
+				self.ty(ty);
+			}
+
+			TypePackKind::Generic(name) => {
+				self.out.push_str(name);
+				self.out.push_str("...");
+			}
+		}
+	}
+
+	/// Writes the annotation of a `...` parameter, where a variadic pack is
+	/// written without its leading `...`.
+	fn vararg_pack(&mut self, pack: &TypePack) {
+		match &pack.kind {
+			TypePackKind::Variadic(ty) => self.ty(ty),
+			_ => self.type_pack(pack)
+		}
+	}
+}
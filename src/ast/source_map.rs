@@ -0,0 +1,178 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::Write;
+use std::ops::Range;
+
+use crate::ast::{Position, Span};
+
+/// Maps between byte offsets into a source and the [`Position`]s that Luau
+/// reports.
+///
+/// Luau counts lines and columns from zero, only treats `\n` as a line break,
+/// and counts columns in bytes rather than characters. A `\r` before a line
+/// break is part of the line as far as Luau is concerned.
+#[derive(Clone, Debug)]
+pub struct SourceMap<'a> {
+	source: &'a str,
+	line_starts: Vec<usize>
+}
+
+impl<'a> SourceMap<'a> {
+	pub fn new(source: &'a str) -> Self {
+		let line_starts = std::iter::once(0)
+			.chain(source.match_indices('\n').map(|(index, _)| index + 1))
+			.collect();
+
+		Self { source, line_starts }
+	}
+
+	pub fn source(&self) -> &'a str {
+		self.source
+	}
+
+	pub fn line_count(&self) -> usize {
+		self.line_starts.len()
+	}
+
+	/// Returns the byte range of a line, excluding its line break.
+	pub fn line_range(&self, line: u32) -> Option<Range<usize>> {
+		let start = *self.line_starts.get(line as usize)?;
+		let end = self.line_starts.get(line as usize + 1).map_or(self.source.len(), |next| next - 1);
+		Some(start..end)
+	}
+
+	/// Returns the text of a line, excluding its line break.
+	pub fn line(&self, line: u32) -> Option<&'a str> {
+		self.line_range(line).map(|range| &self.source[range])
+	}
+
+	/// Converts a byte offset to a position. Offsets past the end of the source
+	/// are not valid, but the offset of the end itself is.
+	pub fn position(&self, offset: usize) -> Option<Position> {
+		if offset > self.source.len() {
+			return None
+		}
+
+		let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+		Some(Position { line: line as u32, column: (offset - self.line_starts[line]) as u32 })
+	}
+
+	/// Converts a position to a byte offset. Columns past the end of the line
+	/// are not valid, but the column of the line break is.
+	pub fn offset(&self, position: Position) -> Option<usize> {
+		let range = self.line_range(position.line)?;
+		let offset = range.start + position.column as usize;
+		(offset <= range.end).then_some(offset)
+	}
+
+	/// Converts a span to a byte range.
+	pub fn range(&self, span: Span) -> Option<Range<usize>> {
+		Some(self.offset(span.0)?..self.offset(span.1)?)
+	}
+
+	/// Converts a byte range to a span.
+	pub fn span(&self, range: Range<usize>) -> Option<Span> {
+		Some(Span(self.position(range.start)?, self.position(range.end)?))
+	}
+
+	/// Returns the source text covered by a span, if the span is valid and lies
+	/// on character boundaries.
+	pub fn slice(&self, span: Span) -> Option<&'a str> {
+		self.source.get(self.range(span)?)
+	}
+
+	/// Renders a snippet of the source that underlines `primary` with `^` and
+	/// each of `secondary` with `-`, optionally labelling them:
+	///
+	/// ```text
+	///  --> 2:7
+	///   |
+	/// 1 | local function f()
+	///   | - to close 'function'
+	/// 2 |     return
+	///   |           ^ expected 'end'
+	/// ```
+	pub fn render_snippet(&self, primary: Span, primary_label: Option<&str>, secondary: &[(Span, &str)]) -> String {
+		let mut labels: Vec<(Span, char, Option<&str>)> = std::iter::once((primary, '^', primary_label))
+			.chain(secondary.iter().map(|(span, label)| (*span, '-', Some(*label))))
+			.collect();
+
+		labels.sort_by_key(|(span, ..)| (span.0.line, span.0.column));
+
+		let gutter = labels.iter().map(|(span, ..)| (span.0.line + 1).to_string().len()).max().unwrap_or(1);
+		let mut out = String::new();
+
+		let _ = writeln!(out, "{:gutter$}--> {}:{}", "", primary.0.line + 1, primary.0.column + 1, gutter = gutter);
+		let _ = writeln!(out, "{:gutter$} |", "", gutter = gutter);
+
+		let mut last_line = None;
+
+		for (span, marker, label) in labels {
+			let Some(line) = self.line(span.0.line) else { continue };
+			let line = line.strip_suffix('\r').unwrap_or(line);
+
+			if last_line != Some(span.0.line) {
+				if last_line.is_some_and(|last| span.0.line > last + 1) {
+					out.push_str("...\n");
+				}
+
+				let _ = writeln!(out, "{:>gutter$} | {}", span.0.line + 1, line, gutter = gutter);
+			}
+
+			last_line = Some(span.0.line);
+
+			let _ = write!(out, "{:gutter$} | ", "", gutter = gutter);
+			out.push_str(&underline(line, span, marker));
+
+			if let Some(label) = label {
+				out.push(' ');
+				out.push_str(label);
+			}
+
+			out.push('\n');
+		}
+
+		out
+	}
+}
+
+/// Builds the underline for `span` on `line`. Tabs are preserved so that the
+/// underline lines up regardless of tab width.
+fn underline(line: &str, span: Span, marker: char) -> String {
+	let start = floor_char_boundary(line, span.0.column as usize);
+
+	let end = if span.1.line == span.0.line {
+		floor_char_boundary(line, span.1.column as usize).max(start)
+	} else {
+		line.len()
+	};
+
+	let mut out: String = line[..start].chars().map(|char| if char == '\t' { '\t' } else { ' ' }).collect();
+	out.extend(std::iter::repeat_n(marker, line[start..end].chars().count().max(1)));
+	out
+}
+
+fn floor_char_boundary(str: &str, mut index: usize) -> usize {
+	if index >= str.len() {
+		return str.len()
+	}
+
+	while !str.is_char_boundary(index) {
+		index -= 1;
+	}
+
+	index
+}
@@ -0,0 +1,719 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Owned versions of the nodes produced by Luau's parser. Every node carries
+//! the [`Span`] it was parsed from.
+
+use bstr::BString;
+
+use crate::ast::{Mode, Span};
+
+/// The result of parsing a source: the top-level block, and every local
+/// variable declared anywhere within it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Ast {
+	pub root: Block,
+	/// Every local referenced by the tree, indexed by [`LocalId`], in the
+	/// order they are declared.
+	///
+	/// Nodes that declare locals, such as [`StatLocal`], [`StatFor`] and the
+	/// parameters of [`ExprFunction`], hold a [`Binding`] with the type
+	/// annotation, while the name of each local lives here.
+	pub locals: Vec<Local>,
+	/// The number of lines in the source.
+	pub lines: u32,
+	/// Every comment in the source, in order. This is only populated if
+	/// comments were captured with [`ParseOptions::set_capture_comments`].
+	///
+	/// [`ParseOptions::set_capture_comments`]: crate::ast::ParseOptions::set_capture_comments
+	pub comments: Vec<Comment>,
+	/// Every hot comment in the source, in order. Hot comments are always
+	/// captured.
+	pub hot_comments: Vec<HotComment>
+}
+
+impl Ast {
+	/// Returns the mode selected by the first `--!strict`, `--!nonstrict` or
+	/// `--!nocheck` hot comment at the top of the source, like Luau does.
+	pub fn mode(&self) -> Option<Mode> {
+		self.hot_comments.iter()
+			.filter(|comment| comment.header)
+			.find_map(|comment| match comment.kind {
+				HotCommentKind::Strict => Some(Mode::Strict),
+				HotCommentKind::Nonstrict => Some(Mode::Nonstrict),
+				HotCommentKind::NoCheck => Some(Mode::NoCheck),
+				_ => None
+			})
+	}
+
+	/// Returns the local with the given ID. IDs are only meaningful for the
+	/// [`Ast`] that they came from.
+	pub fn local(&self, id: LocalId) -> &Local {
+		&self.locals[id.0 as usize]
+	}
+}
+
+/// Refers to a local variable in [`Ast::locals`]. Every reference to the same
+/// local has the same ID.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct LocalId(pub u32);
+
+/// The declaration of a local by [`StatLocal`], [`StatFor`], [`StatForIn`] or
+/// the parameters of [`ExprFunction`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Binding {
+	pub local: LocalId,
+	/// The type annotation, such as `number` in `local x: number`.
+	pub annotation: Option<Type>
+}
+
+/// A local variable, stored in [`Ast::locals`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct Local {
+	pub name: String,
+	/// The span of the name where the local is declared.
+	pub span: Span,
+	/// The local with the same name that this one shadows, if any.
+	pub shadow: Option<LocalId>,
+	pub function_depth: u32,
+	pub loop_depth: u32
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum CommentKind {
+	/// `-- comment`
+	Line,
+	/// `--[[ comment ]]`
+	Block,
+	/// A block comment that is never closed.
+	BrokenBlock
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Comment {
+	pub kind: CommentKind,
+	pub span: Span,
+	/// The text of the comment, including the leading `--`.
+	pub text: String
+}
+
+/// What a hot comment asks for, as understood by Luau.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum HotCommentKind {
+	/// `--!strict`
+	Strict,
+	/// `--!nonstrict`
+	Nonstrict,
+	/// `--!nocheck`
+	NoCheck,
+	/// `--!native`, which compiles the script to native code when possible.
+	Native,
+	/// `--!optimize 0`, `--!optimize 1` or `--!optimize 2`.
+	Optimize(u8),
+	/// `--!nolint`, optionally naming the lint to disable.
+	NoLint(Option<String>),
+	/// Anything else, including malformed versions of the above.
+	Unknown
+}
+
+impl HotCommentKind {
+	/// Interprets the content of a hot comment, which is everything after the
+	/// `--!`.
+	pub fn parse(content: &str) -> Self {
+		let content = content.trim();
+		let (name, argument) = content.split_once(char::is_whitespace).map_or((content, ""), |(name, argument)| (name, argument.trim()));
+
+		match (name, argument) {
+			("strict", "") => Self::Strict,
+			("nonstrict", "") => Self::Nonstrict,
+			("nocheck", "") => Self::NoCheck,
+			("native", "") => Self::Native,
+			("nolint", "") => Self::NoLint(None),
+			("nolint", lint) => Self::NoLint(Some(lint.to_owned())),
+
+			("optimize", level) => match level.parse() {
+				Ok(level @ 0..=2) => Self::Optimize(level),
+				_ => Self::Unknown
+			}
+
+			_ => Self::Unknown
+		}
+	}
+}
+
+/// A comment starting with `--!`.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct HotComment {
+	pub span: Span,
+	/// Whether the comment comes before any code. Only these can set the
+	/// [`Mode`].
+	pub header: bool,
+	/// Everything after the `--!`.
+	pub content: String,
+	pub kind: HotCommentKind
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Block {
+	pub span: Span,
+	pub body: Vec<Stat>,
+	/// Whether the block is terminated by an `end` keyword.
+	pub has_end: bool
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Stat {
+	pub span: Span,
+	pub kind: StatKind
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum StatKind {
+	/// A `do ... end` block.
+	Block(Block),
+	If(StatIf),
+	While(StatWhile),
+	Repeat(StatRepeat),
+	Break,
+	Continue,
+	Return(StatReturn),
+	/// An expression used as a statement, which is always a call.
+	Expr(Expr),
+	Local(StatLocal),
+	For(StatFor),
+	ForIn(StatForIn),
+	Assign(StatAssign),
+	CompoundAssign(StatCompoundAssign),
+	Function(StatFunction),
+	LocalFunction(StatLocalFunction),
+	TypeAlias(StatTypeAlias),
+	DeclareGlobal(StatDeclareGlobal),
+	DeclareFunction(StatDeclareFunction),
+	DeclareClass(StatDeclareClass),
+	/// A statement that could not be parsed.
+	Error(StatError)
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatIf {
+	pub condition: Expr,
+	pub then_body: Block,
+	/// Either another [`StatIf`] for `elseif`, or a [`StatKind::Block`] for
+	/// `else`.
+	pub else_body: Option<Box<Stat>>,
+	pub then_span: Option<Span>,
+	pub else_span: Option<Span>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatWhile {
+	pub condition: Expr,
+	pub body: Block,
+	pub has_do: bool,
+	pub do_span: Span
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatRepeat {
+	pub body: Block,
+	pub condition: Expr
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatReturn {
+	pub values: Vec<Expr>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatLocal {
+	pub vars: Vec<Binding>,
+	pub values: Vec<Expr>,
+	pub equals_span: Option<Span>
+}
+
+/// A numeric `for` loop.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatFor {
+	pub var: Binding,
+	pub from: Expr,
+	pub to: Expr,
+	pub step: Option<Expr>,
+	pub body: Block,
+	pub has_do: bool,
+	pub do_span: Span
+}
+
+/// A generic `for` loop.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatForIn {
+	pub vars: Vec<Binding>,
+	pub values: Vec<Expr>,
+	pub body: Block,
+	pub has_in: bool,
+	pub in_span: Span,
+	pub has_do: bool,
+	pub do_span: Span
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatAssign {
+	pub vars: Vec<Expr>,
+	pub values: Vec<Expr>
+}
+
+/// An assignment such as `a += 1`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatCompoundAssign {
+	pub op: BinaryOp,
+	pub var: Expr,
+	pub value: Expr
+}
+
+/// A `function a.b:c() end` statement. The name is a global, local or index
+/// expression.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatFunction {
+	pub name: Expr,
+	pub func_span: Span,
+	pub func: Box<ExprFunction>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatLocalFunction {
+	pub name: LocalId,
+	pub func_span: Span,
+	pub func: Box<ExprFunction>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatTypeAlias {
+	pub name: String,
+	pub name_span: Span,
+	pub generics: Vec<GenericType>,
+	pub generic_packs: Vec<GenericTypePack>,
+	pub ty: Type,
+	pub exported: bool
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatDeclareGlobal {
+	pub name: String,
+	pub name_span: Span,
+	pub ty: Type
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatDeclareFunction {
+	pub attributes: Vec<Attr>,
+	pub name: String,
+	pub name_span: Span,
+	pub generics: Vec<GenericType>,
+	pub generic_packs: Vec<GenericTypePack>,
+	pub params: TypeList,
+	pub param_names: Vec<ArgumentName>,
+	pub vararg: bool,
+	pub vararg_span: Span,
+	pub return_types: TypeList
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatDeclareClass {
+	pub name: String,
+	pub super_name: Option<String>,
+	pub props: Vec<DeclaredClassProp>,
+	pub indexer: Option<TableIndexer>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatError {
+	pub expressions: Vec<Expr>,
+	pub statements: Vec<Stat>,
+	/// The index of the parse error that produced this node, if known.
+	pub message_index: Option<u32>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Expr {
+	pub span: Span,
+	pub kind: ExprKind
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ExprKind {
+	/// A parenthesized expression.
+	Group(Box<Expr>),
+	Nil,
+	Bool(bool),
+	Number(f64),
+	String(ExprString),
+	Local(ExprLocal),
+	Global(String),
+	Varargs,
+	Call(ExprCall),
+	/// `a.b` or `a:b`.
+	IndexName(ExprIndexName),
+	/// `a[b]`.
+	IndexExpr(ExprIndexExpr),
+	Function(Box<ExprFunction>),
+	Table(ExprTable),
+	Unary(ExprUnary),
+	Binary(ExprBinary),
+	/// `a :: T`.
+	TypeAssertion(ExprTypeAssertion),
+	/// `if a then b else c`.
+	IfElse(ExprIfElse),
+	InterpString(ExprInterpString),
+	/// An expression that could not be parsed.
+	Error(ExprError)
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum QuoteStyle {
+	/// A string in single or double quotes, or an interpolated string without
+	/// any interpolations.
+	Simple,
+	/// A long string, like `[[...]]`.
+	Raw,
+	/// A string that was not quoted in the source, such as the name of a
+	/// record in a table constructor.
+	Unquoted
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ExprString {
+	/// The value of the string after escapes have been processed, which is
+	/// not necessarily UTF-8.
+	pub value: BString,
+	pub quote_style: QuoteStyle
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct ExprLocal {
+	pub local: LocalId,
+	/// Whether the local is captured from an enclosing function.
+	pub upvalue: bool
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExprCall {
+	pub func: Box<Expr>,
+	pub args: Vec<Expr>,
+	/// Whether this is a method call, like `a:b()`.
+	pub is_self: bool,
+	pub arg_span: Span
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExprIndexName {
+	pub expr: Box<Expr>,
+	pub index: String,
+	pub index_span: Span,
+	/// Either `.` or `:`.
+	pub op: char
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExprIndexExpr {
+	pub expr: Box<Expr>,
+	pub index: Box<Expr>
+}
+
+/// A function body. Function expressions are [`ExprKind::Function`], while
+/// function statements store their span separately.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExprFunction {
+	pub attributes: Vec<Attr>,
+	pub generics: Vec<GenericType>,
+	pub generic_packs: Vec<GenericTypePack>,
+	/// The implicit `self` parameter of methods.
+	pub self_local: Option<LocalId>,
+	pub args: Vec<Binding>,
+	pub return_annotation: Option<TypeList>,
+	pub vararg: bool,
+	pub vararg_span: Span,
+	pub vararg_annotation: Option<TypePack>,
+	pub body: Block,
+	pub function_depth: u32,
+	pub debug_name: String,
+	/// The span of the parenthesized argument list.
+	pub arg_span: Option<Span>
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TableItemKind {
+	/// `value`
+	List,
+	/// `key = value`
+	Record,
+	/// `[key] = value`
+	General
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TableItem {
+	pub kind: TableItemKind,
+	/// The key, which is absent for [`TableItemKind::List`] items.
+	pub key: Option<Expr>,
+	pub value: Expr
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum UnaryOp {
+	Not,
+	Minus,
+	Len
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum BinaryOp {
+	Add,
+	Sub,
+	Mul,
+	Div,
+	FloorDiv,
+	Mod,
+	Pow,
+	Concat,
+	CompareNe,
+	CompareEq,
+	CompareLt,
+	CompareLe,
+	CompareGt,
+	CompareGe,
+	And,
+	Or
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExprTable {
+	pub items: Vec<TableItem>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExprUnary {
+	pub op: UnaryOp,
+	pub expr: Box<Expr>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExprBinary {
+	pub op: BinaryOp,
+	pub left: Box<Expr>,
+	pub right: Box<Expr>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExprTypeAssertion {
+	pub expr: Box<Expr>,
+	pub annotation: Box<Type>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExprIfElse {
+	pub condition: Box<Expr>,
+	pub has_then: bool,
+	pub true_expr: Box<Expr>,
+	pub has_else: bool,
+	pub false_expr: Box<Expr>
+}
+
+/// An interpolated string. There is always one more string than there are
+/// expressions.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExprInterpString {
+	pub strings: Vec<BString>,
+	pub expressions: Vec<Expr>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExprError {
+	pub expressions: Vec<Expr>,
+	/// The index of the parse error that produced this node, if known.
+	pub message_index: Option<u32>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Type {
+	pub span: Span,
+	pub kind: TypeKind
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TypeKind {
+	/// A named type, like `number`, `Foo<T>` or `module.Type`.
+	Reference(TypeReference),
+	Table(TypeTable),
+	Function(Box<TypeFunction>),
+	/// `typeof(expr)`.
+	Typeof(Box<Expr>),
+	Union(TypeUnion),
+	Intersection(TypeIntersection),
+	SingletonBool(bool),
+	SingletonString(BString),
+	/// A type that could not be parsed.
+	Error(TypeError)
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TypeReference {
+	/// The module the type is imported from, if any.
+	pub prefix: Option<String>,
+	pub prefix_span: Option<Span>,
+	pub name: String,
+	pub name_span: Span,
+	pub has_parameter_list: bool,
+	pub parameters: Vec<TypeOrPack>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TypeTable {
+	pub props: Vec<TableProp>,
+	pub indexer: Option<Box<TableIndexer>>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TypeFunction {
+	pub attributes: Vec<Attr>,
+	pub generics: Vec<GenericType>,
+	pub generic_packs: Vec<GenericTypePack>,
+	pub arg_types: TypeList,
+	/// The names of the arguments, if they were named. This has one entry per
+	/// entry of `arg_types.types`.
+	pub arg_names: Vec<Option<ArgumentName>>,
+	pub return_types: TypeList
+}
+
+/// `A | B`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TypeUnion {
+	pub types: Vec<Type>
+}
+
+/// `A & B`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TypeIntersection {
+	pub types: Vec<Type>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TypeError {
+	pub types: Vec<Type>,
+	/// Whether the type was missing entirely, rather than malformed.
+	pub is_missing: bool,
+	/// The index of the parse error that produced this node, if known.
+	pub message_index: Option<u32>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TypePack {
+	pub span: Span,
+	pub kind: TypePackKind
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum TypePackKind {
+	/// `(A, B, ...C)`.
+	Explicit(TypeList),
+	/// `...T`.
+	Variadic(Box<Type>),
+	/// `T...`.
+	Generic(String)
+}
+
+/// A list of types, optionally followed by a type pack.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TypeList {
+	pub types: Vec<Type>,
+	pub tail: Option<Box<TypePack>>
+}
+
+/// A type parameter, which can either be a type or a type pack.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TypeOrPack {
+	Type(Type),
+	Pack(TypePack)
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum TableAccess {
+	Read,
+	Write,
+	ReadWrite
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TableProp {
+	pub name: String,
+	pub span: Span,
+	pub ty: Type,
+	pub access: TableAccess,
+	/// The span of the `read` or `write` keyword, if present.
+	pub access_span: Option<Span>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TableIndexer {
+	pub span: Span,
+	pub index_type: Type,
+	pub result_type: Type,
+	pub access: TableAccess,
+	/// The span of the `read` or `write` keyword, if present.
+	pub access_span: Option<Span>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct GenericType {
+	pub name: String,
+	pub span: Span,
+	pub default: Option<Type>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct GenericTypePack {
+	pub name: String,
+	pub span: Span,
+	pub default: Option<TypePack>
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ArgumentName {
+	pub name: String,
+	pub span: Span
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct DeclaredClassProp {
+	pub name: String,
+	pub name_span: Span,
+	pub ty: Type,
+	pub is_method: bool,
+	pub span: Span
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum AttrKind {
+	/// `@checked`
+	Checked,
+	/// `@native`
+	Native,
+	/// An attribute this version of the bindings doesn't know about.
+	Unknown(u8)
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct Attr {
+	pub span: Span,
+	pub kind: AttrKind
+}
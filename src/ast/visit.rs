@@ -0,0 +1,30 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Traversal of an [`Ast`](crate::ast::tree::Ast) by shared reference.
+
+visitor! {
+	/// Traverses an AST, like Luau's `AstVisitor`.
+	///
+	/// Every method has a default implementation that visits the node's
+	/// children. Overriding a method replaces that, so call the corresponding
+	/// `walk_*` function from the override to keep visiting the children, or
+	/// don't to skip them.
+	///
+	/// Statements, expressions, types and type packs are first passed to the
+	/// general method (such as [`Visitor::visit_expr`]), whose default calls
+	/// the method for the node's kind (such as [`Visitor::visit_expr_call`]).
+	Visitor;
+}
@@ -0,0 +1,449 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+// Visitor and VisitorMut are identical except for mutability, so both are
+// generated from this one definition. `$($mut)?` expands to nothing for
+// Visitor and to `mut` for VisitorMut.
+macro_rules! visitor {
+	($(#[$attr:meta])* $trait:ident; $($mut:tt)?) => {
+		use bstr::BString;
+
+		use crate::ast::Span;
+		use crate::ast::tree::*;
+
+		$(#[$attr])*
+		pub trait $trait {
+			fn visit_block(&mut self, block: & $($mut)? Block) { walk_block(self, block) }
+
+			/// Called for every statement before the method for its kind.
+			fn visit_stat(&mut self, stat: & $($mut)? Stat) { walk_stat(self, stat) }
+			fn visit_stat_if(&mut self, _span: Span, stat: & $($mut)? StatIf) { walk_stat_if(self, stat) }
+			fn visit_stat_while(&mut self, _span: Span, stat: & $($mut)? StatWhile) { walk_stat_while(self, stat) }
+			fn visit_stat_repeat(&mut self, _span: Span, stat: & $($mut)? StatRepeat) { walk_stat_repeat(self, stat) }
+			fn visit_stat_break(&mut self, _span: Span) {}
+			fn visit_stat_continue(&mut self, _span: Span) {}
+			fn visit_stat_return(&mut self, _span: Span, stat: & $($mut)? StatReturn) { walk_stat_return(self, stat) }
+			fn visit_stat_expr(&mut self, _span: Span, expr: & $($mut)? Expr) { self.visit_expr(expr) }
+			fn visit_stat_local(&mut self, _span: Span, stat: & $($mut)? StatLocal) { walk_stat_local(self, stat) }
+			fn visit_stat_for(&mut self, _span: Span, stat: & $($mut)? StatFor) { walk_stat_for(self, stat) }
+			fn visit_stat_for_in(&mut self, _span: Span, stat: & $($mut)? StatForIn) { walk_stat_for_in(self, stat) }
+			fn visit_stat_assign(&mut self, _span: Span, stat: & $($mut)? StatAssign) { walk_stat_assign(self, stat) }
+			fn visit_stat_compound_assign(&mut self, _span: Span, stat: & $($mut)? StatCompoundAssign) { walk_stat_compound_assign(self, stat) }
+			fn visit_stat_function(&mut self, _span: Span, stat: & $($mut)? StatFunction) { walk_stat_function(self, stat) }
+			fn visit_stat_local_function(&mut self, _span: Span, stat: & $($mut)? StatLocalFunction) { walk_stat_local_function(self, stat) }
+			fn visit_stat_type_alias(&mut self, _span: Span, stat: & $($mut)? StatTypeAlias) { walk_stat_type_alias(self, stat) }
+			fn visit_stat_declare_global(&mut self, _span: Span, stat: & $($mut)? StatDeclareGlobal) { walk_stat_declare_global(self, stat) }
+			fn visit_stat_declare_function(&mut self, _span: Span, stat: & $($mut)? StatDeclareFunction) { walk_stat_declare_function(self, stat) }
+			fn visit_stat_declare_class(&mut self, _span: Span, stat: & $($mut)? StatDeclareClass) { walk_stat_declare_class(self, stat) }
+			fn visit_stat_error(&mut self, _span: Span, stat: & $($mut)? StatError) { walk_stat_error(self, stat) }
+
+			/// Called for every expression before the method for its kind.
+			fn visit_expr(&mut self, expr: & $($mut)? Expr) { walk_expr(self, expr) }
+			fn visit_expr_group(&mut self, _span: Span, expr: & $($mut)? Expr) { self.visit_expr(expr) }
+			fn visit_expr_nil(&mut self, _span: Span) {}
+			fn visit_expr_bool(&mut self, _span: Span, _value: & $($mut)? bool) {}
+			fn visit_expr_number(&mut self, _span: Span, _value: & $($mut)? f64) {}
+			fn visit_expr_string(&mut self, _span: Span, _expr: & $($mut)? ExprString) {}
+			fn visit_expr_local(&mut self, _span: Span, _expr: & $($mut)? ExprLocal) {}
+			fn visit_expr_global(&mut self, _span: Span, _name: & $($mut)? String) {}
+			fn visit_expr_varargs(&mut self, _span: Span) {}
+			fn visit_expr_call(&mut self, _span: Span, expr: & $($mut)? ExprCall) { walk_expr_call(self, expr) }
+			fn visit_expr_index_name(&mut self, _span: Span, expr: & $($mut)? ExprIndexName) { self.visit_expr(& $($mut)? *expr.expr) }
+			fn visit_expr_index_expr(&mut self, _span: Span, expr: & $($mut)? ExprIndexExpr) { walk_expr_index_expr(self, expr) }
+			fn visit_expr_function(&mut self, _span: Span, func: & $($mut)? ExprFunction) { self.visit_function(func) }
+			fn visit_expr_table(&mut self, _span: Span, expr: & $($mut)? ExprTable) { walk_expr_table(self, expr) }
+			fn visit_expr_unary(&mut self, _span: Span, expr: & $($mut)? ExprUnary) { self.visit_expr(& $($mut)? *expr.expr) }
+			fn visit_expr_binary(&mut self, _span: Span, expr: & $($mut)? ExprBinary) { walk_expr_binary(self, expr) }
+			fn visit_expr_type_assertion(&mut self, _span: Span, expr: & $($mut)? ExprTypeAssertion) { walk_expr_type_assertion(self, expr) }
+			fn visit_expr_if_else(&mut self, _span: Span, expr: & $($mut)? ExprIfElse) { walk_expr_if_else(self, expr) }
+			fn visit_expr_interp_string(&mut self, _span: Span, expr: & $($mut)? ExprInterpString) { walk_expr_interp_string(self, expr) }
+			fn visit_expr_error(&mut self, _span: Span, expr: & $($mut)? ExprError) { walk_expr_error(self, expr) }
+
+			/// Called for function expressions as well as the bodies of function
+			/// statements.
+			fn visit_function(&mut self, func: & $($mut)? ExprFunction) { walk_function(self, func) }
+			fn visit_attr(&mut self, _attr: & $($mut)? Attr) {}
+
+			/// Called for every local declared by a statement or as a
+			/// function parameter.
+			fn visit_binding(&mut self, binding: & $($mut)? Binding) { walk_binding(self, binding) }
+
+			/// Called for every type annotation before the method for its kind.
+			fn visit_type(&mut self, ty: & $($mut)? Type) { walk_type(self, ty) }
+			fn visit_type_reference(&mut self, _span: Span, ty: & $($mut)? TypeReference) { walk_type_reference(self, ty) }
+			fn visit_type_table(&mut self, _span: Span, ty: & $($mut)? TypeTable) { walk_type_table(self, ty) }
+			fn visit_type_function(&mut self, _span: Span, ty: & $($mut)? TypeFunction) { walk_type_function(self, ty) }
+			fn visit_type_typeof(&mut self, _span: Span, expr: & $($mut)? Expr) { self.visit_expr(expr) }
+			fn visit_type_union(&mut self, _span: Span, ty: & $($mut)? TypeUnion) { for ty in & $($mut)? ty.types { self.visit_type(ty) } }
+			fn visit_type_intersection(&mut self, _span: Span, ty: & $($mut)? TypeIntersection) { for ty in & $($mut)? ty.types { self.visit_type(ty) } }
+			fn visit_type_singleton_bool(&mut self, _span: Span, _value: & $($mut)? bool) {}
+			fn visit_type_singleton_string(&mut self, _span: Span, _value: & $($mut)? BString) {}
+			fn visit_type_error(&mut self, _span: Span, ty: & $($mut)? TypeError) { for ty in & $($mut)? ty.types { self.visit_type(ty) } }
+
+			/// Called for every type pack before the method for its kind.
+			fn visit_type_pack(&mut self, pack: & $($mut)? TypePack) { walk_type_pack(self, pack) }
+			fn visit_type_pack_explicit(&mut self, _span: Span, list: & $($mut)? TypeList) { self.visit_type_list(list) }
+			fn visit_type_pack_variadic(&mut self, _span: Span, ty: & $($mut)? Type) { self.visit_type(ty) }
+			fn visit_type_pack_generic(&mut self, _span: Span, _name: & $($mut)? String) {}
+
+			fn visit_type_list(&mut self, list: & $($mut)? TypeList) { walk_type_list(self, list) }
+		}
+
+		pub fn walk_binding<V: $trait + ?Sized>(visitor: &mut V, binding: & $($mut)? Binding) {
+			if let Some(annotation) = & $($mut)? binding.annotation {
+				visitor.visit_type(annotation);
+			}
+		}
+
+		pub fn walk_block<V: $trait + ?Sized>(visitor: &mut V, block: & $($mut)? Block) {
+			for stat in & $($mut)? block.body {
+				visitor.visit_stat(stat);
+			}
+		}
+
+		pub fn walk_stat<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? Stat) {
+			let span = stat.span;
+
+			match & $($mut)? stat.kind {
+				StatKind::Block(block) => visitor.visit_block(block),
+				StatKind::If(stat) => visitor.visit_stat_if(span, stat),
+				StatKind::While(stat) => visitor.visit_stat_while(span, stat),
+				StatKind::Repeat(stat) => visitor.visit_stat_repeat(span, stat),
+				StatKind::Break => visitor.visit_stat_break(span),
+				StatKind::Continue => visitor.visit_stat_continue(span),
+				StatKind::Return(stat) => visitor.visit_stat_return(span, stat),
+				StatKind::Expr(expr) => visitor.visit_stat_expr(span, expr),
+				StatKind::Local(stat) => visitor.visit_stat_local(span, stat),
+				StatKind::For(stat) => visitor.visit_stat_for(span, stat),
+				StatKind::ForIn(stat) => visitor.visit_stat_for_in(span, stat),
+				StatKind::Assign(stat) => visitor.visit_stat_assign(span, stat),
+				StatKind::CompoundAssign(stat) => visitor.visit_stat_compound_assign(span, stat),
+				StatKind::Function(stat) => visitor.visit_stat_function(span, stat),
+				StatKind::LocalFunction(stat) => visitor.visit_stat_local_function(span, stat),
+				StatKind::TypeAlias(stat) => visitor.visit_stat_type_alias(span, stat),
+				StatKind::DeclareGlobal(stat) => visitor.visit_stat_declare_global(span, stat),
+				StatKind::DeclareFunction(stat) => visitor.visit_stat_declare_function(span, stat),
+				StatKind::DeclareClass(stat) => visitor.visit_stat_declare_class(span, stat),
+				StatKind::Error(stat) => visitor.visit_stat_error(span, stat)
+			}
+		}
+
+		pub fn walk_stat_if<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatIf) {
+			visitor.visit_expr(& $($mut)? stat.condition);
+			visitor.visit_block(& $($mut)? stat.then_body);
+
+			if let Some(else_body) = & $($mut)? stat.else_body {
+				visitor.visit_stat(else_body);
+			}
+		}
+
+		pub fn walk_stat_while<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatWhile) {
+			visitor.visit_expr(& $($mut)? stat.condition);
+			visitor.visit_block(& $($mut)? stat.body);
+		}
+
+		pub fn walk_stat_repeat<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatRepeat) {
+			visitor.visit_block(& $($mut)? stat.body);
+			visitor.visit_expr(& $($mut)? stat.condition);
+		}
+
+		pub fn walk_stat_return<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatReturn) {
+			for value in & $($mut)? stat.values {
+				visitor.visit_expr(value);
+			}
+		}
+
+		pub fn walk_stat_local<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatLocal) {
+			for var in & $($mut)? stat.vars {
+				visitor.visit_binding(var);
+			}
+
+			for value in & $($mut)? stat.values {
+				visitor.visit_expr(value);
+			}
+		}
+
+		pub fn walk_stat_for<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatFor) {
+			visitor.visit_binding(& $($mut)? stat.var);
+			visitor.visit_expr(& $($mut)? stat.from);
+			visitor.visit_expr(& $($mut)? stat.to);
+
+			if let Some(step) = & $($mut)? stat.step {
+				visitor.visit_expr(step);
+			}
+
+			visitor.visit_block(& $($mut)? stat.body);
+		}
+
+		pub fn walk_stat_for_in<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatForIn) {
+			for var in & $($mut)? stat.vars {
+				visitor.visit_binding(var);
+			}
+
+			for value in & $($mut)? stat.values {
+				visitor.visit_expr(value);
+			}
+
+			visitor.visit_block(& $($mut)? stat.body);
+		}
+
+		pub fn walk_stat_assign<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatAssign) {
+			for var in & $($mut)? stat.vars {
+				visitor.visit_expr(var);
+			}
+
+			for value in & $($mut)? stat.values {
+				visitor.visit_expr(value);
+			}
+		}
+
+		pub fn walk_stat_compound_assign<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatCompoundAssign) {
+			visitor.visit_expr(& $($mut)? stat.var);
+			visitor.visit_expr(& $($mut)? stat.value);
+		}
+
+		pub fn walk_stat_function<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatFunction) {
+			visitor.visit_expr(& $($mut)? stat.name);
+			visitor.visit_function(& $($mut)? stat.func);
+		}
+
+		pub fn walk_stat_local_function<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatLocalFunction) {
+			visitor.visit_function(& $($mut)? stat.func);
+		}
+
+		pub fn walk_stat_type_alias<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatTypeAlias) {
+			walk_generics(visitor, & $($mut)? stat.generics, & $($mut)? stat.generic_packs);
+			visitor.visit_type(& $($mut)? stat.ty);
+		}
+
+		pub fn walk_stat_declare_global<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatDeclareGlobal) {
+			visitor.visit_type(& $($mut)? stat.ty);
+		}
+
+		pub fn walk_stat_declare_function<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatDeclareFunction) {
+			for attr in & $($mut)? stat.attributes {
+				visitor.visit_attr(attr);
+			}
+
+			walk_generics(visitor, & $($mut)? stat.generics, & $($mut)? stat.generic_packs);
+			visitor.visit_type_list(& $($mut)? stat.params);
+			visitor.visit_type_list(& $($mut)? stat.return_types);
+		}
+
+		pub fn walk_stat_declare_class<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatDeclareClass) {
+			for prop in & $($mut)? stat.props {
+				visitor.visit_type(& $($mut)? prop.ty);
+			}
+
+			if let Some(indexer) = & $($mut)? stat.indexer {
+				walk_table_indexer(visitor, indexer);
+			}
+		}
+
+		pub fn walk_stat_error<V: $trait + ?Sized>(visitor: &mut V, stat: & $($mut)? StatError) {
+			for expr in & $($mut)? stat.expressions {
+				visitor.visit_expr(expr);
+			}
+
+			for stat in & $($mut)? stat.statements {
+				visitor.visit_stat(stat);
+			}
+		}
+
+		pub fn walk_expr<V: $trait + ?Sized>(visitor: &mut V, expr: & $($mut)? Expr) {
+			let span = expr.span;
+
+			match & $($mut)? expr.kind {
+				ExprKind::Group(expr) => visitor.visit_expr_group(span, expr),
+				ExprKind::Nil => visitor.visit_expr_nil(span),
+				ExprKind::Bool(value) => visitor.visit_expr_bool(span, value),
+				ExprKind::Number(value) => visitor.visit_expr_number(span, value),
+				ExprKind::String(expr) => visitor.visit_expr_string(span, expr),
+				ExprKind::Local(expr) => visitor.visit_expr_local(span, expr),
+				ExprKind::Global(name) => visitor.visit_expr_global(span, name),
+				ExprKind::Varargs => visitor.visit_expr_varargs(span),
+				ExprKind::Call(expr) => visitor.visit_expr_call(span, expr),
+				ExprKind::IndexName(expr) => visitor.visit_expr_index_name(span, expr),
+				ExprKind::IndexExpr(expr) => visitor.visit_expr_index_expr(span, expr),
+				ExprKind::Function(func) => visitor.visit_expr_function(span, func),
+				ExprKind::Table(expr) => visitor.visit_expr_table(span, expr),
+				ExprKind::Unary(expr) => visitor.visit_expr_unary(span, expr),
+				ExprKind::Binary(expr) => visitor.visit_expr_binary(span, expr),
+				ExprKind::TypeAssertion(expr) => visitor.visit_expr_type_assertion(span, expr),
+				ExprKind::IfElse(expr) => visitor.visit_expr_if_else(span, expr),
+				ExprKind::InterpString(expr) => visitor.visit_expr_interp_string(span, expr),
+				ExprKind::Error(expr) => visitor.visit_expr_error(span, expr)
+			}
+		}
+
+		pub fn walk_expr_call<V: $trait + ?Sized>(visitor: &mut V, expr: & $($mut)? ExprCall) {
+			visitor.visit_expr(& $($mut)? expr.func);
+
+			for arg in & $($mut)? expr.args {
+				visitor.visit_expr(arg);
+			}
+		}
+
+		pub fn walk_expr_index_expr<V: $trait + ?Sized>(visitor: &mut V, expr: & $($mut)? ExprIndexExpr) {
+			visitor.visit_expr(& $($mut)? expr.expr);
+			visitor.visit_expr(& $($mut)? expr.index);
+		}
+
+		pub fn walk_expr_table<V: $trait + ?Sized>(visitor: &mut V, expr: & $($mut)? ExprTable) {
+			for item in & $($mut)? expr.items {
+				if let Some(key) = & $($mut)? item.key {
+					visitor.visit_expr(key);
+				}
+
+				visitor.visit_expr(& $($mut)? item.value);
+			}
+		}
+
+		pub fn walk_expr_binary<V: $trait + ?Sized>(visitor: &mut V, expr: & $($mut)? ExprBinary) {
+			visitor.visit_expr(& $($mut)? expr.left);
+			visitor.visit_expr(& $($mut)? expr.right);
+		}
+
+		pub fn walk_expr_type_assertion<V: $trait + ?Sized>(visitor: &mut V, expr: & $($mut)? ExprTypeAssertion) {
+			visitor.visit_expr(& $($mut)? expr.expr);
+			visitor.visit_type(& $($mut)? expr.annotation);
+		}
+
+		pub fn walk_expr_if_else<V: $trait + ?Sized>(visitor: &mut V, expr: & $($mut)? ExprIfElse) {
+			visitor.visit_expr(& $($mut)? expr.condition);
+			visitor.visit_expr(& $($mut)? expr.true_expr);
+			visitor.visit_expr(& $($mut)? expr.false_expr);
+		}
+
+		pub fn walk_expr_interp_string<V: $trait + ?Sized>(visitor: &mut V, expr: & $($mut)? ExprInterpString) {
+			for expr in & $($mut)? expr.expressions {
+				visitor.visit_expr(expr);
+			}
+		}
+
+		pub fn walk_expr_error<V: $trait + ?Sized>(visitor: &mut V, expr: & $($mut)? ExprError) {
+			for expr in & $($mut)? expr.expressions {
+				visitor.visit_expr(expr);
+			}
+		}
+
+		pub fn walk_function<V: $trait + ?Sized>(visitor: &mut V, func: & $($mut)? ExprFunction) {
+			for attr in & $($mut)? func.attributes {
+				visitor.visit_attr(attr);
+			}
+
+			walk_generics(visitor, & $($mut)? func.generics, & $($mut)? func.generic_packs);
+
+			for arg in & $($mut)? func.args {
+				visitor.visit_binding(arg);
+			}
+
+			if let Some(list) = & $($mut)? func.return_annotation {
+				visitor.visit_type_list(list);
+			}
+
+			if let Some(pack) = & $($mut)? func.vararg_annotation {
+				visitor.visit_type_pack(pack);
+			}
+
+			visitor.visit_block(& $($mut)? func.body);
+		}
+
+		pub fn walk_type<V: $trait + ?Sized>(visitor: &mut V, ty: & $($mut)? Type) {
+			let span = ty.span;
+
+			match & $($mut)? ty.kind {
+				TypeKind::Reference(ty) => visitor.visit_type_reference(span, ty),
+				TypeKind::Table(ty) => visitor.visit_type_table(span, ty),
+				TypeKind::Function(ty) => visitor.visit_type_function(span, ty),
+				TypeKind::Typeof(expr) => visitor.visit_type_typeof(span, expr),
+				TypeKind::Union(ty) => visitor.visit_type_union(span, ty),
+				TypeKind::Intersection(ty) => visitor.visit_type_intersection(span, ty),
+				TypeKind::SingletonBool(value) => visitor.visit_type_singleton_bool(span, value),
+				TypeKind::SingletonString(value) => visitor.visit_type_singleton_string(span, value),
+				TypeKind::Error(ty) => visitor.visit_type_error(span, ty)
+			}
+		}
+
+		pub fn walk_type_reference<V: $trait + ?Sized>(visitor: &mut V, ty: & $($mut)? TypeReference) {
+			for parameter in & $($mut)? ty.parameters {
+				match parameter {
+					TypeOrPack::Type(ty) => visitor.visit_type(ty),
+					TypeOrPack::Pack(pack) => visitor.visit_type_pack(pack)
+				}
+			}
+		}
+
+		pub fn walk_type_table<V: $trait + ?Sized>(visitor: &mut V, ty: & $($mut)? TypeTable) {
+			for prop in & $($mut)? ty.props {
+				visitor.visit_type(& $($mut)? prop.ty);
+			}
+
+			if let Some(indexer) = & $($mut)? ty.indexer {
+				walk_table_indexer(visitor, indexer);
+			}
+		}
+
+		pub fn walk_type_function<V: $trait + ?Sized>(visitor: &mut V, ty: & $($mut)? TypeFunction) {
+			for attr in & $($mut)? ty.attributes {
+				visitor.visit_attr(attr);
+			}
+
+			walk_generics(visitor, & $($mut)? ty.generics, & $($mut)? ty.generic_packs);
+			visitor.visit_type_list(& $($mut)? ty.arg_types);
+			visitor.visit_type_list(& $($mut)? ty.return_types);
+		}
+
+		pub fn walk_type_pack<V: $trait + ?Sized>(visitor: &mut V, pack: & $($mut)? TypePack) {
+			let span = pack.span;
+
+			match & $($mut)? pack.kind {
+				TypePackKind::Explicit(list) => visitor.visit_type_pack_explicit(span, list),
+				TypePackKind::Variadic(ty) => visitor.visit_type_pack_variadic(span, ty),
+				TypePackKind::Generic(name) => visitor.visit_type_pack_generic(span, name)
+			}
+		}
+
+		pub fn walk_type_list<V: $trait + ?Sized>(visitor: &mut V, list: & $($mut)? TypeList) {
+			for ty in & $($mut)? list.types {
+				visitor.visit_type(ty);
+			}
+
+			if let Some(tail) = & $($mut)? list.tail {
+				visitor.visit_type_pack(tail);
+			}
+		}
+
+		fn walk_table_indexer<V: $trait + ?Sized>(visitor: &mut V, indexer: & $($mut)? TableIndexer) {
+			visitor.visit_type(& $($mut)? indexer.index_type);
+			visitor.visit_type(& $($mut)? indexer.result_type);
+		}
+
+		/// Visits the defaults of generic type parameters.
+		fn walk_generics<V: $trait + ?Sized>(visitor: &mut V, generics: & $($mut)? [GenericType], packs: & $($mut)? [GenericTypePack]) {
+			for generic in generics {
+				if let Some(default) = & $($mut)? generic.default {
+					visitor.visit_type(default);
+				}
+			}
+
+			for pack in packs {
+				if let Some(default) = & $($mut)? pack.default {
+					visitor.visit_type_pack(default);
+				}
+			}
+		}
+	}
+}
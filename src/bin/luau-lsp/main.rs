@@ -0,0 +1,105 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! A language server for Luau, speaking the Language Server Protocol over
+//! stdin and stdout. Documents are checked as they are opened and changed, and
+//! can require each other by their URI.
+
+use std::io::{BufRead, Write};
+
+use serde_json::Value;
+
+use server::Server;
+
+mod server;
+mod symbols;
+
+/// Reads the body of a message framed by a `Content-Length` header, returning
+/// `None` at the end of the input.
+fn read_message(input: &mut impl BufRead) -> std::io::Result<Option<Vec<u8>>> {
+	let mut length = None;
+
+	loop {
+		let mut line = String::new();
+
+		if input.read_line(&mut line)? == 0 {
+			return Ok(None)
+		}
+
+		let line = line.trim_end();
+
+		if line.is_empty() {
+			break
+		}
+
+		if let Some((name, value)) = line.split_once(':') {
+			if name.eq_ignore_ascii_case("Content-Length") {
+				length = value.trim().parse::<usize>().ok();
+			}
+		}
+	}
+
+	let Some(length) = length else {
+		return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "missing Content-Length header"))
+	};
+
+	let mut body = vec![0; length];
+	input.read_exact(&mut body)?;
+	Ok(Some(body))
+}
+
+fn write_message(output: &mut impl Write, message: &Value) -> std::io::Result<()> {
+	let body = message.to_string();
+	write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+	output.flush()
+}
+
+fn main() {
+	let stdin = std::io::stdin();
+	let stdout = std::io::stdout();
+	let (mut input, mut output) = (stdin.lock(), stdout.lock());
+	let mut server = Server::new();
+
+	loop {
+		let body = match read_message(&mut input) {
+			Ok(Some(body)) => body,
+			Ok(None) => break,
+
+			Err(error) => {
+				eprintln!("luau-lsp: {}", error);
+				break
+			}
+		};
+
+		// the framing was fine, so the next message can still be read
+		let replies = match serde_json::from_slice::<Value>(&body) {
+			Ok(message) => server.handle(message),
+			Err(error) => vec![server::parse_error(&error.to_string())]
+		};
+
+		for reply in replies {
+			if write_message(&mut output, &reply).is_err() {
+				return
+			}
+		}
+
+		if let Some(code) = server.exit_code() {
+			std::process::exit(code)
+		}
+	}
+
+	// the client went away without asking us to exit
+	std::process::exit(1)
+}
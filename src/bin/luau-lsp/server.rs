@@ -0,0 +1,307 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+
+use luau::analysis::{CompletionKind, Frontend, TypeError};
+use luau::ast::source_map::SourceMap;
+use luau::ast::{Mode, Position, Span};
+
+use crate::symbols::document_symbols;
+
+// error codes from the JSON-RPC and LSP specifications
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_NOT_INITIALIZED: i64 = -32002;
+const INVALID_REQUEST: i64 = -32600;
+
+/// Handles one message at a time, returning the messages to send back.
+pub struct Server {
+	frontend: Frontend,
+	documents: HashMap<String, String>,
+	initialized: bool,
+	shutdown: bool,
+	exit_code: Option<i32>
+}
+
+impl Server {
+	pub fn new() -> Self {
+		Self {
+			frontend: Frontend::new(Mode::Nonstrict),
+			documents: HashMap::new(),
+			initialized: false,
+			shutdown: false,
+			exit_code: None
+		}
+	}
+
+	/// Set once the client has asked the server to exit.
+	pub fn exit_code(&self) -> Option<i32> {
+		self.exit_code
+	}
+
+	pub fn handle(&mut self, message: Value) -> Vec<Value> {
+		let method = message["method"].as_str().unwrap_or_default().to_owned();
+		let params = &message["params"];
+
+		// requests have an id, notifications don't
+		let Some(id) = message.get("id").cloned() else {
+			return self.notification(&method, params)
+		};
+
+		let result = if method == "initialize" {
+			self.initialized = true;
+			Ok(initialize_result())
+		} else if !self.initialized {
+			Err((SERVER_NOT_INITIALIZED, "the server has not been initialized".to_owned()))
+		} else if self.shutdown {
+			Err((INVALID_REQUEST, "the server is shutting down".to_owned()))
+		} else {
+			self.request(&method, params)
+		};
+
+		vec![match result {
+			Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+			Err((code, message)) => json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+		}]
+	}
+
+	fn request(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+		if method == "shutdown" {
+			self.shutdown = true;
+			return Ok(Value::Null)
+		}
+
+		if !matches!(method, "textDocument/hover" | "textDocument/completion" | "textDocument/definition" | "textDocument/documentSymbol") {
+			return Err((METHOD_NOT_FOUND, format!("unknown method {}", method)))
+		}
+
+		let uri = params["textDocument"]["uri"].as_str().ok_or((INVALID_PARAMS, "missing textDocument.uri".to_owned()))?;
+		let Some(text) = self.documents.get(uri) else { return Ok(Value::Null) };
+		let map = SourceMap::new(text);
+
+		if method == "textDocument/documentSymbol" {
+			return Ok(document_symbols(&map))
+		}
+
+		let position = position_from_lsp(&map, &params["position"]).ok_or((INVALID_PARAMS, "invalid position".to_owned()))?;
+
+		Ok(match method {
+			"textDocument/hover" => match self.frontend.type_at(uri, position) {
+				Some(ty) => json!({ "contents": { "kind": "markdown", "value": format!("```luau\n{}\n```", ty) } }),
+				None => Value::Null
+			}
+
+			"textDocument/completion" => {
+				let items: Vec<Value> = self.frontend.autocomplete(uri, position).into_iter().map(|item| {
+					let mut value = json!({
+						"label": item.label,
+						"kind": completion_kind(item.kind),
+						"deprecated": item.deprecated
+					});
+
+					if let Some(ty) = item.ty {
+						value["detail"] = ty.into();
+					}
+
+					if let Some(insert_text) = item.insert_text {
+						value["insertText"] = insert_text.into();
+					}
+
+					value
+				}).collect();
+
+				json!({ "isIncomplete": false, "items": items })
+			}
+
+			// textDocument/definition
+			_ => match self.frontend.definition_of(uri, position) {
+				Some((module, span)) => match self.documents.get(&module) {
+					Some(text) => json!({ "uri": module, "range": range_to_lsp(&SourceMap::new(text), span) }),
+					None => Value::Null
+				}
+
+				None => Value::Null
+			}
+		})
+	}
+
+	fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+		match method {
+			"exit" => {
+				self.exit_code = Some(if self.shutdown { 0 } else { 1 });
+				Vec::new()
+			}
+
+			"textDocument/didOpen" => {
+				let document = &params["textDocument"];
+
+				match (document["uri"].as_str(), document["text"].as_str()) {
+					(Some(uri), Some(text)) => self.update(uri, text.to_owned()),
+					_ => Vec::new()
+				}
+			}
+
+			"textDocument/didChange" => {
+				// only full document sync is advertised, so the last change
+				// holds the whole document
+				let text = params["contentChanges"].as_array().and_then(|changes| changes.last()).and_then(|change| change["text"].as_str());
+
+				match (params["textDocument"]["uri"].as_str(), text) {
+					(Some(uri), Some(text)) => self.update(uri, text.to_owned()),
+					_ => Vec::new()
+				}
+			}
+
+			"textDocument/didClose" => match params["textDocument"]["uri"].as_str() {
+				Some(uri) => {
+					self.documents.remove(uri);
+					self.frontend.remove_source(uri);
+
+					let mut replies = vec![publish_diagnostics(uri, Vec::new())];
+					replies.extend(self.publish());
+					replies
+				}
+
+				None => Vec::new()
+			}
+
+			_ => Vec::new()
+		}
+	}
+
+	/// Stores the new text of a document, and publishes its diagnostics.
+	fn update(&mut self, uri: &str, text: String) -> Vec<Value> {
+		self.frontend.set_source(uri, text.clone());
+		self.documents.insert(uri.to_owned(), text);
+		self.publish()
+	}
+
+	/// Checks the documents that changed since they were last checked, along
+	/// with the ones that require them, and publishes their diagnostics.
+	fn publish(&mut self) -> Vec<Value> {
+		let checked: Vec<(String, Vec<TypeError>)> = self.frontend.check_changed().into_iter()
+			.filter(|(uri, _)| self.documents.contains_key(uri))
+			.collect();
+
+		checked.into_iter().map(|(uri, errors)| {
+			let diagnostics = self.diagnostics(&uri, errors);
+			publish_diagnostics(&uri, diagnostics)
+		}).collect()
+	}
+
+	fn diagnostics(&mut self, uri: &str, errors: Vec<TypeError>) -> Vec<Value> {
+		let map = SourceMap::new(&self.documents[uri]);
+
+		let errors = errors.into_iter()
+			.map(|error| diagnostic(&map, error.span, 1, &format!("{:?}", error.kind), &error.message));
+
+		// 1 is an error and 2 a warning
+		let severity = if self.frontend.lint_errors() { 1 } else { 2 };
+		let options = self.frontend.lint_options();
+
+		let warnings = self.frontend.lint(uri, &options).into_iter()
+			.map(|warning| diagnostic(&map, warning.span, severity, warning.code.name().unwrap_or("Unknown"), &warning.message));
+
+		errors.chain(warnings).collect()
+	}
+}
+
+fn initialize_result() -> Value {
+	json!({
+		"capabilities": {
+			// full document sync
+			"textDocumentSync": 1,
+			"hoverProvider": true,
+			"completionProvider": { "triggerCharacters": [".", ":"] },
+			"definitionProvider": true,
+			"documentSymbolProvider": true
+		},
+		"serverInfo": { "name": "luau-lsp", "version": env!("CARGO_PKG_VERSION") }
+	})
+}
+
+/// The reply to a message that isn't valid JSON, which has no ID to reply to.
+pub fn parse_error(message: &str) -> Value {
+	json!({ "jsonrpc": "2.0", "id": null, "error": { "code": PARSE_ERROR, "message": message } })
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+	json!({
+		"jsonrpc": "2.0",
+		"method": "textDocument/publishDiagnostics",
+		"params": { "uri": uri, "diagnostics": diagnostics }
+	})
+}
+
+fn diagnostic(map: &SourceMap, span: Span, severity: u8, code: &str, message: &str) -> Value {
+	json!({
+		"range": range_to_lsp(map, span),
+		"severity": severity,
+		"code": code,
+		"source": "luau",
+		"message": message
+	})
+}
+
+fn completion_kind(kind: CompletionKind) -> u8 {
+	// numbered like LSP's CompletionItemKind
+	match kind {
+		CompletionKind::Property => 10,
+		CompletionKind::Method => 2,
+		CompletionKind::Variable => 6,
+		CompletionKind::Function | CompletionKind::GeneratedFunction => 3,
+		CompletionKind::Keyword => 14,
+		CompletionKind::String => 12,
+		CompletionKind::Type => 7,
+		CompletionKind::Module => 9,
+		CompletionKind::RequirePath => 17
+	}
+}
+
+/// LSP counts columns in UTF-16 code units, while Luau counts them in bytes.
+pub fn position_from_lsp(map: &SourceMap, position: &Value) -> Option<Position> {
+	let line = position["line"].as_u64()? as u32;
+	let character = position["character"].as_u64()? as usize;
+	let text = map.line(line)?;
+
+	let mut units = 0;
+	let column = text.char_indices()
+		.find(|(_, char)| {
+			units += char.len_utf16();
+			units > character
+		})
+		.map_or(text.len(), |(index, _)| index);
+
+	Some(Position { line, column: column as u32 })
+}
+
+pub fn position_to_lsp(map: &SourceMap, position: Position) -> Value {
+	let text = map.line(position.line).unwrap_or_default();
+	let mut column = (position.column as usize).min(text.len());
+
+	while !text.is_char_boundary(column) {
+		column -= 1;
+	}
+
+	json!({ "line": position.line, "character": text[..column].encode_utf16().count() })
+}
+
+pub fn range_to_lsp(map: &SourceMap, span: Span) -> Value {
+	json!({ "start": position_to_lsp(map, span.0), "end": position_to_lsp(map, span.1) })
+}
@@ -0,0 +1,113 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use serde_json::{json, Value};
+
+use luau::ast::source_map::SourceMap;
+use luau::ast::tree::{Ast, Block, Expr, ExprKind, Stat, StatKind};
+use luau::ast::{parse_recovering, ParseOptions, Span};
+
+use crate::server::range_to_lsp;
+
+// numbered like LSP's SymbolKind
+const CLASS: u8 = 5;
+const METHOD: u8 = 6;
+const FUNCTION: u8 = 12;
+const VARIABLE: u8 = 13;
+const STRUCT: u8 = 23;
+
+/// Lists the locals, functions and types declared in a document. Functions
+/// contain what is declared in their bodies. Documents that don't parse still
+/// produce the symbols that could be recovered.
+pub fn document_symbols(map: &SourceMap) -> Value {
+	match parse_recovering(map.source(), &ParseOptions::default()) {
+		Ok(result) => Value::Array(block_symbols(map, &result.ast, &result.ast.root)),
+		Err(_) => Value::Array(Vec::new())
+	}
+}
+
+fn symbol(map: &SourceMap, name: String, kind: u8, span: Span, selection: Span, children: Vec<Value>) -> Value {
+	json!({
+		"name": name,
+		"kind": kind,
+		"range": range_to_lsp(map, span),
+		"selectionRange": range_to_lsp(map, selection),
+		"children": children
+	})
+}
+
+fn block_symbols(map: &SourceMap, ast: &Ast, block: &Block) -> Vec<Value> {
+	let mut symbols = Vec::new();
+
+	for stat in &block.body {
+		stat_symbols(map, ast, stat, &mut symbols);
+	}
+
+	symbols
+}
+
+fn stat_symbols(map: &SourceMap, ast: &Ast, stat: &Stat, symbols: &mut Vec<Value>) {
+	match &stat.kind {
+		StatKind::Local(local) => for var in &local.vars {
+			let local = ast.local(var.local);
+			symbols.push(symbol(map, local.name.clone(), VARIABLE, stat.span, local.span, Vec::new()));
+		}
+
+		StatKind::LocalFunction(function) => {
+			let local = ast.local(function.name);
+			let children = block_symbols(map, ast, &function.func.body);
+			symbols.push(symbol(map, local.name.clone(), FUNCTION, stat.span, local.span, children));
+		}
+
+		StatKind::Function(function) => if let Some(name) = function_name(ast, &function.name) {
+			let kind = if matches!(&function.name.kind, ExprKind::IndexName(index) if index.op == ':') { METHOD } else { FUNCTION };
+			let children = block_symbols(map, ast, &function.func.body);
+			symbols.push(symbol(map, name, kind, stat.span, function.name.span, children));
+		}
+
+		StatKind::TypeAlias(alias) => symbols.push(symbol(map, alias.name.clone(), STRUCT, stat.span, alias.name_span, Vec::new())),
+		StatKind::DeclareGlobal(global) => symbols.push(symbol(map, global.name.clone(), VARIABLE, stat.span, global.name_span, Vec::new())),
+		StatKind::DeclareFunction(function) => symbols.push(symbol(map, function.name.clone(), FUNCTION, stat.span, function.name_span, Vec::new())),
+		StatKind::DeclareClass(class) => symbols.push(symbol(map, class.name.clone(), CLASS, stat.span, stat.span, Vec::new())),
+
+		// declarations inside of control flow are listed alongside the
+		// statement, since there is no symbol for the statement itself
+		StatKind::Block(block) => symbols.extend(block_symbols(map, ast, block)),
+		StatKind::While(stat) => symbols.extend(block_symbols(map, ast, &stat.body)),
+		StatKind::Repeat(stat) => symbols.extend(block_symbols(map, ast, &stat.body)),
+		StatKind::For(stat) => symbols.extend(block_symbols(map, ast, &stat.body)),
+		StatKind::ForIn(stat) => symbols.extend(block_symbols(map, ast, &stat.body)),
+
+		StatKind::If(stat) => {
+			symbols.extend(block_symbols(map, ast, &stat.then_body));
+
+			if let Some(else_body) = &stat.else_body {
+				stat_symbols(map, ast, else_body, symbols);
+			}
+		}
+
+		_ => {}
+	}
+}
+
+/// Formats the name of a function statement, like `module.sub:method`.
+fn function_name(ast: &Ast, expr: &Expr) -> Option<String> {
+	match &expr.kind {
+		ExprKind::Global(name) => Some(name.clone()),
+		ExprKind::Local(local) => Some(ast.local(local.local).name.clone()),
+		ExprKind::IndexName(index) => Some(format!("{}{}{}", function_name(ast, &index.expr)?, index.op, index.index)),
+		_ => None
+	}
+}
@@ -0,0 +1,254 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::ffi::CStr;
+use std::num::NonZeroUsize;
+use std::os::raw::{c_char, c_int};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::ast::ParseOptions;
+use crate::compiler::bytecode::Bytecode;
+use crate::compiler::{compile_unlocked, CompiledFunction, CompileError, CompileOptions};
+use crate::fvalue::{self, FValue};
+
+/// 128-bit FNV-1a. The cache key has to be stable across processes and Rust
+/// versions, which rules out `std::hash`.
+struct ContentHash(u128);
+
+impl ContentHash {
+	fn new() -> Self { Self(0x6c62272e07bb014262b821756295c58d) }
+
+	fn write(&mut self, bytes: &[u8]) {
+		for byte in bytes {
+			self.0 ^= *byte as u128;
+			self.0 = self.0.wrapping_mul(0x0000000001000000000000000000013b);
+		}
+	}
+
+	/// Writes a length-prefixed field, so that adjacent fields can't bleed into
+	/// each other.
+	fn field(&mut self, bytes: &[u8]) {
+		self.write(&(bytes.len() as u64).to_le_bytes());
+		self.write(bytes);
+	}
+
+	unsafe fn c_str(&mut self, str: *const c_char) {
+		if str.is_null() {
+			self.write(&[0]);
+		} else {
+			self.write(&[1]);
+			self.field(CStr::from_ptr(str).to_bytes());
+		}
+	}
+}
+
+/// The result of compiling a single entry of a [`Batch`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct BatchOutput {
+	pub name: String,
+	pub result: Result<CompiledFunction, CompileError>,
+	/// Whether the bytecode was loaded from the cache instead of compiled.
+	pub cached: bool
+}
+
+/// Compiles many sources at once, spread across multiple threads, optionally
+/// caching the resulting bytecode on disk. There is no thread pool: each call
+/// to [`Self::compile`] starts its own scoped threads, which take sources from
+/// a shared counter until none are left.
+///
+/// Cache entries are keyed by a hash of the source, the compile and parse
+/// options, the bytecode version and the value of every FValue, so changing
+/// any of them invalidates the cache. FValues cannot be changed while a batch
+/// is compiling.
+#[derive(Debug)]
+pub struct Batch {
+	compile_opts: CompileOptions,
+	parse_opts: ParseOptions,
+	cache_dir: Option<PathBuf>,
+	threads: Option<NonZeroUsize>,
+	sources: Vec<(String, String)>
+}
+
+impl Batch {
+	pub fn new(compile_opts: CompileOptions, parse_opts: ParseOptions) -> Self {
+		Self {
+			compile_opts,
+			parse_opts,
+			cache_dir: None,
+			threads: None,
+			sources: Vec::new()
+		}
+	}
+
+	/// Sets the directory to store cached bytecode in. It will be created if it
+	/// does not exist. Without a cache directory, everything is compiled.
+	pub fn set_cache_dir(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+		self.cache_dir = Some(dir.into());
+		self
+	}
+
+	/// Sets the number of threads to compile on. Defaults to the available
+	/// parallelism of the system.
+	pub fn set_threads(&mut self, threads: NonZeroUsize) -> &mut Self {
+		self.threads = Some(threads);
+		self
+	}
+
+	pub fn add(&mut self, name: impl Into<String>, source: impl Into<String>) -> &mut Self {
+		self.sources.push((name.into(), source.into()));
+		self
+	}
+
+	pub fn cache_dir(&self) -> Option<&Path> {
+		self.cache_dir.as_deref()
+	}
+
+	pub fn len(&self) -> usize {
+		self.sources.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.sources.is_empty()
+	}
+
+	/// Compiles every source in the batch, returning the results in the order
+	/// the sources were added.
+	///
+	/// The cache is only an optimization, so failures to read or write it are
+	/// not errors; the affected sources are simply compiled again.
+	pub fn compile(&self) -> Vec<BatchOutput> {
+		let _flags = fvalue::read_lock();
+
+		let base = self.cache_dir.as_ref().map(|dir| {
+			let _ = std::fs::create_dir_all(dir);
+			self.base_hash()
+		});
+
+		let threads = self.threads
+			.or_else(|| std::thread::available_parallelism().ok())
+			.map_or(1, NonZeroUsize::get)
+			.min(self.sources.len().max(1));
+
+		let next = AtomicUsize::new(0);
+		let outputs = Mutex::new(vec![None; self.sources.len()]);
+
+		std::thread::scope(|scope| {
+			for thread in 0..threads {
+				let (next, outputs, base) = (&next, &outputs, &base);
+
+				scope.spawn(move || loop {
+					let index = next.fetch_add(1, Ordering::Relaxed);
+					let Some((name, source)) = self.sources.get(index) else { break };
+
+					let cache_path = base.as_ref().and_then(|base| self.cache_path(base, source));
+					let output = self.compile_one(name, source, cache_path.as_deref(), thread);
+
+					outputs.lock().unwrap()[index] = Some(output);
+				});
+			}
+		});
+
+		outputs.into_inner().unwrap().into_iter().map(Option::unwrap).collect()
+	}
+
+	fn compile_one(&self, name: &str, source: &str, cache_path: Option<&Path>, thread: usize) -> BatchOutput {
+		let cached = cache_path
+			.and_then(|path| std::fs::read(path).ok())
+			.filter(|bytecode| Bytecode::parse(bytecode).is_ok());
+
+		if let Some(bytecode) = cached {
+			return BatchOutput {
+				name: name.to_owned(),
+				result: Ok(CompiledFunction(bytecode)),
+				cached: true
+			}
+		}
+
+		let result = compile_unlocked(source, &self.compile_opts, &self.parse_opts);
+
+		if let (Some(path), Ok(function)) = (cache_path, &result) {
+			// write to a temporary file first so that other processes sharing
+			// the cache never see a partially written entry
+			let temp = path.with_extension(format!("{}.{}.tmp", std::process::id(), thread));
+
+			if std::fs::write(&temp, &function.0).is_err() || std::fs::rename(&temp, path).is_err() {
+				let _ = std::fs::remove_file(&temp);
+			}
+		}
+
+		BatchOutput {
+			name: name.to_owned(),
+			result,
+			cached: false
+		}
+	}
+
+	/// Hashes everything that affects compilation except for the source. The
+	/// caller must hold the FValue read lock.
+	fn base_hash(&self) -> ContentHash {
+		let mut hash = ContentHash::new();
+		let opts = &self.compile_opts.0;
+
+		hash.field(&opts.optimizationLevel.to_le_bytes());
+		hash.field(&opts.debugLevel.to_le_bytes());
+		hash.field(&opts.coverageLevel.to_le_bytes());
+
+		unsafe {
+			hash.c_str(opts.vectorLib);
+			hash.c_str(opts.vectorCtor);
+
+			if !opts.mutableGlobals.is_null() {
+				let mut global = opts.mutableGlobals;
+
+				while !(*global).is_null() {
+					hash.c_str(*global);
+					global = global.add(1);
+				}
+			}
+		}
+
+		hash.write(&[0xff]);
+		hash.field(&[self.parse_opts.allow_declaration_syntax() as u8, self.parse_opts.capture_comments() as u8]);
+
+		// the first byte of any bytecode is its version, which depends on the
+		// Luau version and possibly on FFlags
+		let version = compile_unlocked("", &CompileOptions::default(), &ParseOptions::default())
+			.map_or(0, |function| function.0.first().copied().unwrap_or(0));
+
+		hash.field(&[version]);
+
+		// re-entering the read lock through FValue::value could deadlock if a
+		// writer is waiting
+		for fflag in FValue::<bool>::list() {
+			hash.field(fflag.name().as_bytes());
+			hash.field(&[fflag.value_unlocked() as u8]);
+		}
+
+		for fint in FValue::<c_int>::list() {
+			hash.field(fint.name().as_bytes());
+			hash.field(&fint.value_unlocked().to_le_bytes());
+		}
+
+		hash
+	}
+
+	fn cache_path(&self, base: &ContentHash, source: &str) -> Option<PathBuf> {
+		let mut hash = ContentHash(base.0);
+		hash.field(source.as_bytes());
+		Some(self.cache_dir.as_ref()?.join(format!("{:032x}.luauc", hash.0)))
+	}
+}
@@ -0,0 +1,775 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+//! A reader and writer for Luau's serialized bytecode format, as produced by
+//! `BytecodeBuilder::getBytecode` and consumed by `luau_load`.
+
+use std::collections::HashMap;
+
+use bstr::{BStr, BString, ByteSlice};
+
+/// The oldest bytecode version that can be read.
+pub const VERSION_MIN: u8 = 3;
+/// The newest bytecode version that can be read.
+pub const VERSION_MAX: u8 = 6;
+/// The newest type information version that can be read.
+pub const TYPES_VERSION_MAX: u8 = 3;
+
+const CONSTANT_NIL: u8 = 0;
+const CONSTANT_BOOLEAN: u8 = 1;
+const CONSTANT_NUMBER: u8 = 2;
+const CONSTANT_STRING: u8 = 3;
+const CONSTANT_IMPORT: u8 = 4;
+const CONSTANT_TABLE: u8 = 5;
+const CONSTANT_CLOSURE: u8 = 6;
+const CONSTANT_VECTOR: u8 = 7;
+
+const DEBUG_INFO_VERSION: u8 = 1;
+
+#[derive(Clone, Eq, PartialEq, Debug, thiserror::Error)]
+pub enum BytecodeError {
+	/// The bytecode encodes a compile error instead of a function. This is what
+	/// `Luau::compile_sneakily` produces when compilation fails.
+	#[error("bytecode contains a compile error: {0}")]
+	CompileError(BString),
+
+	#[error("unsupported bytecode version {0}")]
+	UnsupportedVersion(u8),
+
+	#[error("unsupported types version {0}")]
+	UnsupportedTypesVersion(u8),
+
+	#[error("unsupported debug info version {0}")]
+	UnsupportedDebugInfoVersion(u8),
+
+	#[error("unknown constant type {0}")]
+	UnknownConstant(u8),
+
+	#[error("unexpected end of bytecode")]
+	UnexpectedEof,
+
+	#[error("malformed varint")]
+	MalformedVarInt,
+
+	#[error("malformed line info")]
+	MalformedLineInfo,
+
+	#[error("string reference {0} is out of bounds")]
+	InvalidStringRef(StringRef),
+
+	#[error("trailing data after bytecode")]
+	TrailingData
+}
+
+/// A string reference as used by the bytecode format: a 1-based index into the
+/// string table, where 0 means no string.
+pub type StringRef = u32;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Constant {
+	Nil,
+	Boolean(bool),
+	Number(f64),
+	Vector([f32; 4]),
+	String(StringRef),
+	Import(u32),
+	Table(Vec<u32>),
+	Closure(u32)
+}
+
+/// Line information for a function. Lines are stored relative to a baseline
+/// that changes every `2^gap_log2` instructions, which is how they are encoded.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct LineInfo {
+	pub gap_log2: u8,
+	pub offsets: Vec<u8>,
+	pub baselines: Vec<i32>
+}
+
+impl LineInfo {
+	/// Returns the line that the instruction at `pc` originates from.
+	pub fn line(&self, pc: usize) -> Option<u32> {
+		let offset = *self.offsets.get(pc)?;
+		let baseline = *self.baselines.get(pc >> self.gap_log2)?;
+		Some((baseline + offset as i32) as u32)
+	}
+
+	/// Returns the line of every instruction in order.
+	pub fn lines(&self) -> Vec<u32> {
+		(0..self.offsets.len()).filter_map(|pc| self.line(pc)).collect()
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct LocalVariable {
+	pub name: StringRef,
+	pub start_pc: u32,
+	pub end_pc: u32,
+	pub register: u8
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ProtoDebugInfo {
+	pub locals: Vec<LocalVariable>,
+	pub upvalues: Vec<StringRef>
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Proto {
+	pub max_stack_size: u8,
+	pub num_params: u8,
+	pub num_upvalues: u8,
+	pub is_vararg: bool,
+	pub flags: u8,
+	pub types: Vec<u8>,
+	pub code: Vec<u32>,
+	pub constants: Vec<Constant>,
+	pub children: Vec<u32>,
+	pub line_defined: u32,
+	pub debug_name: StringRef,
+	pub line_info: Option<LineInfo>,
+	pub debug_info: Option<ProtoDebugInfo>
+}
+
+/// A parsed bytecode chunk. Parsing and then re-encoding a chunk produces
+/// exactly the same bytes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Bytecode {
+	pub version: u8,
+	pub types_version: u8,
+	pub strings: Vec<BString>,
+	pub userdata_types: Vec<(u8, StringRef)>,
+	pub protos: Vec<Proto>,
+	pub main: u32
+}
+
+struct Reader<'a> {
+	data: &'a [u8],
+	offset: usize
+}
+
+impl<'a> Reader<'a> {
+	fn new(data: &'a [u8]) -> Self { Self { data, offset: 0 } }
+
+	fn bytes(&mut self, len: usize) -> Result<&'a [u8], BytecodeError> {
+		let end = self.offset.checked_add(len).filter(|end| *end <= self.data.len()).ok_or(BytecodeError::UnexpectedEof)?;
+		let bytes = &self.data[self.offset..end];
+		self.offset = end;
+		Ok(bytes)
+	}
+
+	fn array<const N: usize>(&mut self) -> Result<[u8; N], BytecodeError> {
+		let mut array = [0u8; N];
+		array.copy_from_slice(self.bytes(N)?);
+		Ok(array)
+	}
+
+	fn byte(&mut self) -> Result<u8, BytecodeError> { Ok(self.bytes(1)?[0]) }
+	fn u32(&mut self) -> Result<u32, BytecodeError> { Ok(u32::from_le_bytes(self.array()?)) }
+	fn i32(&mut self) -> Result<i32, BytecodeError> { Ok(i32::from_le_bytes(self.array()?)) }
+	fn f32(&mut self) -> Result<f32, BytecodeError> { Ok(f32::from_le_bytes(self.array()?)) }
+	fn f64(&mut self) -> Result<f64, BytecodeError> { Ok(f64::from_le_bytes(self.array()?)) }
+
+	fn varint(&mut self) -> Result<u32, BytecodeError> {
+		let mut result = 0u32;
+
+		for shift in (0..35).step_by(7) {
+			let byte = self.byte()?;
+			let bits = (byte & 127) as u32;
+
+			// the fifth byte only has room for the top 4 bits
+			if shift == 28 && bits > 15 {
+				return Err(BytecodeError::MalformedVarInt)
+			}
+
+			result |= bits << shift;
+
+			if byte & 128 == 0 {
+				return Ok(result)
+			}
+		}
+
+		Err(BytecodeError::MalformedVarInt)
+	}
+
+	fn len(&mut self) -> Result<usize, BytecodeError> {
+		let len = self.varint()? as usize;
+
+		// every element takes at least one byte, so don't let garbage input
+		// make us allocate gigabytes up front
+		if len > self.data.len() - self.offset {
+			Err(BytecodeError::UnexpectedEof)
+		} else {
+			Ok(len)
+		}
+	}
+
+	fn finish(self) -> Result<(), BytecodeError> {
+		if self.offset == self.data.len() { Ok(()) } else { Err(BytecodeError::TrailingData) }
+	}
+}
+
+#[derive(Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+	fn bytes(&mut self, bytes: &[u8]) { self.0.extend_from_slice(bytes) }
+	fn byte(&mut self, byte: u8) { self.0.push(byte) }
+	fn u32(&mut self, value: u32) { self.bytes(&value.to_le_bytes()) }
+	fn i32(&mut self, value: i32) { self.bytes(&value.to_le_bytes()) }
+	fn f32(&mut self, value: f32) { self.bytes(&value.to_le_bytes()) }
+	fn f64(&mut self, value: f64) { self.bytes(&value.to_le_bytes()) }
+
+	fn varint(&mut self, mut value: u32) {
+		loop {
+			let byte = (value & 127) as u8;
+			value >>= 7;
+
+			if value == 0 {
+				break self.byte(byte)
+			}
+
+			self.byte(byte | 128);
+		}
+	}
+
+	fn len(&mut self, len: usize) { self.varint(len as u32) }
+}
+
+impl Constant {
+	fn read(reader: &mut Reader) -> Result<Self, BytecodeError> {
+		Ok(match reader.byte()? {
+			CONSTANT_NIL => Self::Nil,
+			CONSTANT_BOOLEAN => Self::Boolean(reader.byte()? != 0),
+			CONSTANT_NUMBER => Self::Number(reader.f64()?),
+			CONSTANT_VECTOR => Self::Vector([reader.f32()?, reader.f32()?, reader.f32()?, reader.f32()?]),
+			CONSTANT_STRING => Self::String(reader.varint()?),
+			CONSTANT_IMPORT => Self::Import(reader.u32()?),
+			CONSTANT_TABLE => {
+				let len = reader.len()?;
+				Self::Table((0..len).map(|_| reader.varint()).collect::<Result<_, _>>()?)
+			}
+			CONSTANT_CLOSURE => Self::Closure(reader.varint()?),
+			other => return Err(BytecodeError::UnknownConstant(other))
+		})
+	}
+
+	fn write(&self, writer: &mut Writer) {
+		match self {
+			Self::Nil => writer.byte(CONSTANT_NIL),
+			Self::Boolean(value) => {
+				writer.byte(CONSTANT_BOOLEAN);
+				writer.byte(*value as u8);
+			}
+			Self::Number(value) => {
+				writer.byte(CONSTANT_NUMBER);
+				writer.f64(*value);
+			}
+			Self::Vector(value) => {
+				writer.byte(CONSTANT_VECTOR);
+				value.iter().for_each(|component| writer.f32(*component));
+			}
+			Self::String(string) => {
+				writer.byte(CONSTANT_STRING);
+				writer.varint(*string);
+			}
+			Self::Import(id) => {
+				writer.byte(CONSTANT_IMPORT);
+				writer.u32(*id);
+			}
+			Self::Table(keys) => {
+				writer.byte(CONSTANT_TABLE);
+				writer.len(keys.len());
+				keys.iter().for_each(|key| writer.varint(*key));
+			}
+			Self::Closure(proto) => {
+				writer.byte(CONSTANT_CLOSURE);
+				writer.varint(*proto);
+			}
+		}
+	}
+}
+
+impl LineInfo {
+	fn read(reader: &mut Reader, code_len: usize) -> Result<Self, BytecodeError> {
+		let gap_log2 = reader.byte()?;
+
+		if gap_log2 >= 32 {
+			return Err(BytecodeError::MalformedLineInfo)
+		}
+
+		let intervals = if code_len == 0 { 0 } else { ((code_len - 1) >> gap_log2) + 1 };
+
+		let mut last_offset = 0u8;
+		let offsets = reader.bytes(code_len)?.iter().map(|delta| {
+			last_offset = last_offset.wrapping_add(*delta);
+			last_offset
+		}).collect();
+
+		let mut last_line = 0i32;
+		let mut baselines = Vec::with_capacity(intervals.min(code_len));
+
+		for _ in 0..intervals {
+			last_line = last_line.wrapping_add(reader.i32()?);
+			baselines.push(last_line);
+		}
+
+		Ok(Self { gap_log2, offsets, baselines })
+	}
+
+	fn write(&self, writer: &mut Writer) {
+		writer.byte(self.gap_log2);
+
+		let mut last_offset = 0u8;
+		for offset in &self.offsets {
+			writer.byte(offset.wrapping_sub(last_offset));
+			last_offset = *offset;
+		}
+
+		let mut last_line = 0i32;
+		for baseline in &self.baselines {
+			writer.i32(baseline.wrapping_sub(last_line));
+			last_line = *baseline;
+		}
+	}
+}
+
+impl ProtoDebugInfo {
+	fn read(reader: &mut Reader) -> Result<Self, BytecodeError> {
+		let locals_len = reader.len()?;
+		let mut locals = Vec::with_capacity(locals_len);
+
+		for _ in 0..locals_len {
+			locals.push(LocalVariable {
+				name: reader.varint()?,
+				start_pc: reader.varint()?,
+				end_pc: reader.varint()?,
+				register: reader.byte()?
+			});
+		}
+
+		let upvalues_len = reader.len()?;
+		let upvalues = (0..upvalues_len).map(|_| reader.varint()).collect::<Result<_, _>>()?;
+
+		Ok(Self { locals, upvalues })
+	}
+
+	fn write(&self, writer: &mut Writer) {
+		writer.len(self.locals.len());
+
+		for local in &self.locals {
+			writer.varint(local.name);
+			writer.varint(local.start_pc);
+			writer.varint(local.end_pc);
+			writer.byte(local.register);
+		}
+
+		writer.len(self.upvalues.len());
+		self.upvalues.iter().for_each(|upvalue| writer.varint(*upvalue));
+	}
+}
+
+impl Proto {
+	fn read(reader: &mut Reader, version: u8) -> Result<Self, BytecodeError> {
+		let max_stack_size = reader.byte()?;
+		let num_params = reader.byte()?;
+		let num_upvalues = reader.byte()?;
+		let is_vararg = reader.byte()? != 0;
+
+		let (flags, types) = if version >= 4 {
+			let flags = reader.byte()?;
+			let types_len = reader.len()?;
+			(flags, reader.bytes(types_len)?.to_vec())
+		} else {
+			(0, Vec::new())
+		};
+
+		let code_len = reader.len()?;
+		let code = (0..code_len).map(|_| reader.u32()).collect::<Result<_, _>>()?;
+
+		let constants_len = reader.len()?;
+		let constants = (0..constants_len).map(|_| Constant::read(reader)).collect::<Result<_, _>>()?;
+
+		let children_len = reader.len()?;
+		let children = (0..children_len).map(|_| reader.varint()).collect::<Result<_, _>>()?;
+
+		let line_defined = reader.varint()?;
+		let debug_name = reader.varint()?;
+
+		let line_info = if reader.byte()? != 0 { Some(LineInfo::read(reader, code_len)?) } else { None };
+		let debug_info = if reader.byte()? != 0 { Some(ProtoDebugInfo::read(reader)?) } else { None };
+
+		Ok(Self {
+			max_stack_size,
+			num_params,
+			num_upvalues,
+			is_vararg,
+			flags,
+			types,
+			code,
+			constants,
+			children,
+			line_defined,
+			debug_name,
+			line_info,
+			debug_info
+		})
+	}
+
+	fn write(&self, writer: &mut Writer, version: u8) {
+		writer.byte(self.max_stack_size);
+		writer.byte(self.num_params);
+		writer.byte(self.num_upvalues);
+		writer.byte(self.is_vararg as u8);
+
+		if version >= 4 {
+			writer.byte(self.flags);
+			writer.len(self.types.len());
+			writer.bytes(&self.types);
+		}
+
+		writer.len(self.code.len());
+		self.code.iter().for_each(|insn| writer.u32(*insn));
+
+		writer.len(self.constants.len());
+		self.constants.iter().for_each(|constant| constant.write(writer));
+
+		writer.len(self.children.len());
+		self.children.iter().for_each(|child| writer.varint(*child));
+
+		writer.varint(self.line_defined);
+		writer.varint(self.debug_name);
+
+		match &self.line_info {
+			Some(line_info) => {
+				writer.byte(1);
+				line_info.write(writer);
+			}
+			None => writer.byte(0)
+		}
+
+		match &self.debug_info {
+			Some(debug_info) => {
+				writer.byte(1);
+				debug_info.write(writer);
+			}
+			None => writer.byte(0)
+		}
+	}
+
+	fn string_refs_mut(&mut self) -> impl Iterator<Item = &mut StringRef> {
+		let constants = self.constants.iter_mut().filter_map(|constant| match constant {
+			Constant::String(string) => Some(string),
+			_ => None
+		});
+
+		let (locals, upvalues) = match &mut self.debug_info {
+			Some(debug_info) => (Some(debug_info.locals.iter_mut().map(|local| &mut local.name)), Some(debug_info.upvalues.iter_mut())),
+			None => (None, None)
+		};
+
+		std::iter::once(&mut self.debug_name)
+			.chain(constants)
+			.chain(locals.into_iter().flatten())
+			.chain(upvalues.into_iter().flatten())
+	}
+}
+
+impl Bytecode {
+	pub fn parse(data: &[u8]) -> Result<Self, BytecodeError> {
+		let mut reader = Reader::new(data);
+		let version = reader.byte()?;
+
+		if version == 0 {
+			return Err(BytecodeError::CompileError(BString::from(&data[1..])))
+		} else if !(VERSION_MIN..=VERSION_MAX).contains(&version) {
+			return Err(BytecodeError::UnsupportedVersion(version))
+		}
+
+		let types_version = if version >= 4 { reader.byte()? } else { 0 };
+
+		if types_version > TYPES_VERSION_MAX {
+			return Err(BytecodeError::UnsupportedTypesVersion(types_version))
+		}
+
+		let strings_len = reader.len()?;
+		let mut strings = Vec::with_capacity(strings_len);
+
+		for _ in 0..strings_len {
+			let len = reader.len()?;
+			strings.push(BString::from(reader.bytes(len)?));
+		}
+
+		let mut userdata_types = Vec::new();
+
+		if types_version == 3 {
+			loop {
+				let index = reader.byte()?;
+
+				if index == 0 {
+					break
+				}
+
+				userdata_types.push((index, reader.varint()?));
+			}
+		}
+
+		let protos_len = reader.len()?;
+		let protos = (0..protos_len).map(|_| Proto::read(&mut reader, version)).collect::<Result<_, _>>()?;
+		let main = reader.varint()?;
+
+		reader.finish()?;
+
+		let mut bytecode = Self { version, types_version, strings, userdata_types, protos, main };
+		let strings_len = bytecode.strings.len() as StringRef;
+
+		if let Some(string) = bytecode.string_refs_mut().find(|string| **string > strings_len) {
+			return Err(BytecodeError::InvalidStringRef(*string))
+		}
+
+		Ok(bytecode)
+	}
+
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut writer = Writer::default();
+		writer.byte(self.version);
+
+		if self.version >= 4 {
+			writer.byte(self.types_version);
+		}
+
+		writer.len(self.strings.len());
+
+		for string in &self.strings {
+			writer.len(string.len());
+			writer.bytes(string);
+		}
+
+		if self.types_version == 3 {
+			for (index, name) in &self.userdata_types {
+				writer.byte(*index);
+				writer.varint(*name);
+			}
+
+			writer.byte(0);
+		}
+
+		writer.len(self.protos.len());
+		self.protos.iter().for_each(|proto| proto.write(&mut writer, self.version));
+		writer.varint(self.main);
+
+		writer.0
+	}
+
+	/// Resolves a string reference into the string table.
+	pub fn string(&self, string: StringRef) -> Option<&BStr> {
+		self.strings.get(string.checked_sub(1)? as usize).map(|string| string.as_bstr())
+	}
+
+	/// Removes line information and local and upvalue names from every
+	/// function. Function names are kept so that tracebacks remain readable.
+	/// Strings that are no longer referenced are removed from the string table.
+	pub fn strip_debug_info(&mut self) {
+		for proto in &mut self.protos {
+			proto.line_info = None;
+			proto.debug_info = None;
+		}
+
+		self.compact_strings();
+	}
+
+	fn compact_strings(&mut self) {
+		let mut remap = HashMap::new();
+		let mut strings = Vec::new();
+
+		let old_strings = std::mem::take(&mut self.strings);
+
+		for string in self.string_refs_mut().filter(|string| **string != 0) {
+			let old = *string;
+
+			*string = *remap.entry(old).or_insert_with(|| {
+				strings.push(old_strings[old as usize - 1].clone());
+				strings.len() as StringRef
+			});
+		}
+
+		self.strings = strings;
+	}
+
+	fn string_refs_mut(&mut self) -> impl Iterator<Item = &mut StringRef> {
+		self.userdata_types.iter_mut().map(|(_, name)| name)
+			.chain(self.protos.iter_mut().flat_map(Proto::string_refs_mut))
+	}
+}
+
+/// A local variable as recorded in the debug information of a function.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct LocalDebugInfo {
+	pub name: Option<BString>,
+	pub start_pc: u32,
+	pub end_pc: u32,
+	pub register: u8
+}
+
+/// The debug information of a single function.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct FunctionDebugInfo {
+	pub name: Option<BString>,
+	pub line_defined: u32,
+	pub lines: Option<LineInfo>,
+	pub locals: Vec<LocalDebugInfo>,
+	pub upvalues: Vec<Option<BString>>
+}
+
+/// Debug information extracted from bytecode, indexed by function (proto) id.
+/// Function ids are unaffected by stripping, so this can be used to symbolicate
+/// locations in stripped bytecode.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct DebugInfo {
+	pub functions: Vec<FunctionDebugInfo>
+}
+
+impl DebugInfo {
+	pub fn from_bytecode(bytecode: &Bytecode) -> Self {
+		let string = |string| bytecode.string(string).map(BStr::to_owned);
+
+		Self {
+			functions: bytecode.protos.iter().map(|proto| FunctionDebugInfo {
+				name: string(proto.debug_name),
+				line_defined: proto.line_defined,
+				lines: proto.line_info.clone(),
+				locals: proto.debug_info.iter().flat_map(|debug_info| &debug_info.locals).map(|local| LocalDebugInfo {
+					name: string(local.name),
+					start_pc: local.start_pc,
+					end_pc: local.end_pc,
+					register: local.register
+				}).collect(),
+				upvalues: proto.debug_info.iter().flat_map(|debug_info| &debug_info.upvalues).map(|upvalue| string(*upvalue)).collect()
+			}).collect()
+		}
+	}
+
+	/// Returns the source line of instruction `pc` in function `function`.
+	pub fn line(&self, function: usize, pc: usize) -> Option<u32> {
+		self.functions.get(function)?.lines.as_ref()?.line(pc)
+	}
+
+	/// Returns the local variable that lives in `register` at instruction `pc`
+	/// in function `function`.
+	pub fn local(&self, function: usize, register: u8, pc: u32) -> Option<&LocalDebugInfo> {
+		self.functions.get(function)?.locals.iter()
+			.find(|local| local.register == register && (local.start_pc..local.end_pc).contains(&pc))
+	}
+
+	/// Serializes the debug information to a compact binary format that uses
+	/// the same encoding primitives as bytecode.
+	pub fn to_bytes(&self) -> Vec<u8> {
+		let mut writer = Writer::default();
+
+		fn string(writer: &mut Writer, string: &Option<BString>) {
+			match string {
+				Some(string) => {
+					writer.varint(string.len() as u32 + 1);
+					writer.bytes(string);
+				}
+				None => writer.varint(0)
+			}
+		}
+
+		writer.byte(DEBUG_INFO_VERSION);
+		writer.len(self.functions.len());
+
+		for function in &self.functions {
+			string(&mut writer, &function.name);
+			writer.varint(function.line_defined);
+
+			match &function.lines {
+				Some(lines) => {
+					writer.byte(1);
+					writer.len(lines.offsets.len());
+					lines.write(&mut writer);
+				}
+				None => writer.byte(0)
+			}
+
+			writer.len(function.locals.len());
+
+			for local in &function.locals {
+				string(&mut writer, &local.name);
+				writer.varint(local.start_pc);
+				writer.varint(local.end_pc);
+				writer.byte(local.register);
+			}
+
+			writer.len(function.upvalues.len());
+			function.upvalues.iter().for_each(|upvalue| string(&mut writer, upvalue));
+		}
+
+		writer.0
+	}
+
+	/// Deserializes debug information produced by [`DebugInfo::to_bytes`].
+	pub fn from_bytes(data: &[u8]) -> Result<Self, BytecodeError> {
+		let mut reader = Reader::new(data);
+
+		fn string(reader: &mut Reader) -> Result<Option<BString>, BytecodeError> {
+			match reader.varint()? {
+				0 => Ok(None),
+				len => Ok(Some(BString::from(reader.bytes(len as usize - 1)?)))
+			}
+		}
+
+		let version = reader.byte()?;
+
+		if version != DEBUG_INFO_VERSION {
+			return Err(BytecodeError::UnsupportedDebugInfoVersion(version))
+		}
+
+		let functions_len = reader.len()?;
+		let mut functions = Vec::with_capacity(functions_len);
+
+		for _ in 0..functions_len {
+			let name = string(&mut reader)?;
+			let line_defined = reader.varint()?;
+
+			let lines = if reader.byte()? != 0 {
+				let code_len = reader.len()?;
+				Some(LineInfo::read(&mut reader, code_len)?)
+			} else {
+				None
+			};
+
+			let locals_len = reader.len()?;
+			let mut locals = Vec::with_capacity(locals_len);
+
+			for _ in 0..locals_len {
+				locals.push(LocalDebugInfo {
+					name: string(&mut reader)?,
+					start_pc: reader.varint()?,
+					end_pc: reader.varint()?,
+					register: reader.byte()?
+				});
+			}
+
+			let upvalues_len = reader.len()?;
+			let upvalues = (0..upvalues_len).map(|_| string(&mut reader)).collect::<Result<_, _>>()?;
+
+			functions.push(FunctionDebugInfo { name, line_defined, lines, locals, upvalues });
+		}
+
+		reader.finish()?;
+		Ok(Self { functions })
+	}
+}
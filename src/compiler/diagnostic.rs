@@ -0,0 +1,142 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::{Display, Formatter, Write};
+
+#[cfg(feature = "analysis")]
+use crate::analysis::LintWarning;
+use crate::ast::{Label, Span, Suggestion};
+use crate::ast::source_map::SourceMap;
+use crate::compiler::{CompileError, Error};
+
+/// How serious a diagnostic is. Luau has no parse or compile warnings, so
+/// everything produced by [`CompileError::diagnostics`] is an [`Severity::Error`],
+/// while lints are reported as warnings.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum Severity {
+	Note,
+	Warning,
+	Error
+}
+
+impl Display for Severity {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		f.write_str(match self {
+			Self::Note => "note",
+			Self::Warning => "warning",
+			Self::Error => "error"
+		})
+	}
+}
+
+/// Which stage a diagnostic originates from.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum DiagnosticKind {
+	/// The source could not be parsed.
+	Parse,
+	/// The source was parsed, but could not be compiled, for example because a
+	/// function uses too many registers or upvalues.
+	Compile,
+	/// The source compiles, but a lint found something suspicious in it.
+	Lint
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Diagnostic {
+	pub severity: Severity,
+	pub kind: DiagnosticKind,
+	/// A short, stable code for what went wrong, for use in machine readable
+	/// output. This is the [`ErrorCode`](crate::ast::ErrorCode) name for errors
+	/// and the lint name for lints.
+	pub code: &'static str,
+	pub message: String,
+	pub span: Span,
+	pub secondary: Option<Label>,
+	pub suggestion: Option<Suggestion>
+}
+
+impl Display for Diagnostic {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}[{}]: {}: {}", self.severity, self.code, self.span, self.message)
+	}
+}
+
+impl Diagnostic {
+	pub fn from_error(kind: DiagnosticKind, error: &Error) -> Self {
+		Self {
+			severity: Severity::Error,
+			kind,
+			code: error.code.name(),
+			message: error.message.clone(),
+			span: error.span,
+			secondary: error.related.clone(),
+			suggestion: error.suggestion.clone()
+		}
+	}
+
+	/// Converts a lint warning into a [`Severity::Warning`]. Raise the severity
+	/// when lints are treated as errors, as with
+	/// [`Config::lint_errors`](crate::analysis::Config::lint_errors).
+	#[cfg(feature = "analysis")]
+	pub fn from_lint(warning: &LintWarning) -> Self {
+		Self {
+			severity: Severity::Warning,
+			kind: DiagnosticKind::Lint,
+			code: warning.code.name().unwrap_or("unknown"),
+			message: warning.message.clone(),
+			span: warning.span,
+			secondary: None,
+			suggestion: None
+		}
+	}
+
+	/// Renders the diagnostic along with the lines of `source` it points at,
+	/// underlining the primary span with `^` and the secondary span with `-`:
+	///
+	/// ```text
+	/// error[unexpected-token]: Expected identifier when parsing expression, got ')'
+	///  --> 1:7
+	///   |
+	/// 1 | print()) + 1
+	///   |        ^
+	/// ```
+	pub fn render(&self, source: &str) -> String {
+		let secondary: Vec<(Span, &str)> = self.secondary.iter().map(|label| (label.span, label.message.as_str())).collect();
+		let snippet = SourceMap::new(source).render_snippet(self.span, None, &secondary);
+		let mut out = format!("{}[{}]: {}\n{}", self.severity, self.code, self.message, snippet);
+
+		if let Some(suggestion) = &self.suggestion {
+			let _ = writeln!(out, "help: {}", suggestion);
+		}
+
+		out
+	}
+}
+
+impl CompileError {
+	/// Returns every diagnostic produced while compiling. Parse errors are all
+	/// reported; compilation stops at the first compile error.
+	pub fn diagnostics(&self) -> Vec<Diagnostic> {
+		match self {
+			Self::Parse(errors) => errors.iter().map(|error| Diagnostic::from_error(DiagnosticKind::Parse, error)).collect(),
+			Self::Compile(error) => vec![Diagnostic::from_error(DiagnosticKind::Compile, error)]
+		}
+	}
+
+	/// Renders every diagnostic against `source`, separated by blank lines.
+	pub fn render(&self, source: &str) -> String {
+		self.diagnostics().iter().map(|diagnostic| diagnostic.render(source)).collect::<Vec<_>>().join("\n")
+	}
+}
@@ -13,11 +13,19 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
-use std::fmt::{Display, Formatter};
+use luau_sys::glue::{gluau_Buffer, gluau_compile, gluau_compile_sneakily, gluau_compile_with_output, gluau_CompileOpts, gluau_CompileResult, gluau_CompileResultType, gluau_CompileSuccess, gluau_OptionalBuffer, gluau_Optionality, gluau_OutputOpts};
 
-use luau_sys::glue::{gluau_Buffer, gluau_compile, gluau_compile_sneakily, gluau_CompileOpts, gluau_CompileResultType, gluau_Error};
+use crate::ast::{error_from_gluau, suggest_confusables, ParseOptions};
+use crate::compiler::bytecode::{Bytecode, BytecodeError, DebugInfo};
+use crate::fvalue;
 
-use crate::ast::{ParseOptions, Span};
+pub use crate::ast::{Error, ErrorCode, Label};
+pub use diagnostic::{Diagnostic, DiagnosticKind, Severity};
+pub use batch::{Batch, BatchOutput};
+
+pub mod bytecode;
+pub mod batch;
+pub mod diagnostic;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum OptimizationLevel {
@@ -52,6 +60,10 @@ pub enum CoverageLevel {
 #[derive(Copy, Clone, Debug)]
 pub struct CompileOptions(gluau_CompileOpts);
 
+// SAFETY: the pointers inside are never set to anything other than null
+unsafe impl Send for CompileOptions {}
+unsafe impl Sync for CompileOptions {}
+
 impl Default for CompileOptions {
 	fn default() -> Self {
 		Self(gluau_CompileOpts {
@@ -129,29 +141,37 @@ impl CompileOptions {
 	}
 }
 
-#[derive(Clone, Eq, PartialEq, Debug)]
-pub struct Error {
-	pub message: String,
-	pub span: Span
+/// Selects which human-readable dumps to produce alongside the bytecode.
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct CompileOutput {
+	/// Produce a textual disassembly of the bytecode, including local names.
+	pub text: bool,
+	/// Produce the source code annotated with compiler remarks, such as
+	/// inlining and loop unrolling decisions. Most remarks are only emitted
+	/// with [`OptimizationLevel::Full`].
+	pub remarks: bool,
+	/// Interleave the source lines with the textual disassembly. This implies
+	/// [`Self::text`], since the annotations are only part of that dump.
+	pub source_annotations: bool
 }
 
-impl Display for Error {
-	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-		Display::fmt(&self.span, f)?;
-		f.write_str(": ")?;
-		Display::fmt(&self.message, f)
-	}
+/// The result of compiling with [`CompileOutput`]. Each dump is only present if
+/// it was requested.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct CompiledOutput {
+	pub function: CompiledFunction,
+	pub text: Option<String>,
+	pub remarks: Option<String>
 }
 
-impl std::error::Error for Error {}
-
 #[derive(Clone, Eq, PartialEq, Debug, thiserror::Error)]
 pub enum CompileError {
 	#[error("parse error")]
 	Parse(Vec<Error>),
 
+	// boxed since errors are large, and this is returned from every compile
 	#[error("{0}")]
-	Compile(Error)
+	Compile(Box<Error>)
 }
 
 #[derive(Clone, Eq, PartialEq, Debug)]
@@ -163,36 +183,44 @@ impl AsRef<[u8]> for CompiledFunction {
 	}
 }
 
-fn error_from_gluau(gluau: gluau_Error) -> Error {
-	unsafe {
-		Error {
-			// SAFETY: this is fine
-			message: String::from_raw_parts(gluau.message.data as _, gluau.message.len as _, gluau.message.len as _),
-			span: Span::new(
-				gluau.span.start_line as _,
-				gluau.span.start_column as _,
-				gluau.span.end_line as _,
-				gluau.span.end_column as _
-			)
-		}
+impl CompiledFunction {
+	/// Wraps existing bytecode, for example bytecode that was previously
+	/// compiled and stored. The bytecode is not validated until it is loaded.
+	pub fn from_bytecode(bytecode: Vec<u8>) -> Self {
+		Self(bytecode)
 	}
-}
 
-pub(crate) fn compile(source: &str, compile_opts: &CompileOptions, parse_opts: &ParseOptions) -> Result<CompiledFunction, CompileError> {
-	let source = gluau_Buffer {
-		data: source.as_ptr() as _,
-		len: source.len() as _
-	};
+	pub fn into_bytecode(self) -> Vec<u8> {
+		self.0
+	}
 
-	// SAFETY: C++ exceptions are caught by the C++ glue, and never unwind into Rust
-	let result = unsafe { gluau_compile(source, compile_opts.0, parse_opts.0) };
+	/// Returns equivalent bytecode without line information or local and
+	/// upvalue names. Function names are kept. Use [`Self::debug_info`] first to
+	/// keep the removed information around for symbolicating tracebacks.
+	///
+	/// Bytecode that encodes a compile error is returned unchanged.
+	pub fn strip_debug_info(&self) -> Result<Self, BytecodeError> {
+		match Bytecode::parse(&self.0) {
+			Ok(mut bytecode) => {
+				bytecode.strip_debug_info();
+				Ok(Self(bytecode.to_bytes()))
+			}
+
+			Err(BytecodeError::CompileError(_)) => Ok(self.clone()),
+			Err(error) => Err(error)
+		}
+	}
+
+	/// Extracts the debug information contained in the bytecode, indexed by
+	/// function id. This can be serialized with [`DebugInfo::to_bytes`].
+	pub fn debug_info(&self) -> Result<DebugInfo, BytecodeError> {
+		Bytecode::parse(&self.0).map(|bytecode| DebugInfo::from_bytecode(&bytecode))
+	}
+}
 
-	match result.type_ {
-		gluau_CompileResultType::SUCCESS => Ok(CompiledFunction(unsafe {
-			let bytecode = result.data.success.bytecode;
-			// SAFETY: this is fine
-			Vec::from_raw_parts(bytecode.data as _, bytecode.len as _, bytecode.len as _)
-		})),
+fn result_from_gluau(result: gluau_CompileResult, source: &str) -> Result<gluau_CompileSuccess, CompileError> {
+	let result = match result.type_ {
+		gluau_CompileResultType::SUCCESS => Ok(unsafe { result.data.success }),
 
 		gluau_CompileResultType::PARSE_FAILURE => Err(unsafe {
 			let failure = result.data.parse_failure;
@@ -204,9 +232,82 @@ pub(crate) fn compile(source: &str, compile_opts: &CompileOptions, parse_opts: &
 		}),
 
 		gluau_CompileResultType::COMPILE_FAILURE => Err(unsafe {
-			CompileError::Compile(error_from_gluau(result.data.compile_failure))
+			CompileError::Compile(Box::new(error_from_gluau(result.data.compile_failure)))
 		})
+	};
+
+	result.map_err(|mut error| {
+		if let CompileError::Parse(errors) = &mut error {
+			suggest_confusables(errors, source);
+		}
+
+		error
+	})
+}
+
+fn bytecode_from_gluau(bytecode: gluau_Buffer) -> CompiledFunction {
+	CompiledFunction(unsafe {
+		// SAFETY: this is fine
+		Vec::from_raw_parts(bytecode.data as _, bytecode.len as _, bytecode.len as _)
+	})
+}
+
+fn dump_from_gluau(dump: gluau_OptionalBuffer) -> Option<String> {
+	if dump.presence == gluau_Optionality::None {
+		return None
+	}
+
+	// an empty dump may not have been allocated at all
+	if dump.value.data.is_null() {
+		return Some(String::new())
 	}
+
+	// SAFETY: the buffer is non-null, so it was allocated by the glue
+	let bytes = unsafe { Vec::from_raw_parts(dump.value.data as _, dump.value.len as _, dump.value.len as _) };
+
+	// dumps can contain string constants, which are not necessarily UTF-8
+	Some(String::from_utf8(bytes).unwrap_or_else(|error| String::from_utf8_lossy(error.as_bytes()).into_owned()))
+}
+
+pub(crate) fn compile(source: &str, compile_opts: &CompileOptions, parse_opts: &ParseOptions) -> Result<CompiledFunction, CompileError> {
+	let _flags = fvalue::read_lock();
+	compile_unlocked(source, compile_opts, parse_opts)
+}
+
+/// Compiles without locking the FValues. The caller must hold a read lock.
+fn compile_unlocked(source: &str, compile_opts: &CompileOptions, parse_opts: &ParseOptions) -> Result<CompiledFunction, CompileError> {
+	let buffer = gluau_Buffer {
+		data: source.as_ptr() as _,
+		len: source.len() as _
+	};
+
+	// SAFETY: C++ exceptions are caught by the C++ glue, and never unwind into Rust
+	let result = unsafe { gluau_compile(buffer, compile_opts.0, parse_opts.0) };
+	result_from_gluau(result, source).map(|success| bytecode_from_gluau(success.bytecode))
+}
+
+pub(crate) fn compile_with_output(source: &str, compile_opts: &CompileOptions, parse_opts: &ParseOptions, output: CompileOutput) -> Result<CompiledOutput, CompileError> {
+	let buffer = gluau_Buffer {
+		data: source.as_ptr() as _,
+		len: source.len() as _
+	};
+
+	let output_opts = gluau_OutputOpts {
+		text: output.text,
+		remarks: output.remarks,
+		sourceAnnotations: output.source_annotations
+	};
+
+	let _flags = fvalue::read_lock();
+
+	// SAFETY: C++ exceptions are caught by the C++ glue, and never unwind into Rust
+	let result = unsafe { gluau_compile_with_output(buffer, compile_opts.0, parse_opts.0, output_opts) };
+
+	result_from_gluau(result, source).map(|success| CompiledOutput {
+		function: bytecode_from_gluau(success.bytecode),
+		text: dump_from_gluau(success.text),
+		remarks: dump_from_gluau(success.remarks)
+	})
 }
 
 pub(crate) fn compile_sneakily(source: &str, compile_opts: &CompileOptions, parse_opts: &ParseOptions) -> CompiledFunction {
@@ -215,6 +316,8 @@ pub(crate) fn compile_sneakily(source: &str, compile_opts: &CompileOptions, pars
 		len: source.len() as _
 	};
 
+	let _flags = fvalue::read_lock();
+
 	CompiledFunction(unsafe {
 		// SAFETY: this method cannot throw
 		let buffer = gluau_compile_sneakily(source, compile_opts.0, parse_opts.0);
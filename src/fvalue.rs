@@ -14,9 +14,43 @@
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
 use std::marker::PhantomData;
+use std::os::raw::c_int;
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 
 use __sealed::FValueType;
 
+/// Guards the values of all FValues. Luau reads them without synchronization,
+/// so anything that may read them (such as the compiler) must hold a read lock
+/// while anything that writes them must hold the write lock.
+static LOCK: RwLock<()> = RwLock::new(());
+
+pub(crate) fn read_lock() -> RwLockReadGuard<'static, ()> {
+	LOCK.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub(crate) fn write_lock() -> RwLockWriteGuard<'static, ()> {
+	LOCK.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Serializes changes to FValues, so that one can't restore a value while
+/// another change is still relying on it. Unlike [`LOCK`], this is held for
+/// as long as the changes are.
+static OVERRIDES: Mutex<()> = Mutex::new(());
+
+/// Exclusive permission to change FValues, which is needed by [`FValue::set`],
+/// [`FValue::scoped_set`], [`apply_from_str`] and [`restore`]. Only one can
+/// exist at a time, so tests that change FValues don't interfere with each
+/// other even when they run in parallel.
+#[must_use = "other overrides can start as soon as this is dropped"]
+pub struct Exclusive {
+	_guard: MutexGuard<'static, ()>
+}
+
+/// Waits until no other [`Exclusive`] exists, then returns one.
+pub fn exclusive() -> Exclusive {
+	Exclusive { _guard: OVERRIDES.lock().unwrap_or_else(PoisonError::into_inner) }
+}
+
 mod __sealed {
 	use std::ffi::c_void;
 	use std::os::raw::c_int;
@@ -148,10 +182,140 @@ impl<T: FValueType> FValue<T> {
 	}
 
 	pub fn value(&self) -> T {
+		let _lock = read_lock();
+		self.value_unlocked()
+	}
+
+	/// Reads the value without locking. The caller must hold a lock.
+	pub(crate) fn value_unlocked(&self) -> T {
 		T::value(self.inner)
 	}
 
-	pub fn set(&mut self, value: T) {
+	pub fn set(&mut self, _exclusive: &Exclusive, value: T) {
+		let _lock = write_lock();
+		value.set(self.inner);
+	}
+
+	/// Sets the value until the returned guard is dropped, which restores the
+	/// value from before. Guards for the same FValue should be dropped in the
+	/// reverse order that they were created.
+	pub fn scoped_set<'a>(&mut self, _exclusive: &'a Exclusive, value: T) -> FValueGuard<'a, T> {
+		let _lock = write_lock();
+		let old = T::value(self.inner);
 		value.set(self.inner);
+		FValueGuard { fvalue: self.clone(), old, exclusive: PhantomData }
+	}
+}
+
+/// Restores an FValue to its previous value when dropped. See
+/// [`FValue::scoped_set`].
+#[must_use = "the old value is restored as soon as the guard is dropped"]
+pub struct FValueGuard<'a, T: FValueType> {
+	fvalue: FValue<T>,
+	old: T,
+	exclusive: PhantomData<&'a Exclusive>
+}
+
+impl<T: FValueType> FValueGuard<'_, T> {
+	/// The value that will be restored.
+	pub fn old(&self) -> T {
+		self.old
+	}
+}
+
+impl<T: FValueType> Drop for FValueGuard<'_, T> {
+	fn drop(&mut self) {
+		let _lock = write_lock();
+		self.old.set(self.fvalue.inner);
+	}
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, thiserror::Error)]
+pub enum ApplyError {
+	#[error("expected name=value, got {0:?}")]
+	Syntax(String),
+
+	#[error("unknown FValue {0}")]
+	Unknown(String),
+
+	#[error("invalid value {value:?} for FValue {name}")]
+	InvalidValue { name: String, value: String }
+}
+
+/// Sets many FValues at once from a comma separated list of assignments, like
+/// `LuauFoo=true,LuauBar=3`. FFlags take `true` or `false`, and FInts take
+/// integers. Nothing is set unless every assignment is valid.
+pub fn apply_from_str(_exclusive: &Exclusive, assignments: &str) -> Result<(), ApplyError> {
+	enum Assignment {
+		FFlag(FValue<bool>, bool),
+		FInt(FValue<c_int>, c_int)
+	}
+
+	let mut parsed = Vec::new();
+
+	for assignment in assignments.split(',').map(str::trim).filter(|assignment| !assignment.is_empty()) {
+		let (name, value) = assignment.split_once('=').ok_or_else(|| ApplyError::Syntax(assignment.to_owned()))?;
+		let (name, value) = (name.trim(), value.trim());
+		let invalid = || ApplyError::InvalidValue { name: name.to_owned(), value: value.to_owned() };
+
+		if let Some(fflag) = FValue::<bool>::find(name) {
+			parsed.push(Assignment::FFlag(fflag, value.parse().map_err(|_| invalid())?));
+		} else if let Some(fint) = FValue::<c_int>::find(name) {
+			parsed.push(Assignment::FInt(fint, value.parse().map_err(|_| invalid())?));
+		} else {
+			return Err(ApplyError::Unknown(name.to_owned()))
+		}
+	}
+
+	let _lock = write_lock();
+
+	for assignment in parsed {
+		match assignment {
+			Assignment::FFlag(fflag, value) => value.set(fflag.inner),
+			Assignment::FInt(fint, value) => value.set(fint.inner)
+		}
+	}
+
+	Ok(())
+}
+
+/// The values of every FValue at some point in time, which can be restored
+/// later with [`restore`].
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+	fflags: Vec<(FValue<bool>, bool)>,
+	fints: Vec<(FValue<c_int>, c_int)>
+}
+
+impl Snapshot {
+	pub fn fflags(&self) -> impl Iterator<Item = (&str, bool)> {
+		self.fflags.iter().map(|(fflag, value)| (fflag.name(), *value))
+	}
+
+	pub fn fints(&self) -> impl Iterator<Item = (&str, c_int)> {
+		self.fints.iter().map(|(fint, value)| (fint.name(), *value))
+	}
+}
+
+/// Records the value of every FValue.
+pub fn snapshot() -> Snapshot {
+	let _lock = read_lock();
+
+	Snapshot {
+		fflags: FValue::<bool>::list().into_iter().map(|fflag| { let value = fflag.value_unlocked(); (fflag, value) }).collect(),
+		fints: FValue::<c_int>::list().into_iter().map(|fint| { let value = fint.value_unlocked(); (fint, value) }).collect()
+	}
+}
+
+/// Sets every FValue back to its value in a snapshot.
+pub fn restore(_exclusive: &Exclusive, snapshot: &Snapshot) {
+	let _lock = write_lock();
+
+	for (fflag, value) in &snapshot.fflags {
+		value.set(fflag.inner);
+	}
+
+	for (fint, value) in &snapshot.fints {
+		value.set(fint.inner);
 	}
 }
@@ -0,0 +1,180 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::{Display, Formatter};
+
+/// Luau type signatures for the globals, functions and userdata classes that a
+/// host exposes, which can be exported as a definitions file and loaded into
+/// `analysis::Frontend::load_definitions`, so that scripts are checked against
+/// the API they actually run with.
+///
+/// Types and signatures are written in Luau syntax, and are not validated
+/// until the definitions are loaded.
+#[derive(Clone, Eq, PartialEq, Debug, Default)]
+pub struct Definitions {
+	classes: Vec<ClassDefinition>,
+	globals: Vec<(String, String)>,
+	functions: Vec<(String, String)>
+}
+
+impl Definitions {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Declares a global variable of the given type.
+	pub fn global(&mut self, name: impl Into<String>, ty: impl Into<String>) -> &mut Self {
+		self.globals.push((name.into(), ty.into()));
+		self
+	}
+
+	/// Declares a global function. The signature is everything after the name,
+	/// like `(name: string, count: number?): boolean`.
+	pub fn function(&mut self, name: impl Into<String>, signature: impl Into<String>) -> &mut Self {
+		self.functions.push((name.into(), signature.into()));
+		self
+	}
+
+	/// Declares a userdata class, returning it so that its properties and
+	/// methods can be declared. Classes are exported before anything else, so
+	/// that globals and functions can refer to them.
+	pub fn class(&mut self, name: impl Into<String>) -> &mut ClassDefinition {
+		self.classes.push(ClassDefinition {
+			name: name.into(),
+			extends: None,
+			properties: Vec::new(),
+			methods: Vec::new()
+		});
+
+		self.classes.last_mut().unwrap()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.classes.is_empty() && self.globals.is_empty() && self.functions.is_empty()
+	}
+
+	/// Returns the definitions file, in the syntax accepted by
+	/// `Frontend::load_definitions`.
+	pub fn export(&self) -> String {
+		self.to_string()
+	}
+}
+
+impl Display for Definitions {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		for class in &self.classes {
+			writeln!(f, "{}", class)?;
+		}
+
+		for (name, ty) in &self.globals {
+			writeln!(f, "declare {}: {}", name, ty)?;
+		}
+
+		for (name, signature) in &self.functions {
+			writeln!(f, "declare function {}{}", name, signature)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// A userdata class declared by [`Definitions::class`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct ClassDefinition {
+	name: String,
+	extends: Option<String>,
+	properties: Vec<(String, String)>,
+	methods: Vec<(String, String)>
+}
+
+impl ClassDefinition {
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Makes the class inherit the properties and methods of another class.
+	pub fn set_extends(&mut self, class: impl Into<String>) -> &mut Self {
+		self.extends = Some(class.into());
+		self
+	}
+
+	pub fn property(&mut self, name: impl Into<String>, ty: impl Into<String>) -> &mut Self {
+		self.properties.push((name.into(), ty.into()));
+		self
+	}
+
+	/// Declares a method. The signature does not include `self`, which is
+	/// added automatically, so `(amount: number): ()` declares a method called
+	/// like `object:method(1)`.
+	pub fn method(&mut self, name: impl Into<String>, signature: impl Into<String>) -> &mut Self {
+		self.methods.push((name.into(), signature.into()));
+		self
+	}
+}
+
+impl Display for ClassDefinition {
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "declare class {}", self.name)?;
+
+		if let Some(extends) = &self.extends {
+			write!(f, " extends {}", extends)?;
+		}
+
+		writeln!(f)?;
+
+		for (name, ty) in &self.properties {
+			writeln!(f, "\t{}: {}", name, ty)?;
+		}
+
+		for (name, signature) in &self.methods {
+			let (generics, signature) = split_generics(signature.trim_start());
+
+			// splice `self` in as the first parameter
+			match signature.strip_prefix('(') {
+				Some(rest) if rest.trim_start().starts_with(')') => writeln!(f, "\tfunction {}{}(self{}", name, generics, rest.trim_start())?,
+				Some(rest) => writeln!(f, "\tfunction {}{}(self, {}", name, generics, rest)?,
+				None => writeln!(f, "\tfunction {}{}(self){}", name, generics, signature)?
+			}
+		}
+
+		write!(f, "end")
+	}
+}
+
+/// Splits the generic parameters off the front of a signature, so that
+/// `<T>(value: T): T` becomes `<T>` and `(value: T): T`.
+fn split_generics(signature: &str) -> (&str, &str) {
+	if !signature.starts_with('<') {
+		return ("", signature)
+	}
+
+	let mut depth = 0;
+
+	for (index, char) in signature.char_indices() {
+		match char {
+			'<' => depth += 1,
+			'>' => {
+				depth -= 1;
+
+				if depth == 0 {
+					return (&signature[..=index], signature[index + 1..].trim_start())
+				}
+			}
+			_ => {}
+		}
+	}
+
+	("", signature)
+}
@@ -13,6 +13,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <https://www.gnu.org/licenses/>.
 
+use std::cell::RefCell;
 use std::marker::PhantomData;
 use std::ptr::NonNull;
 
@@ -20,8 +21,9 @@ use data::{Data, GlobalData, ThreadData};
 use luau_sys::luau::lua_State;
 use value::thread::Thread;
 
-use crate::compiler::{compile, compile_sneakily, CompiledFunction, CompileError};
+use crate::compiler::{compile, compile_sneakily, compile_with_output, CompiledFunction, CompiledOutput, CompileError, CompileOptions, CompileOutput};
 use crate::vm::builder::LuauBuildData;
+use crate::vm::definitions::Definitions;
 use crate::vm::error::LResult;
 use crate::vm::raw::RawGlobal;
 use crate::vm::raw::thread::RawThread;
@@ -32,10 +34,12 @@ pub mod raw;
 pub mod value;
 pub mod data;
 pub mod builder;
+pub mod definitions;
 
 #[derive(Debug)]
 pub struct Luau<D: GlobalData> {
 	global: NonNull<RawGlobal>,
+	definitions: RefCell<Definitions>,
 	phantom: PhantomData<NonNull<D>>
 }
 
@@ -65,7 +69,7 @@ impl<D: GlobalData> Luau<D> {
 				}
 			}
 
-			Some(Self { global, phantom: PhantomData })
+			Some(Self { global, definitions: RefCell::new(Definitions::new()), phantom: PhantomData })
 		}
 	}
 }
@@ -80,6 +84,14 @@ impl Luau<()> {
 		compile(source, &Default::default(), &Default::default())
 	}
 
+	/// Compiles Luau source code with the given options, additionally
+	/// producing the human-readable dumps selected by `output`. This is useful
+	/// for reviewing what the optimizer did to a piece of code.
+	#[cfg(feature = "compiler")]
+	pub fn compile_with_output(source: &str, compile_opts: &CompileOptions, output: CompileOutput) -> Result<CompiledOutput, CompileError> {
+		compile_with_output(source, compile_opts, &Default::default(), output)
+	}
+
 	/// Compiles Luau source code. The compiled function can then be loaded into
 	/// a thread and executed.
 	///
@@ -100,4 +112,24 @@ impl<D: GlobalData> Luau<D> {
 	pub fn new_thread(&self) -> LResult<LuauValue<Thread>> {
 		unsafe { self.main_thread() }.new_thread()
 	}
+
+	/// Declares type signatures for what the host has exposed to scripts. Do
+	/// this next to where the values are set up, so that the definitions stay
+	/// in sync with the runtime.
+	///
+	/// The VM has no API for registering native functions or userdata yet, so
+	/// signatures can't be attached at registration and have to be declared
+	/// here separately.
+	///
+	/// The definitions are borrowed for the duration of the closure, so it
+	/// must not call this or [`Self::export_definitions`] itself.
+	pub fn definitions<T>(&self, declare: impl FnOnce(&mut Definitions) -> T) -> T {
+		declare(&mut self.definitions.borrow_mut())
+	}
+
+	/// Exports the declared [`Definitions`] as a definitions file, to be loaded
+	/// into the type checker.
+	pub fn export_definitions(&self) -> String {
+		self.definitions.borrow().export()
+	}
 }
@@ -0,0 +1,95 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(all(feature = "compiler", feature = "vm"))]
+
+mod common;
+
+use luau::compiler::{Batch, CompileOptions, CoverageLevel, DebugLevel, OptimizationLevel};
+use luau::fvalue::{self, FValue};
+use luau::vm::Luau;
+
+use common::temp_dir;
+
+fn batch(compile_opts: CompileOptions, dir: &std::path::Path) -> Batch {
+	let mut batch = Batch::new(compile_opts, Default::default());
+	batch.set_cache_dir(dir).add("a", "return 1").add("b", "local x = ... return x + 1").add("c", "return (");
+	batch
+}
+
+fn entries(dir: &std::path::Path) -> usize {
+	std::fs::read_dir(dir).unwrap().count()
+}
+
+#[test]
+fn parallel() {
+	// `cache` flips an FFlag, which could change the bytecode between the
+	// batch and the separate compiles
+	let _exclusive = fvalue::exclusive();
+	let mut batch = Batch::new(Default::default(), Default::default());
+
+	for index in 0..32 {
+		batch.add(format!("{}", index), format!("return {}", index));
+	}
+
+	let outputs = batch.compile();
+	assert_eq!(outputs.len(), 32);
+
+	for (index, output) in outputs.into_iter().enumerate() {
+		assert_eq!(output.name, format!("{}", index));
+		assert_eq!(output.result, Luau::compile(&format!("return {}", index)));
+		assert!(!output.cached);
+	}
+}
+
+// FValues are global, and flipping one changes every cache key, so everything
+// that relies on the cache is tested in one test
+#[test]
+fn cache() {
+	let dir = temp_dir("batch-cache");
+	let opts = CompileOptions::default();
+
+	let first = batch(opts, &dir).compile();
+	assert!(first.iter().all(|output| !output.cached));
+	assert!(first[2].result.is_err());
+
+	// errors are not cached
+	assert_eq!(entries(&dir), 2);
+
+	let second = batch(opts, &dir).compile();
+	assert!(second[0].cached && second[1].cached && !second[2].cached);
+	assert_eq!(second[0].result, first[0].result);
+	assert_eq!(second[1].result, first[1].result);
+	assert_eq!(second[0].result, Luau::compile("return 1"));
+
+	// changing an option changes the key
+	let full = CompileOptions::new(OptimizationLevel::Basic, DebugLevel::Full, CoverageLevel::None);
+	assert!(batch(full, &dir).compile().iter().all(|output| !output.cached));
+	assert_eq!(entries(&dir), 4);
+
+	// and so does changing an FFlag
+	Luau::compile("").unwrap();
+	let mut fflag = FValue::<bool>::list().into_iter().next().expect("no FFlags");
+	let default = fflag.value();
+
+	{
+		let exclusive = fvalue::exclusive();
+		let _guard = fflag.scoped_set(&exclusive, !default);
+		assert!(batch(opts, &dir).compile().iter().all(|output| !output.cached));
+		assert_eq!(entries(&dir), 6);
+	}
+
+	assert!(batch(opts, &dir).compile()[0].cached);
+}
@@ -0,0 +1,111 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(all(feature = "compiler", feature = "vm"))]
+
+use luau::compiler::{CompileOptions, CompiledFunction, CoverageLevel, DebugLevel, OptimizationLevel};
+use luau::compiler::bytecode::{Bytecode, BytecodeError, DebugInfo};
+use luau::vm::Luau;
+
+const SOURCE: &str = r#"
+local function add(first: number, second: number): number
+	local sum = first + second
+	return sum
+end
+
+local greeting = "hello"
+
+local function greet(name: string)
+	return greeting .. ", " .. name
+end
+
+for index = 1, 3 do
+	print(greet(tostring(add(index, 1))))
+end
+
+return { add = add }
+"#;
+
+fn compile(debug_level: DebugLevel) -> CompiledFunction {
+	let opts = CompileOptions::new(OptimizationLevel::Basic, debug_level, CoverageLevel::None);
+	Luau::compile_with_output(SOURCE, &opts, Default::default()).expect("failed to compile").function
+}
+
+fn strings(bytecode: &Bytecode) -> Vec<String> {
+	bytecode.strings.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn round_trip() {
+	for debug_level in [DebugLevel::None, DebugLevel::Traceback, DebugLevel::Full] {
+		let function = compile(debug_level);
+		let bytecode = Bytecode::parse(function.as_ref()).unwrap();
+		assert_eq!(bytecode.to_bytes(), function.as_ref(), "round trip with {:?}", debug_level);
+	}
+}
+
+#[test]
+fn round_trip_type_info() {
+	let function = compile(DebugLevel::Full);
+	let bytecode = Bytecode::parse(function.as_ref()).unwrap();
+
+	// annotated parameters are encoded as type information
+	assert!(bytecode.protos.iter().any(|proto| !proto.types.is_empty()));
+	assert_eq!(bytecode.to_bytes(), function.as_ref());
+}
+
+#[test]
+fn strip_debug_info() {
+	let function = compile(DebugLevel::Full);
+	let before = Bytecode::parse(function.as_ref()).unwrap();
+
+	assert!(before.protos.iter().any(|proto| proto.line_info.is_some()));
+	assert!(before.protos.iter().any(|proto| proto.debug_info.is_some()));
+	assert!(strings(&before).contains(&"sum".to_owned()));
+
+	let stripped = function.strip_debug_info().unwrap();
+	let after = Bytecode::parse(stripped.as_ref()).unwrap();
+
+	assert!(after.protos.iter().all(|proto| proto.line_info.is_none() && proto.debug_info.is_none()));
+	assert!(!strings(&after).contains(&"sum".to_owned()));
+	assert!(strings(&after).contains(&"greet".to_owned()));
+	assert_eq!(after.protos.len(), before.protos.len());
+
+	let vm = Luau::builder()
+		.no_data().expect("failed to create Luau VM")
+		.all_libs()
+		.no_setup();
+
+	vm.new_thread().unwrap().new_closure(stripped).expect("stripped bytecode failed to load");
+}
+
+#[test]
+fn debug_info_round_trip() {
+	let function = compile(DebugLevel::Full);
+	let debug_info = function.debug_info().unwrap();
+
+	assert!(debug_info.functions.iter().flat_map(|function| &function.locals).any(|local| local.name.as_ref().map_or(false, |name| name == "sum")));
+	assert_eq!(DebugInfo::from_bytes(&debug_info.to_bytes()), Ok(debug_info));
+}
+
+#[test]
+fn malformed_debug_info() {
+	assert_eq!(DebugInfo::from_bytes(&[2, 0]), Err(BytecodeError::UnsupportedDebugInfoVersion(2)));
+	assert_eq!(DebugInfo::from_bytes(&[1, 0]), Ok(DebugInfo::default()));
+
+	// 2^32 does not fit in the fifth byte of a varint
+	assert_eq!(DebugInfo::from_bytes(&[1, 0x80, 0x80, 0x80, 0x80, 0x10]), Err(BytecodeError::MalformedVarInt));
+	assert_eq!(DebugInfo::from_bytes(&[1, 0x80, 0x80, 0x80, 0x80, 0x80, 0x00]), Err(BytecodeError::MalformedVarInt));
+}
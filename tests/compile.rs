@@ -0,0 +1,101 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(all(feature = "compiler", feature = "vm"))]
+
+use luau::compiler::{CompileOptions, CompileOutput, CoverageLevel, DebugLevel, OptimizationLevel};
+use luau::vm::Luau;
+
+const SOURCE: &str = r#"
+local function double(value)
+	return value * 2
+end
+
+local result = 0
+
+for index = 1, 4 do
+	result += double(index)
+end
+
+return result
+"#;
+
+fn full() -> CompileOptions {
+	CompileOptions::new(OptimizationLevel::Full, DebugLevel::Full, CoverageLevel::None)
+}
+
+#[test]
+fn no_output() {
+	let output = Luau::compile_with_output(SOURCE, &CompileOptions::default(), CompileOutput::default()).unwrap();
+
+	assert_eq!(output.text, None);
+	assert_eq!(output.remarks, None);
+	assert_eq!(output.function, Luau::compile(SOURCE).unwrap());
+}
+
+#[test]
+fn text() {
+	let output = Luau::compile_with_output(SOURCE, &full(), CompileOutput { text: true, ..Default::default() }).unwrap();
+	let text = output.text.expect("text was requested");
+
+	assert!(text.contains("RETURN"), "{}", text);
+	assert!(text.contains("double"), "{}", text);
+	assert!(!text.contains("local function double"), "source was not requested: {}", text);
+	assert_eq!(output.remarks, None);
+}
+
+#[test]
+fn source_annotations() {
+	let output = Luau::compile_with_output(SOURCE, &full(), CompileOutput { text: true, source_annotations: true, ..Default::default() }).unwrap();
+	let text = output.text.expect("text was requested");
+
+	assert!(text.contains("local function double"), "{}", text);
+	assert!(text.contains("RETURN"), "{}", text);
+}
+
+#[test]
+fn source_annotations_only() {
+	// annotations are part of the text dump, so they produce it on their own
+	let output = Luau::compile_with_output(SOURCE, &full(), CompileOutput { source_annotations: true, ..Default::default() }).unwrap();
+	let text = output.text.expect("text is implied by source annotations");
+
+	assert!(text.contains("local function double"), "{}", text);
+	assert!(text.contains("RETURN"), "{}", text);
+	assert_eq!(output.remarks, None);
+}
+
+#[test]
+fn remarks() {
+	let output = Luau::compile_with_output(SOURCE, &full(), CompileOutput { remarks: true, ..Default::default() }).unwrap();
+	let remarks = output.remarks.expect("remarks were requested");
+
+	assert!(remarks.contains("remark"), "{}", remarks);
+	assert!(remarks.contains("local function double"), "{}", remarks);
+	assert_eq!(output.text, None);
+}
+
+#[test]
+fn empty_output() {
+	// an empty dump is still present when it was requested
+	let output = Luau::compile_with_output("", &full(), CompileOutput { text: true, remarks: true, ..Default::default() }).unwrap();
+
+	assert!(output.text.is_some());
+	assert!(output.remarks.is_some());
+}
+
+#[test]
+fn parse_error() {
+	assert!(Luau::compile_with_output("local", &full(), CompileOutput { text: true, ..Default::default() }).is_err());
+}
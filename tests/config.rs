@@ -0,0 +1,131 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "analysis")]
+
+mod common;
+
+use std::path::Path;
+
+use luau::analysis::{Config, FileResolver, Frontend, FsResolver, LintCode, RequireExpr};
+use luau::ast::Mode;
+
+use common::temp_dir;
+
+#[test]
+fn merge() {
+	let root = temp_dir("config-merge");
+	std::fs::create_dir_all(root.join("src/shared")).unwrap();
+	std::fs::write(root.join("src/shared/util.luau"), "return 1").unwrap();
+
+	std::fs::write(root.join(".luaurc"), r#"{
+		// comments and trailing commas are allowed
+		"languageMode": "strict",
+		"lint": { "*": false, "LocalUnused": true },
+		"globals": ["game"],
+		"aliases": { "Shared": "./src/shared" },
+	}"#).unwrap();
+
+	std::fs::write(root.join("src/.luaurc"), r#"{ "languageMode": "nonstrict", "lint": { "LocalShadow": true }, "globals": ["script"] }"#).unwrap();
+
+	let config = Config::load_for(root.join("src/main.luau")).unwrap();
+
+	assert_eq!(config.mode, Mode::Nonstrict);
+	assert!(config.lint.is_enabled(LintCode::LocalUnused));
+	assert!(config.lint.is_enabled(LintCode::LocalShadow));
+	assert!(!config.lint.is_enabled(LintCode::UnknownGlobal));
+	assert_eq!(config.globals, ["game", "script"]);
+
+	let mut resolver = FsResolver::new(&root);
+	resolver.set_aliases(config.aliases);
+
+	assert_eq!(resolver.resolve_module("src/main.luau", RequireExpr::String("@shared/util")), Some("src/shared/util.luau".to_owned()));
+	assert_eq!(resolver.resolve_module("src/main.luau", RequireExpr::String("@missing/util")), None);
+
+	// the top-level config alone
+	assert_eq!(Config::load_for(&root).unwrap().mode, Mode::Strict);
+
+	let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn relative_root() {
+	// cargo runs tests from the package root, so this stays relative to it
+	let root = Path::new("target").join(format!("luau-rs-config-relative-{}", std::process::id()));
+	let _ = std::fs::remove_dir_all(&root);
+	std::fs::create_dir_all(root.join("src/shared")).unwrap();
+	std::fs::write(root.join("src/shared/util.luau"), "return 1").unwrap();
+	std::fs::write(root.join("src/main.luau"), "local util: number = require(\"@shared/util\")").unwrap();
+	std::fs::write(root.join(".luaurc"), r#"{ "aliases": { "shared": "src/shared" } }"#).unwrap();
+
+	let config = Config::load_for(root.join("src/main.luau")).unwrap();
+	assert!(config.aliases["shared"].is_absolute());
+
+	let mut frontend = Frontend::with_resolver(FsResolver::new(&root), Mode::Strict);
+	frontend.set_config(&config);
+
+	assert_eq!(frontend.resolver().resolve_module("src/main.luau", RequireExpr::String("@shared/util")), Some("src/shared/util.luau".to_owned()));
+	assert!(frontend.check("src/main.luau").is_empty());
+
+	let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn errors() {
+	let mut config = Config::default();
+
+	assert!(config.apply(r#"{ "languageMode": "loose" }"#, "".as_ref()).is_err());
+	assert!(config.apply(r#"{ "lint": { "NotALint": true } }"#, "".as_ref()).is_err());
+	assert!(config.apply(r#"{ "unknown": 1 }"#, "".as_ref()).is_err());
+	assert!(config.apply(r#"{ "typeErrors": false"#, "".as_ref()).is_err());
+
+	assert!(config.apply(r#"{ "typeErrors": false }"#, "".as_ref()).is_ok());
+	assert!(!config.type_errors);
+}
+
+#[test]
+fn escapes() {
+	let mut config = Config::default();
+
+	config.apply(r#"{ "globals": ["tab\tquote\"slash\/", "\b\f", "é中", "😀"] }"#, "".as_ref()).unwrap();
+	assert_eq!(config.globals, ["tab\tquote\"slash/", "\u{8}\u{c}", "é中", "😀"]);
+
+	assert!(config.apply(r#"{ "globals": ["\u12"] }"#, "".as_ref()).is_err());
+	assert!(config.apply(r#"{ "globals": ["\ud83d"] }"#, "".as_ref()).is_err());
+	assert!(config.apply(r#"{ "globals": ["\ud83dA"] }"#, "".as_ref()).is_err());
+	assert!(config.apply(r#"{ "globals": ["\ude00"] }"#, "".as_ref()).is_err());
+	assert!(config.apply(r#"{ "globals": ["\x41"] }"#, "".as_ref()).is_err());
+}
+
+#[test]
+fn frontend() {
+	let mut config = Config::default();
+	config.apply(r#"{ "languageMode": "strict", "lint": { "*": false, "LocalUnused": true }, "lintErrors": true, "typeErrors": false }"#, "".as_ref()).unwrap();
+
+	let mut frontend = Frontend::new(Mode::Nonstrict);
+	frontend.set_config(&config);
+	frontend.set_source("main", "local x: number = \"one\"");
+
+	assert_eq!(frontend.mode(), Mode::Strict);
+	assert!(frontend.check("main").is_empty());
+	assert!(frontend.lint_errors());
+
+	let options = frontend.lint_options();
+	assert!(options.is_enabled(LintCode::LocalUnused));
+	assert!(!options.is_enabled(LintCode::LocalShadow));
+
+	let codes: Vec<LintCode> = frontend.lint("main", &options).into_iter().map(|warning| warning.code).collect();
+	assert_eq!(codes, [LintCode::LocalUnused]);
+}
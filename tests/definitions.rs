@@ -0,0 +1,123 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "vm")]
+
+use luau::vm::definitions::Definitions;
+use luau::vm::Luau;
+
+fn example() -> Definitions {
+	let mut definitions = Definitions::new();
+
+	definitions.class("Instance")
+		.property("Name", "string")
+		.method("Destroy", "()")
+		.method("Clone", "(): Instance");
+
+	definitions.class("Part")
+		.set_extends("Instance")
+		.property("Size", "number")
+		.method("Resize", "(size: number): ()")
+		.method("Get", "<T>(key: string, default: T): T")
+		.method("Pair", "<K, V...>(): (K, V...)");
+
+	definitions.global("workspace", "Instance");
+	definitions.function("spawn", "(name: string): Part");
+	definitions
+}
+
+#[test]
+fn export() {
+	assert!(Definitions::new().is_empty());
+	assert_eq!(Definitions::new().export(), "");
+
+	assert_eq!(example().export(), concat!(
+		"declare class Instance\n",
+		"\tName: string\n",
+		"\tfunction Destroy(self)\n",
+		"\tfunction Clone(self): Instance\n",
+		"end\n",
+		"declare class Part extends Instance\n",
+		"\tSize: number\n",
+		"\tfunction Resize(self, size: number): ()\n",
+		"\tfunction Get<T>(self, key: string, default: T): T\n",
+		"\tfunction Pair<K, V...>(self): (K, V...)\n",
+		"end\n",
+		"declare workspace: Instance\n",
+		"declare function spawn(name: string): Part\n"
+	));
+}
+
+#[test]
+fn luau() {
+	let vm = Luau::builder()
+		.no_data().expect("failed to create Luau VM")
+		.all_libs()
+		.no_setup();
+
+	let name = vm.definitions(|definitions| {
+		definitions.global("answer", "number");
+		definitions.class("Thing").name().to_owned()
+	});
+
+	assert_eq!(name, "Thing");
+	assert_eq!(vm.export_definitions(), "declare class Thing\nend\ndeclare answer: number\n");
+}
+
+#[cfg(feature = "analysis")]
+#[test]
+fn load() {
+	use luau::analysis::{Frontend, TypeErrorKind};
+	use luau::ast::Mode;
+
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.load_definitions("@host", &example().export()).unwrap();
+	frontend.set_source("main", r#"
+		local part = spawn("part")
+		part.Size = 2
+		part:Resize(part.Size)
+		local name: string = part:Get("name", "default")
+		local clone: Instance = part:Clone()
+		clone:Destroy()
+		workspace.Name = name
+		local wrong: number = part:Get("size", "small")
+		return wrong
+	"#);
+
+	let errors = frontend.check("main");
+	assert_eq!(errors.iter().map(|error| error.kind).collect::<Vec<_>>(), [TypeErrorKind::TypeMismatch], "{:?}", errors);
+	assert_eq!(errors[0].span.0.line, 8);
+}
+
+#[cfg(feature = "analysis")]
+#[test]
+fn export_and_load() {
+	use luau::analysis::Frontend;
+	use luau::ast::Mode;
+
+	let vm = Luau::builder()
+		.no_data().expect("failed to create Luau VM")
+		.all_libs()
+		.no_setup();
+
+	vm.definitions(|definitions| *definitions = example());
+
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.load_definitions("@host", &vm.export_definitions()).unwrap();
+	frontend.set_source("main", "local part = spawn(\"part\")\npart:Resize(part.Size)\nworkspace.Name = \"root\"\n");
+
+	let errors = frontend.check("main");
+	assert!(errors.is_empty(), "{:?}", errors);
+}
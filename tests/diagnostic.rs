@@ -0,0 +1,124 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(all(feature = "compiler", feature = "vm"))]
+
+use luau::ast::{Error, ErrorCode, Label, Span};
+use luau::compiler::{CompileError, Diagnostic, DiagnosticKind, Severity};
+use luau::vm::Luau;
+
+fn parse_errors(source: &str) -> Vec<Diagnostic> {
+	let Err(error @ CompileError::Parse(_)) = Luau::compile(source) else { panic!("expected a parse error") };
+	error.diagnostics()
+}
+
+#[test]
+fn from_error() {
+	let diagnostic = Diagnostic::from_error(DiagnosticKind::Parse, &Error {
+		message: "Expected 'end' (to close 'function' at line 1), got <eof>".to_owned(),
+		span: Span::new(1, 9, 1, 9),
+		code: ErrorCode::UnclosedBlock,
+		related: Some(Label { span: Span::new(0, 6, 0, 14), message: "to close 'function'".to_owned() }),
+		suggestion: None
+	});
+
+	assert_eq!(diagnostic.severity, Severity::Error);
+	assert_eq!(diagnostic.code, "unclosed-block");
+	assert_eq!(diagnostic.to_string(), "error[unclosed-block]: (1,9)..(1,9): Expected 'end' (to close 'function' at line 1), got <eof>");
+	assert_eq!(diagnostic.render("local function f()\n\treturn 1"), concat!(
+		"error[unclosed-block]: Expected 'end' (to close 'function' at line 1), got <eof>\n",
+		" --> 2:10\n",
+		"  |\n",
+		"1 | local function f()\n",
+		"  |       -------- to close 'function'\n",
+		"2 | \treturn 1\n",
+		"  | \t        ^\n"
+	));
+}
+
+#[test]
+fn unclosed_block() {
+	let diagnostics = parse_errors("local function f()\n\tif true then\n\tend\n\treturn 1");
+
+	assert_eq!(diagnostics[0].code, "unclosed-block");
+	assert_eq!(diagnostics[0].secondary, Some(Label {
+		span: Span::new(0, 6, 0, 14),
+		message: "to close 'function'".to_owned()
+	}));
+}
+
+#[test]
+fn unclosed_bracket() {
+	let diagnostics = parse_errors("print(1");
+
+	assert_eq!(diagnostics[0].code, "unclosed-block");
+	assert_eq!(diagnostics[0].secondary, Some(Label {
+		span: Span::new(0, 5, 0, 6),
+		message: "to close '('".to_owned()
+	}));
+
+	assert_eq!(diagnostics[0].render("print(1"), concat!(
+		"error[unclosed-block]: Expected ')' (to close '(' at column 6), got <eof>\n",
+		" --> 1:8\n",
+		"  |\n",
+		"1 | print(1\n",
+		"  |      - to close '('\n",
+		"  |        ^\n"
+	));
+}
+
+#[test]
+fn loops_close_once() {
+	// the `do` of a loop belongs to it, so a single `end` closes both
+	let diagnostics = parse_errors("while true do\nend\nfor i = 1, 2 do\n\tdo\n\tend\n\tlocal t = {");
+
+	let unclosed = diagnostics.iter().find(|diagnostic| diagnostic.code == "unclosed-block").expect("no unclosed block");
+	assert_eq!(unclosed.secondary, Some(Label {
+		span: Span::new(5, 11, 5, 12),
+		message: "to close '{'".to_owned()
+	}));
+}
+
+#[test]
+fn no_label() {
+	let diagnostics = parse_errors("print()) + 1");
+
+	assert_eq!(diagnostics[0].code, "unexpected-token");
+	assert_eq!(diagnostics[0].secondary, None);
+}
+
+#[test]
+fn compile_errors() {
+	let source = "local function f()\n\treturn (1 +\nend";
+	let Err(error @ CompileError::Parse(_)) = Luau::compile(source) else { panic!("expected a parse error") };
+	let diagnostics = error.diagnostics();
+
+	assert!(!diagnostics.is_empty());
+	assert!(diagnostics.iter().all(|diagnostic| diagnostic.kind == DiagnosticKind::Parse && diagnostic.severity == Severity::Error));
+	assert!(diagnostics.iter().all(|diagnostic| diagnostic.code != "unknown"));
+	assert_eq!(error.render(source), diagnostics.iter().map(|diagnostic| diagnostic.render(source)).collect::<Vec<_>>().join("\n"));
+}
+
+#[test]
+#[cfg(feature = "analysis")]
+fn from_lint() {
+	let warnings = luau::analysis::lint("local x = 1", &Default::default());
+	let diagnostic = Diagnostic::from_lint(&warnings[0]);
+
+	assert_eq!(diagnostic.severity, Severity::Warning);
+	assert_eq!(diagnostic.kind, DiagnosticKind::Lint);
+	assert_eq!(diagnostic.code, "LocalUnused");
+	assert!(diagnostic.to_string().starts_with("warning[LocalUnused]: "), "{}", diagnostic);
+}
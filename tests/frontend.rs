@@ -0,0 +1,178 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "analysis")]
+
+use luau::analysis::{CompletionKind, Frontend, TypeError, TypeErrorKind};
+use luau::ast::{Mode, Position, Span};
+
+#[test]
+fn type_mismatch() {
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.set_source("main", "local x: number = \"hello\"\nreturn x");
+
+	let errors = frontend.check("main");
+	assert_eq!(errors.len(), 1, "{:?}", errors);
+	assert_eq!(errors[0].kind, TypeErrorKind::TypeMismatch);
+	assert_eq!(errors[0].span, Span::new(0, 18, 0, 25));
+	assert_eq!(errors[0].module, "main");
+
+	// the hot comment overrides the mode of the frontend
+	let mut frontend = Frontend::new(Mode::NoCheck);
+	frontend.set_source("main", "--!strict\nlocal x: number = \"hello\"\nreturn x");
+	assert_eq!(frontend.check("main").len(), 1);
+}
+
+#[test]
+fn required_module_errors() {
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.set_source("a", "local b = require(\"b\")\nreturn b");
+	frontend.set_source("b", "local y: string = 1\nreturn {}");
+
+	let errors = frontend.check("a");
+	assert_eq!(errors.len(), 1, "{:?}", errors);
+	assert_eq!(errors[0].kind, TypeErrorKind::TypeMismatch);
+	assert_eq!(errors[0].module, "b");
+	assert_eq!(errors[0].span.0.line, 0);
+}
+
+#[test]
+fn syntax_error() {
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.set_source("main", "local = 1");
+
+	let errors = frontend.check("main");
+	assert!(!errors.is_empty());
+	assert!(errors.iter().all(|error| error.kind == TypeErrorKind::SyntaxError && error.module == "main"), "{:?}", errors);
+	assert_eq!(errors[0].span, Span::new(0, 6, 0, 7));
+}
+
+#[test]
+fn unknown_require() {
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.set_source("main", "local missing = require(\"missing\")\nreturn missing");
+
+	let errors = frontend.check("main");
+	assert_eq!(errors.iter().map(|error| error.kind).collect::<Vec<_>>(), [TypeErrorKind::UnknownRequire]);
+}
+
+const DEFINITIONS: &str = r#"
+declare class Part
+	Name: string
+	function Destroy(self): ()
+end
+
+declare function spawn(name: string): Part
+"#;
+
+fn kinds(errors: &[TypeError]) -> Vec<TypeErrorKind> {
+	errors.iter().map(|error| error.kind).collect()
+}
+
+#[test]
+fn definitions() {
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.load_definitions("@test", DEFINITIONS).unwrap();
+	frontend.set_source("main", "local part = spawn(\"a\")\nlocal name: string = part.Name\npart:Destroy()\nlocal count: number = part.Name\nreturn name, count");
+
+	let errors = frontend.check("main");
+	assert_eq!(kinds(&errors), [TypeErrorKind::TypeMismatch]);
+	assert_eq!(errors[0].span.0.line, 3);
+}
+
+#[test]
+fn malformed_definitions() {
+	let mut frontend = Frontend::new(Mode::Strict);
+
+	// the first declaration is fine, but nothing is loaded if any of it fails
+	let errors = frontend.load_definitions("@test", "declare function spawn(name: string): number\ndeclare class Broken\n\tName: string\n").unwrap_err();
+	assert!(errors.iter().any(|error| error.kind == TypeErrorKind::SyntaxError), "{:?}", errors);
+
+	let errors = frontend.load_definitions("@test", "declare function spawn(name: string): number\ndeclare function other(): Missing").unwrap_err();
+	assert_eq!(kinds(&errors), [TypeErrorKind::UnknownSymbol]);
+
+	frontend.set_source("main", "return spawn");
+	assert_eq!(kinds(&frontend.check("main")), [TypeErrorKind::UnknownSymbol]);
+}
+
+#[test]
+fn autocomplete_properties() {
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.set_source("main", "local t = { alpha = 1, beta = function() end }\nlocal x = t.");
+
+	let completions = frontend.autocomplete("main", Position { line: 1, column: 12 });
+	let labels: Vec<(&str, CompletionKind)> = completions.iter().map(|item| (item.label.as_str(), item.kind)).collect();
+	assert_eq!(labels, [("alpha", CompletionKind::Property), ("beta", CompletionKind::Method)]);
+	assert_eq!(completions[0].ty.as_deref(), Some("number"));
+}
+
+#[test]
+fn autocomplete_variables() {
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.set_source("main", "local apple = 1\nlocal function apply() end\nlocal x = ap");
+
+	let completions = frontend.autocomplete("main", Position { line: 2, column: 12 });
+	let apple = completions.iter().find(|item| item.label == "apple").expect("no completion for a local");
+	assert_eq!(apple.kind, CompletionKind::Variable);
+
+	let apply = completions.iter().find(|item| item.label == "apply").expect("no completion for a local function");
+	assert_eq!(apply.kind, CompletionKind::Function);
+}
+
+#[test]
+fn type_at() {
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.set_source("main", "local count: number = 1\nreturn count");
+
+	assert_eq!(frontend.type_at("main", Position { line: 1, column: 8 }).as_deref(), Some("number"));
+	assert_eq!(frontend.type_at("main", Position { line: 5, column: 0 }), None);
+}
+
+#[test]
+fn definition_of() {
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.set_source("util", "local util = {}\nfunction util.greet() end\nreturn util");
+	frontend.set_source("main", "local util = require(\"util\")\nutil.greet()");
+
+	let (module, span) = frontend.definition_of("main", Position { line: 1, column: 1 }).expect("no definition for a local");
+	assert_eq!(module, "main");
+	assert_eq!(span.0, Position { line: 0, column: 6 });
+
+	let (module, span) = frontend.definition_of("main", Position { line: 1, column: 7 }).expect("no definition for a property");
+	assert_eq!(module, "util");
+	assert_eq!(span.0.line, 1);
+}
+
+#[test]
+fn check_changed() {
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.set_source("a", "local b = require(\"b\")\nreturn b");
+	frontend.set_source("b", "return 1");
+	frontend.set_source("c", "return 1");
+
+	// checking a module checks everything it requires
+	assert!(frontend.check("a").is_empty());
+	assert_eq!(frontend.dirty_modules().iter().collect::<Vec<_>>(), ["c"]);
+	assert_eq!(frontend.module_graph().requires("a").collect::<Vec<_>>(), ["b"]);
+
+	frontend.check("c");
+	frontend.set_source("b", "return \"one\"");
+	assert_eq!(frontend.dirty_modules().iter().collect::<Vec<_>>(), ["a", "b"]);
+
+	let checked = frontend.check_changed();
+	assert_eq!(checked.keys().collect::<Vec<_>>(), ["a", "b"]);
+	assert!(checked.values().all(Vec::is_empty));
+	assert!(frontend.dirty_modules().is_empty());
+}
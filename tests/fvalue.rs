@@ -0,0 +1,64 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(all(feature = "compiler", feature = "vm"))]
+
+use std::os::raw::c_int;
+
+use luau::fvalue::{self, ApplyError, FValue};
+use luau::vm::Luau;
+
+// FValues are global, so everything is tested in one test to avoid racing
+// with other tests in this file
+#[test]
+fn overrides() {
+	// most FValues are only registered once the compiler has been used
+	Luau::compile("").unwrap();
+
+	let exclusive = fvalue::exclusive();
+	let defaults = fvalue::snapshot();
+	let mut fflag = FValue::<bool>::list().into_iter().next().expect("no FFlags");
+	let name = fflag.name().to_owned();
+	let default = fflag.value();
+
+	{
+		let _guard = fflag.scoped_set(&exclusive, !default);
+		assert_eq!(fflag.value(), !default);
+	}
+
+	assert_eq!(fflag.value(), default);
+
+	fflag.set(&exclusive, !default);
+	assert_eq!(fflag.value(), !default);
+	fflag.set(&exclusive, default);
+
+	fvalue::apply_from_str(&exclusive, &format!("{}={}", name, !default)).unwrap();
+	assert_eq!(fflag.value(), !default);
+
+	assert_eq!(fvalue::apply_from_str(&exclusive, &format!("{}=3", name)), Err(ApplyError::InvalidValue { name: name.clone(), value: "3".to_owned() }));
+	assert_eq!(fvalue::apply_from_str(&exclusive, "NotAnFValue=true"), Err(ApplyError::Unknown("NotAnFValue".to_owned())));
+	assert_eq!(fvalue::apply_from_str(&exclusive, &name), Err(ApplyError::Syntax(name.clone())));
+
+	// nothing is applied if any assignment is invalid
+	assert!(fvalue::apply_from_str(&exclusive, &format!("{}={},NotAnFValue=true", name, default)).is_err());
+	assert_eq!(fflag.value(), !default);
+
+	fvalue::restore(&exclusive, &defaults);
+	assert_eq!(fflag.value(), default);
+
+	let after: Vec<(String, c_int)> = fvalue::snapshot().fints().map(|(name, value)| (name.to_owned(), value)).collect();
+	let before: Vec<(String, c_int)> = defaults.fints().map(|(name, value)| (name.to_owned(), value)).collect();
+	assert_eq!(after, before);
+}
@@ -0,0 +1,95 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(all(feature = "ast", feature = "serde"))]
+
+use luau::ast::{parse, ParseOptions};
+use serde_json::{json, Value};
+
+fn to_json(source: &str) -> Value {
+	let mut opts = ParseOptions::default();
+	opts.set_capture_comments(true);
+
+	let ast = parse(source, &opts).unwrap_or_else(|errors| panic!("failed to parse {:?}: {:?}", source, errors));
+	serde_json::to_value(&ast).unwrap()
+}
+
+#[test]
+fn local() {
+	let json = to_json("local a = b + 1");
+
+	assert_eq!(json["root"]["type"], "AstStatBlock");
+	assert_eq!(json["root"]["body"][0], json!({
+		"type": "AstStatLocal",
+		"location": "0,0 - 0,15",
+		"vars": [{
+			"luauType": null,
+			"name": "a",
+			"type": "AstLocal",
+			"location": "0,6 - 0,7"
+		}],
+		"values": [{
+			"type": "AstExprBinary",
+			"location": "0,10 - 0,15",
+			"op": "Add",
+			"left": {
+				"type": "AstExprGlobal",
+				"location": "0,10 - 0,11",
+				"global": "b"
+			},
+			"right": {
+				"type": "AstExprConstantNumber",
+				"location": "0,14 - 0,15",
+				"value": 1.0
+			}
+		}]
+	}));
+}
+
+#[test]
+fn locals_are_written_in_full() {
+	let json = to_json("local x: number = 1\nprint(x)");
+	let arg = &json["root"]["body"][1]["expr"]["args"][0];
+
+	assert_eq!(arg["type"], "AstExprLocal");
+	assert_eq!(arg["local"]["name"], "x");
+	assert_eq!(arg["local"]["luauType"]["type"], "AstTypeReference");
+	assert_eq!(arg["local"]["luauType"]["name"], "number");
+}
+
+#[test]
+fn comments() {
+	let json = to_json("-- line\n--[[ block ]]\nreturn");
+
+	assert_eq!(json["commentLocations"], json!([
+		{ "type": "Comment", "location": "0,0 - 0,7" },
+		{ "type": "BlockComment", "location": "1,0 - 1,13" }
+	]));
+}
+
+#[test]
+fn functions() {
+	let json = to_json("function t:m(a, ...: number): string end");
+	let func = &json["root"]["body"][0]["func"];
+
+	assert_eq!(func["type"], "AstExprFunction");
+	assert_eq!(func["self"]["name"], "self");
+	assert_eq!(func["args"][0]["name"], "a");
+	assert_eq!(func["vararg"], true);
+	assert_eq!(func["varargAnnotation"]["type"], "AstTypePackVariadic");
+	assert_eq!(func["returnAnnotation"]["type"], "AstTypeList");
+	assert_eq!(func["debugname"], "m");
+	assert!(func.get("generics").is_none());
+}
@@ -0,0 +1,112 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "ast")]
+
+use luau::ast::{lex, Keyword, Span, TokenKind};
+
+fn tokens(source: &str) -> Vec<(TokenKind, &str)> {
+	lex(source).map(|token| (token.kind, token.text)).collect()
+}
+
+#[test]
+fn kinds() {
+	assert_eq!(tokens("local x: number = a.b .. 0x10 -- hi"), [
+		(TokenKind::Keyword(Keyword::Local), "local"),
+		(TokenKind::Name, "x"),
+		(TokenKind::Char(':'), ":"),
+		(TokenKind::Name, "number"),
+		(TokenKind::Char('='), "="),
+		(TokenKind::Name, "a"),
+		(TokenKind::Char('.'), "."),
+		(TokenKind::Name, "b"),
+		(TokenKind::Dot2, ".."),
+		(TokenKind::Number, "0x10"),
+		(TokenKind::Comment, "-- hi")
+	]);
+
+	assert_eq!(tokens("x += 1 y //= 2 f = function(...): () -> () end"), [
+		(TokenKind::Name, "x"),
+		(TokenKind::AddAssign, "+="),
+		(TokenKind::Number, "1"),
+		(TokenKind::Name, "y"),
+		(TokenKind::FloorDivAssign, "//="),
+		(TokenKind::Number, "2"),
+		(TokenKind::Name, "f"),
+		(TokenKind::Char('='), "="),
+		(TokenKind::Keyword(Keyword::Function), "function"),
+		(TokenKind::Char('('), "("),
+		(TokenKind::Dot3, "..."),
+		(TokenKind::Char(')'), ")"),
+		(TokenKind::Char(':'), ":"),
+		(TokenKind::Char('('), "("),
+		(TokenKind::Char(')'), ")"),
+		(TokenKind::SkinnyArrow, "->"),
+		(TokenKind::Char('('), "("),
+		(TokenKind::Char(')'), ")"),
+		(TokenKind::Keyword(Keyword::End), "end")
+	]);
+}
+
+#[test]
+fn strings() {
+	assert_eq!(tokens("'a' [[b]] --[[c]]"), [
+		(TokenKind::QuotedString, "'a'"),
+		(TokenKind::RawString, "[[b]]"),
+		(TokenKind::BlockComment, "--[[c]]")
+	]);
+
+	assert_eq!(tokens("'broken\nx"), [
+		(TokenKind::BrokenString, "'broken"),
+		(TokenKind::Name, "x")
+	]);
+
+	assert_eq!(tokens("--[[ never closed").last().map(|(kind, _)| *kind), Some(TokenKind::BrokenComment));
+}
+
+#[test]
+fn interpolated_strings() {
+	assert_eq!(tokens("`plain`"), [(TokenKind::InterpStringSimple, "`plain`")]);
+
+	assert_eq!(tokens("`a{b}c{d + 1}e`"), [
+		(TokenKind::InterpStringBegin, "`a{"),
+		(TokenKind::Name, "b"),
+		(TokenKind::InterpStringMid, "}c{"),
+		(TokenKind::Name, "d"),
+		(TokenKind::Char('+'), "+"),
+		(TokenKind::Number, "1"),
+		(TokenKind::InterpStringEnd, "}e`")
+	]);
+}
+
+#[test]
+fn spans() {
+	let spans: Vec<Span> = lex("local a\n  = 1").map(|token| token.span).collect();
+
+	assert_eq!(spans, [
+		Span::new(0, 0, 0, 5),
+		Span::new(0, 6, 0, 7),
+		Span::new(1, 2, 1, 3),
+		Span::new(1, 4, 1, 5)
+	]);
+}
+
+#[test]
+fn early_drop() {
+	// the lexer is freed even if the iterator isn't exhausted
+	let mut tokens = lex("local a = 1");
+	assert_eq!(tokens.next().map(|token| token.kind), Some(TokenKind::Keyword(Keyword::Local)));
+	drop(tokens);
+}
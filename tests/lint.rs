@@ -0,0 +1,89 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "analysis")]
+
+use luau::analysis::{lint, Frontend, LintCode, LintOptions, LintWarning};
+use luau::ast::{parse, Mode, ParseOptions, Span};
+
+fn codes(warnings: &[LintWarning]) -> Vec<LintCode> {
+	warnings.iter().map(|warning| warning.code).collect()
+}
+
+#[test]
+fn local_unused() {
+	let warnings = lint("local x = 1", &LintOptions::default());
+
+	assert_eq!(codes(&warnings), [LintCode::LocalUnused]);
+	assert_eq!(warnings[0].span, Span::new(0, 6, 0, 7));
+	assert!(warnings[0].to_string().contains("LocalUnused (7)"), "{}", warnings[0]);
+
+	// prefixing with an underscore silences it
+	assert!(lint("local _x = 1", &LintOptions::default()).is_empty());
+}
+
+#[test]
+fn disabled() {
+	let mut options = LintOptions::default();
+	options.disable(LintCode::LocalUnused);
+	assert!(!options.is_enabled(LintCode::LocalUnused));
+	assert!(lint("local x = 1", &options).is_empty());
+
+	assert!(lint("local x = 1", &LintOptions::none()).is_empty());
+	assert_eq!(codes(&lint("local x = 1", LintOptions::none().enable(LintCode::LocalUnused))), [LintCode::LocalUnused]);
+}
+
+#[test]
+fn nolint() {
+	assert!(lint("--!nolint LocalUnused\nlocal x = 1", &LintOptions::default()).is_empty());
+	assert!(lint("--!nolint\nlocal x = 1", &LintOptions::default()).is_empty());
+	assert_eq!(codes(&lint("--!nolint UnknownGlobal\nlocal x = 1", &LintOptions::default())), [LintCode::LocalUnused]);
+
+	// the same hot comments can be applied by hand
+	let ast = parse("--!nolint LocalUnused\nlocal x = 1", &ParseOptions::default()).unwrap();
+	let mut options = LintOptions::default();
+	options.apply_hot_comments(&ast.hot_comments);
+	assert!(!options.is_enabled(LintCode::LocalUnused));
+	assert!(options.is_enabled(LintCode::UnknownGlobal));
+}
+
+#[test]
+fn numbers() {
+	// these match LintWarning::Code in Luau's LinterConfig.h
+	assert_eq!(LintCode::UnknownGlobal.number(), 1);
+	assert_eq!(LintCode::LocalUnused.number(), 7);
+	assert_eq!(LintCode::UnreachableCode.number(), 12);
+	assert_eq!(LintCode::ComparisonPrecedence.number(), 28);
+
+	assert_eq!(LintCode::from_number(7), LintCode::LocalUnused);
+	assert_eq!(LintCode::from_number(200), LintCode::Other(200));
+	assert_eq!(LintCode::from_name("LocalUnused"), Some(LintCode::LocalUnused));
+	assert_eq!(LintCode::from_name("NotALint"), None);
+
+	// the numbers come straight from Luau, so they have to agree with the names
+	let warnings = lint("local x = 1\nprint(y)", &LintOptions::default());
+	assert_eq!(codes(&warnings), [LintCode::LocalUnused, LintCode::UnknownGlobal]);
+}
+
+#[test]
+fn frontend() {
+	let mut frontend = Frontend::new(Mode::Strict);
+	frontend.set_source("main", "local unused = require(\"util\")\nreturn nil");
+	frontend.set_source("util", "local x = 1\nreturn {}");
+
+	// only the linted module is reported
+	assert_eq!(codes(&frontend.lint("main", &LintOptions::default())), [LintCode::ImportUnused]);
+	assert_eq!(codes(&frontend.lint("util", &LintOptions::default())), [LintCode::LocalUnused]);
+}
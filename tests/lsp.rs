@@ -0,0 +1,186 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "lsp")]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Command, Stdio};
+
+use serde_json::{json, Value};
+
+/// Runs the server on a whole session, returning every message it sent and
+/// its exit code.
+fn session(messages: &[Value]) -> (Vec<Value>, Option<i32>) {
+	let bodies: Vec<String> = messages.iter().map(Value::to_string).collect();
+	session_raw(&bodies)
+}
+
+/// Like [`session`], but the bodies don't have to be valid JSON.
+fn session_raw(bodies: &[String]) -> (Vec<Value>, Option<i32>) {
+	let mut child = Command::new(env!("CARGO_BIN_EXE_luau-lsp"))
+		.stdin(Stdio::piped())
+		.stdout(Stdio::piped())
+		.spawn()
+		.unwrap();
+
+	let mut stdin = child.stdin.take().unwrap();
+
+	for body in bodies {
+		write!(stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+	}
+
+	drop(stdin);
+
+	let mut stdout = BufReader::new(child.stdout.take().unwrap());
+	let mut replies = Vec::new();
+
+	loop {
+		let mut header = String::new();
+
+		if stdout.read_line(&mut header).unwrap() == 0 {
+			break
+		}
+
+		let length: usize = header.trim().strip_prefix("Content-Length: ").unwrap().parse().unwrap();
+		stdout.read_line(&mut String::new()).unwrap();
+
+		let mut body = vec![0; length];
+		stdout.read_exact(&mut body).unwrap();
+		replies.push(serde_json::from_slice(&body).unwrap());
+	}
+
+	(replies, child.wait().unwrap().code())
+}
+
+fn request(id: u32, method: &str, params: Value) -> Value {
+	json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+	json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn open(uri: &str, text: &str) -> Value {
+	notification("textDocument/didOpen", json!({ "textDocument": { "uri": uri, "languageId": "luau", "version": 1, "text": text } }))
+}
+
+fn reply(replies: &[Value], id: u32) -> &Value {
+	replies.iter().find(|reply| reply["id"] == id).unwrap()
+}
+
+#[test]
+fn diagnostics() {
+	let (replies, code) = session(&[
+		request(1, "initialize", json!({ "capabilities": {} })),
+		notification("initialized", json!({})),
+		open("file:///main.luau", "local x: number = \"hello\"\nprint(x)\n"),
+		notification("textDocument/didChange", json!({
+			"textDocument": { "uri": "file:///main.luau", "version": 2 },
+			"contentChanges": [{ "text": "local x: number = 1\nprint(x)\n" }]
+		})),
+		request(2, "shutdown", Value::Null),
+		notification("exit", Value::Null)
+	]);
+
+	assert_eq!(code, Some(0));
+	assert_eq!(reply(&replies, 1)["result"]["capabilities"]["hoverProvider"], true);
+
+	let published: Vec<&Value> = replies.iter()
+		.filter(|reply| reply["method"] == "textDocument/publishDiagnostics")
+		.map(|reply| &reply["params"]["diagnostics"])
+		.collect();
+
+	assert_eq!(published.len(), 2);
+	assert_eq!(published[0][0]["severity"], 1);
+	assert_eq!(published[0][0]["range"]["start"], json!({ "line": 0, "character": 18 }));
+	assert_eq!(published[1], &json!([]));
+}
+
+#[test]
+fn incremental() {
+	let (replies, _) = session(&[
+		request(1, "initialize", json!({ "capabilities": {} })),
+		open("file:///a.luau", "return 1"),
+		open("file:///b.luau", "local a: string = require(\"file:///a.luau\")\nprint(a)\n"),
+		open("file:///c.luau", "print(1)\n"),
+		notification("textDocument/didChange", json!({
+			"textDocument": { "uri": "file:///a.luau", "version": 2 },
+			"contentChanges": [{ "text": "return \"one\"" }]
+		})),
+		notification("textDocument/didClose", json!({ "textDocument": { "uri": "file:///a.luau" } })),
+		notification("exit", Value::Null)
+	]);
+
+	let published: Vec<(&str, &Value)> = replies.iter()
+		.filter(|reply| reply["method"] == "textDocument/publishDiagnostics")
+		.map(|reply| (reply["params"]["uri"].as_str().unwrap(), &reply["params"]["diagnostics"]))
+		.collect();
+
+	// only the changed document and the ones requiring it are published
+	let uris: Vec<&str> = published.iter().map(|(uri, _)| *uri).collect();
+	assert_eq!(uris, ["file:///a.luau", "file:///b.luau", "file:///c.luau", "file:///a.luau", "file:///b.luau", "file:///a.luau", "file:///b.luau"]);
+
+	assert_eq!(published[1].1.as_array().unwrap().len(), 1);
+	assert_eq!(published[4].1, &json!([]));
+	assert_eq!(published[5].1, &json!([]));
+}
+
+#[test]
+fn parse_error() {
+	let (replies, code) = session_raw(&[
+		request(1, "initialize", json!({ "capabilities": {} })).to_string(),
+		"{ not json".to_owned(),
+		request(2, "shutdown", Value::Null).to_string(),
+		notification("exit", Value::Null).to_string()
+	]);
+
+	// the server keeps going after replying with an error
+	assert_eq!(code, Some(0));
+	assert_eq!(replies[1]["id"], Value::Null);
+	assert_eq!(replies[1]["error"]["code"], -32700);
+	assert_eq!(reply(&replies, 2)["result"], Value::Null);
+}
+
+#[test]
+fn queries() {
+	let source = "local function add(a: number, b: number)\n\treturn a + b\nend\n\nlocal total = add(1, 2)\nprint(tot)\n";
+
+	let (replies, code) = session(&[
+		request(1, "initialize", json!({ "capabilities": {} })),
+		open("file:///main.luau", source),
+		request(2, "textDocument/hover", json!({ "textDocument": { "uri": "file:///main.luau" }, "position": { "line": 4, "character": 15 } })),
+		request(3, "textDocument/definition", json!({ "textDocument": { "uri": "file:///main.luau" }, "position": { "line": 4, "character": 15 } })),
+		request(4, "textDocument/completion", json!({ "textDocument": { "uri": "file:///main.luau" }, "position": { "line": 5, "character": 9 } })),
+		request(5, "textDocument/documentSymbol", json!({ "textDocument": { "uri": "file:///main.luau" } })),
+		notification("exit", Value::Null)
+	]);
+
+	// exiting without shutting down first is an error
+	assert_eq!(code, Some(1));
+
+	let hover = reply(&replies, 2)["result"]["contents"]["value"].as_str().unwrap();
+	assert!(hover.contains("number"), "{}", hover);
+
+	assert_eq!(reply(&replies, 3)["result"], json!({
+		"uri": "file:///main.luau",
+		"range": { "start": { "line": 0, "character": 15 }, "end": { "line": 0, "character": 18 } }
+	}));
+
+	let completions = reply(&replies, 4)["result"]["items"].as_array().unwrap();
+	assert!(completions.iter().any(|item| item["label"] == "total"));
+
+	let symbols: Vec<&str> = reply(&replies, 5)["result"].as_array().unwrap().iter().map(|symbol| symbol["name"].as_str().unwrap()).collect();
+	assert_eq!(symbols, ["add", "total"]);
+}
@@ -0,0 +1,140 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "ast")]
+
+use luau::ast::tree::{Ast, CommentKind, ExprKind, HotCommentKind, StatKind};
+use luau::ast::{parse, parse_recovering, Mode, ParseOptions, Span, Suggestion};
+
+fn parse_ok(source: &str, opts: &ParseOptions) -> Ast {
+	parse(source, opts).unwrap_or_else(|errors| panic!("failed to parse {:?}: {:?}", source, errors))
+}
+
+#[test]
+fn mode() {
+	let opts = ParseOptions::default();
+
+	assert_eq!(parse_ok("--!strict\nlocal x = 1", &opts).mode(), Some(Mode::Strict));
+	assert_eq!(parse_ok("--!optimize 2\n--!nonstrict\nlocal x = 1", &opts).mode(), Some(Mode::Nonstrict));
+	assert_eq!(parse_ok("--!nocheck\n--!strict", &opts).mode(), Some(Mode::NoCheck));
+	assert_eq!(parse_ok("local x = 1", &opts).mode(), None);
+
+	// hot comments after the first token don't set the mode
+	assert_eq!(parse_ok("local x = 1\n--!strict", &opts).mode(), None);
+}
+
+#[test]
+fn hot_comments() {
+	let ast = parse_ok("--!strict\n--!nolint LocalUnused\nlocal x = 1\n--!optimize 3\n", &ParseOptions::default());
+	let hot_comments: Vec<(bool, &str, &HotCommentKind)> = ast.hot_comments.iter()
+		.map(|comment| (comment.header, comment.content.as_str(), &comment.kind))
+		.collect();
+
+	assert_eq!(hot_comments, [
+		(true, "strict", &HotCommentKind::Strict),
+		(true, "nolint LocalUnused", &HotCommentKind::NoLint(Some("LocalUnused".to_owned()))),
+		(false, "optimize 3", &HotCommentKind::Unknown)
+	]);
+
+	assert_eq!(ast.hot_comments[0].span.0.line, 0);
+	assert_eq!(ast.hot_comments[2].span.0.line, 3);
+
+	// hot comments are captured even when comments aren't
+	assert!(ast.comments.is_empty());
+}
+
+#[test]
+fn hot_comment_kinds() {
+	assert_eq!(HotCommentKind::parse(" nocheck "), HotCommentKind::NoCheck);
+	assert_eq!(HotCommentKind::parse("native"), HotCommentKind::Native);
+	assert_eq!(HotCommentKind::parse("nolint"), HotCommentKind::NoLint(None));
+	assert_eq!(HotCommentKind::parse("optimize 1"), HotCommentKind::Optimize(1));
+	assert_eq!(HotCommentKind::parse("optimize"), HotCommentKind::Unknown);
+	assert_eq!(HotCommentKind::parse("strict please"), HotCommentKind::Unknown);
+}
+
+#[test]
+fn comments() {
+	let mut opts = ParseOptions::default();
+	opts.set_capture_comments(true);
+
+	// an unclosed comment may also be reported as an error
+	let ast = parse_recovering("-- line\nlocal x = 1 --[[ block ]]\n--[==[ unclosed", &opts).unwrap().ast;
+	let comments: Vec<(CommentKind, &str, u32)> = ast.comments.iter()
+		.map(|comment| (comment.kind, comment.text.as_str(), comment.span.0.line))
+		.collect();
+
+	assert_eq!(comments, [
+		(CommentKind::Line, "-- line", 0),
+		(CommentKind::Block, "--[[ block ]]", 1),
+		(CommentKind::BrokenBlock, "--[==[ unclosed", 2)
+	]);
+}
+
+#[test]
+fn recovering() {
+	let source = "local a = 1\nlocal b =\nlocal c = 3";
+	let result = parse_recovering(source, &ParseOptions::default()).unwrap();
+
+	assert!(!result.errors.is_empty());
+	assert_eq!(result.errors[0].span.0.line, 2);
+	assert_eq!(parse(source, &ParseOptions::default()), Err(result.errors.clone()));
+
+	let names: Vec<&str> = result.ast.locals.iter().map(|local| local.name.as_str()).collect();
+	assert_eq!(names, ["a", "b", "c"]);
+
+	let body = &result.ast.root.body;
+	assert_eq!(body.len(), 3);
+
+	let StatKind::Local(local) = &body[1].kind else { panic!("expected a local, got {:?}", body[1]) };
+	let ExprKind::Error(error) = &local.values[0].kind else { panic!("expected an error, got {:?}", local.values[0]) };
+	assert_eq!(error.message_index, Some(0));
+
+	let StatKind::Local(local) = &body[2].kind else { panic!("expected a local, got {:?}", body[2]) };
+	assert_eq!(result.ast.local(local.vars[0].local).name, "c");
+}
+
+#[test]
+fn recovering_unclosed() {
+	let result = parse_recovering("local function f()\n\treturn 1\n", &ParseOptions::default()).unwrap();
+
+	assert_eq!(result.errors.len(), 1);
+	assert!(result.errors[0].message.contains("to close 'function'"), "{}", result.errors[0].message);
+
+	let StatKind::LocalFunction(function) = &result.ast.root.body[0].kind else { panic!("expected a function") };
+	assert!(!function.func.body.has_end);
+	assert_eq!(function.func.body.body.len(), 1);
+}
+
+#[test]
+fn confusables() {
+	// a Greek question mark in place of a semicolon
+	let errors = parse("local a = 1\u{37e}", &ParseOptions::default()).unwrap_err();
+
+	assert_eq!(errors.iter().find_map(|error| error.suggestion.clone()), Some(Suggestion {
+		span: Span::new(0, 11, 0, 13),
+		replacement: ";".to_owned()
+	}));
+
+	// smart quotes
+	let errors = parse("print(\u{201c}hi\u{201d})", &ParseOptions::default()).unwrap_err();
+	let suggestion = errors[0].suggestion.as_ref().expect("no suggestion for a smart quote");
+	assert_eq!(suggestion.span, Span::new(0, 6, 0, 9));
+	assert_eq!(suggestion.replacement, "\"");
+
+	// plain syntax errors have nothing to suggest
+	let errors = parse("local a = = 1", &ParseOptions::default()).unwrap_err();
+	assert!(errors.iter().all(|error| error.suggestion.is_none()));
+}
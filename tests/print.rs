@@ -0,0 +1,210 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "ast")]
+
+use luau::ast::{parse, print, print_with_options, ParseOptions, PrintOptions};
+
+/// Formats an AST without any of its spans, which aren't expected to survive
+/// printing. Locals are included, since the tree only refers to their names
+/// by ID.
+fn structure(source: &str) -> String {
+	let ast = parse(source, &ParseOptions::default())
+		.unwrap_or_else(|errors| panic!("failed to parse {:?}: {:?}", source, errors));
+
+	let debug = format!("{:?}\n{:?}", ast.root, ast.locals);
+	let mut out = String::new();
+	let mut rest = debug.as_str();
+
+	while let Some(start) = rest.find("Span(") {
+		out.push_str(&rest[..start]);
+		rest = &rest[start..];
+
+		let mut depth = 0;
+		let end = rest.char_indices().find(|(_, char)| {
+			match char {
+				'(' => depth += 1,
+				')' => depth -= 1,
+				_ => {}
+			}
+
+			*char == ')' && depth == 0
+		}).unwrap().0;
+
+		rest = &rest[end + 1..];
+	}
+
+	out.push_str(rest);
+	out
+}
+
+fn round_trip(source: &str) {
+	let printed = print(&parse(source, &ParseOptions::default()).unwrap());
+	assert_eq!(structure(source), structure(&printed), "printed as:\n{}", printed);
+
+	// printing is stable once the source is in printed form
+	assert_eq!(printed, print(&parse(&printed, &ParseOptions::default()).unwrap()));
+}
+
+#[test]
+fn statements() {
+	round_trip(r#"
+		local a, b: number = 1, 2
+		local function f(x, y: string, ...: number): (number, string)
+			if x then
+				return 1, "a"
+			elseif y then
+				return 2, 'b'
+			else
+				return 3, [[c]]
+			end
+		end
+
+		function t.a.b:c(...)
+			for i = 1, 10, 2 do
+				while i < 5 do
+					i += 1
+					continue
+				end
+			end
+
+			for k, v in pairs({}) do
+				repeat
+					local z = k
+				until z
+				break
+			end
+
+			do
+				a, b = b, a
+			end
+		end
+
+		f(1)
+		;(f)(2)
+		t.x.y = -#a ^ 2
+		a ..= "x"
+	"#);
+}
+
+#[test]
+fn expressions() {
+	round_trip(r#"
+		local x = (a + b) * c - d / e // f % g
+		local y = a .. b .. (c .. d) .. e
+		local z = not a == b and c or d
+		local w = -(2 ^ 3) ^ -2
+		local s = "quote\" newline\n tab\t nul\0 byte\255 unicode é"
+		local l = [==[long ]] string]==]
+		local i = `interpolated {x} and { {1} } \{escaped} \`tick\``
+		local t = { 1, 2, a = 3, ["b c"] = 4, [5] = 6, f = function(self) return self end }
+		local c = if a then b elseif c then d else e
+		local m = obj:method(1, "two"):other { 3 } "four"
+		local q = (a :: any) :: number
+		local v = ...
+		local n = 1e300 + 0.1 + 0x10 + 1_000
+	"#);
+}
+
+#[test]
+fn types() {
+	round_trip(r#"
+		export type Map<K, V = string> = { [K]: V }
+		type Fn<T..., U... = ...number> = (T...) -> U...
+		type Callback = (name: string, number, ...any) -> (boolean, string?)
+		type Mixed = (A & B) | (() -> ()) | "literal" | true | nil
+		type Nested = { a: { b: number }, c: string, ["e f"]: typeof(x) }
+		type Generic = Map<string, Array<number>>
+		type Packs = Fn<(number, string), ...boolean>
+		type Imported = module.Type<number>
+		type Empty = {}
+
+		local function g<T>(x: T): T
+			return x
+		end
+
+		local h = function(): () -> ()
+			return function() end
+		end
+	"#);
+}
+
+#[test]
+fn annotated_locals() {
+	round_trip(r#"
+		local a: number, b: { string }?, c = 1, nil, 3
+		local function f(x: number, y: (string) -> boolean, ...: any): number
+			return x
+		end
+
+		for i: number = 1, 10 do
+			print(i)
+		end
+
+		for key: string, value: { [string]: number } in pairs({}) do
+			print(key, value)
+		end
+
+		local g = function(self: typeof(a), z: Map<string, number>) end
+	"#);
+}
+
+#[test]
+fn attributes() {
+	round_trip(r#"
+		@native
+		local function f() end
+
+		@native function g() end
+	"#);
+}
+
+#[test]
+fn declarations() {
+	let source = r#"
+		declare foo: number
+		declare function bar<T>(a: T, ...: string): T
+
+		declare class Base
+			x: number
+			function method(self, y: string): boolean
+		end
+
+		declare class Derived extends Base
+			[string]: number
+		end
+	"#;
+
+	let mut opts = ParseOptions::default();
+	opts.set_allow_declaration_syntax(true);
+
+	let printed = print(&parse(source, &opts).unwrap());
+	let reparsed = print(&parse(&printed, &opts).unwrap());
+	assert_eq!(printed, reparsed);
+}
+
+#[test]
+fn comments() {
+	let source = "--!strict\n-- leading\nlocal a = 1 -- trailing\n\nif a then\n\t-- inside\n\tprint(a)\n\t--[[ before end ]]\nend\n-- last\n";
+
+	let mut opts = ParseOptions::default();
+	opts.set_capture_comments(true);
+
+	let printed = print_with_options(&parse(source, &opts).unwrap(), &PrintOptions { comments: true });
+	assert_eq!(printed, "--!strict\n-- leading\nlocal a = 1 -- trailing\nif a then\n\t-- inside\n\tprint(a)\n\t--[[ before end ]]\nend\n-- last\n");
+
+	// without the option, comments are dropped
+	assert_eq!(print(&parse(source, &opts).unwrap()), "local a = 1\nif a then\n\tprint(a)\nend\n");
+}
@@ -0,0 +1,112 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "analysis")]
+
+mod common;
+
+use luau::analysis::{require_graph, FileResolver, FsResolver, MemoryResolver, ModuleGraph, RequireErrorKind, RequireExpr, Source};
+
+use common::temp_dir;
+
+#[test]
+fn memory() {
+	let mut resolver = MemoryResolver::new();
+	resolver.set_source("a", Source::module("return 1"));
+
+	assert_eq!(resolver.read_source("a"), Some(Source::module("return 1")));
+	assert_eq!(resolver.read_source("b"), None);
+	assert_eq!(resolver.resolve_module("b", RequireExpr::String("a")), Some("a".to_owned()));
+	assert_eq!(resolver.resolve_module("a", RequireExpr::String("b")), None);
+	assert_eq!(resolver.resolve_module("b", RequireExpr::Path(&["script", "a"])), None);
+}
+
+#[test]
+fn filesystem() {
+	let root = temp_dir("filesystem");
+	std::fs::create_dir_all(root.join("src/util")).unwrap();
+	std::fs::write(root.join("src/main.luau"), "return 1").unwrap();
+	std::fs::write(root.join("src/sibling.lua"), "return 2").unwrap();
+	std::fs::write(root.join("src/util/init.luau"), "return 3").unwrap();
+	std::fs::write(root.join("top.luau"), "return 4").unwrap();
+
+	let resolver = FsResolver::new(&root);
+	let resolve = |from, path| resolver.resolve_module(from, RequireExpr::String(path));
+
+	assert_eq!(resolver.read_source("src/main.luau"), Some(Source::module("return 1")));
+	assert_eq!(resolve("src/main.luau", "./sibling"), Some("src/sibling.lua".to_owned()));
+	assert_eq!(resolve("src/main.luau", "./util"), Some("src/util/init.luau".to_owned()));
+	assert_eq!(resolve("src/util/init.luau", "../../top"), Some("top.luau".to_owned()));
+	assert_eq!(resolve("src/main.luau", "./missing"), None);
+	assert_eq!(resolve("top.luau", "../outside"), None);
+
+	let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn filesystem_escapes() {
+	let base = temp_dir("filesystem-escapes");
+	let root = base.join("root");
+	std::fs::create_dir_all(root.join("src")).unwrap();
+	std::fs::write(root.join("src/main.luau"), "return 1").unwrap();
+	std::fs::write(base.join("secret.luau"), "return 2").unwrap();
+
+	let resolver = FsResolver::new(&root);
+
+	// names are normalized the same way requires are
+	assert_eq!(resolver.read_source("./src/../src/main.luau"), Some(Source::module("return 1")));
+	assert_eq!(resolver.read_source("src//main.luau"), Some(Source::module("return 1")));
+
+	assert_eq!(resolver.read_source("../secret.luau"), None);
+	assert_eq!(resolver.read_source("src/../../secret.luau"), None);
+	assert_eq!(resolver.read_source(base.join("secret.luau").to_str().unwrap()), None);
+	assert_eq!(resolver.read_source("src\\..\\..\\secret.luau"), None);
+	assert_eq!(resolver.read_source(""), None);
+
+	let _ = std::fs::remove_dir_all(&base);
+}
+
+#[test]
+fn cycles() {
+	let mut graph = ModuleGraph::new();
+	graph.add_require("main", "a").add_require("a", "b").add_require("b", "a");
+	graph.add_require("main", "c").add_require("c", "c");
+	graph.add_require("main", "d");
+
+	assert_eq!(graph.cycles(), [vec!["a", "b"], vec!["c"]]);
+	assert_eq!(graph.dependents("a").collect::<Vec<_>>(), ["b", "main"]);
+	assert_eq!(graph.requires("main").collect::<Vec<_>>(), ["a", "c", "d"]);
+}
+
+#[test]
+fn requires() {
+	let mut resolver = MemoryResolver::new();
+	resolver.set_source("main", Source::module("local a = require(\"a\")\nlocal b = require(\"missing\")\nreturn require(name())"));
+	resolver.set_source("a", Source::module("return require(\"b\")"));
+	resolver.set_source("b", Source::module("local x = require(script.x)\nreturn require(\"a\")"));
+
+	let graph = require_graph("main", &resolver);
+
+	assert_eq!(graph.graph.modules().collect::<Vec<_>>(), ["a", "b", "main"]);
+	assert_eq!(graph.cycles, [vec!["a", "b"]]);
+
+	let errors: Vec<(&str, &RequireErrorKind)> = graph.errors.iter().map(|error| (error.module.as_str(), &error.kind)).collect();
+
+	assert_eq!(errors, [
+		("main", &RequireErrorKind::Unresolved("\"missing\"".to_owned())),
+		("main", &RequireErrorKind::Dynamic),
+		("b", &RequireErrorKind::Unresolved("script.x".to_owned()))
+	]);
+}
@@ -0,0 +1,105 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "ast")]
+
+use luau::ast::source_map::SourceMap;
+use luau::ast::{Position, Span};
+
+fn position(line: u32, column: u32) -> Position {
+	Position { line, column }
+}
+
+#[test]
+fn lines() {
+	let map = SourceMap::new("local a\nlocal b\n");
+
+	assert_eq!(map.line_count(), 3);
+	assert_eq!(map.line(0), Some("local a"));
+	assert_eq!(map.line(1), Some("local b"));
+	assert_eq!(map.line(2), Some(""));
+	assert_eq!(map.line(3), None);
+	assert_eq!(map.line_range(1), Some(8..15));
+}
+
+#[test]
+fn conversions() {
+	let map = SourceMap::new("local a\nlocal b");
+
+	assert_eq!(map.position(0), Some(position(0, 0)));
+	assert_eq!(map.position(7), Some(position(0, 7)));
+	assert_eq!(map.position(8), Some(position(1, 0)));
+	assert_eq!(map.position(15), Some(position(1, 7)));
+	assert_eq!(map.position(16), None);
+
+	assert_eq!(map.offset(position(1, 6)), Some(14));
+	assert_eq!(map.offset(position(0, 7)), Some(7));
+	assert_eq!(map.offset(position(0, 8)), None);
+	assert_eq!(map.offset(position(2, 0)), None);
+
+	let span = Span::new(1, 6, 1, 7);
+	assert_eq!(map.range(span), Some(14..15));
+	assert_eq!(map.span(14..15), Some(span));
+	assert_eq!(map.slice(span), Some("b"));
+}
+
+#[test]
+fn multibyte() {
+	// columns are counted in bytes, like Luau does
+	let map = SourceMap::new("local s = \"héllo\"\nprint(s)");
+
+	assert_eq!(map.position(13), Some(position(0, 13)));
+	assert_eq!(map.slice(Span::new(0, 10, 0, 18)), Some("\"héllo\""));
+	assert_eq!(map.position(19), Some(position(1, 0)));
+
+	// the middle of a character is not a valid place to slice
+	assert_eq!(map.slice(Span::new(0, 12, 0, 13)), None);
+}
+
+#[test]
+fn crlf() {
+	// only \n is a line break, so \r belongs to the line before it
+	let map = SourceMap::new("local a\r\nlocal b\r\n");
+
+	assert_eq!(map.line_count(), 3);
+	assert_eq!(map.line(0), Some("local a\r"));
+	assert_eq!(map.position(9), Some(position(1, 0)));
+	assert_eq!(map.offset(position(0, 8)), Some(8));
+	assert_eq!(map.slice(Span::new(1, 6, 1, 7)), Some("b"));
+}
+
+#[test]
+fn render() {
+	let source = "local a = 1\r\nlocal b = é + 1\r\n";
+
+	// carriage returns are not rendered, and underlines count characters
+	assert_eq!(Span::new(1, 10, 1, 12).render(source, "not a number"), concat!(
+		" --> 2:11\n",
+		"  |\n",
+		"2 | local b = é + 1\n",
+		"  |           ^ not a number\n"
+	));
+
+	let map = SourceMap::new("local function f()\n\tprint(x)\n\n\nend");
+	assert_eq!(map.render_snippet(Span::new(4, 0, 4, 3), Some("here"), &[(Span::new(0, 6, 0, 14), "opened here")]), concat!(
+		" --> 5:1\n",
+		"  |\n",
+		"1 | local function f()\n",
+		"  |       -------- opened here\n",
+		"...\n",
+		"5 | end\n",
+		"  | ^^^ here\n"
+	));
+}
@@ -0,0 +1,69 @@
+// luau-rs - Rust bindings to Roblox's Luau
+// Copyright (C) 2021 LoganDark
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of version 3 of the GNU General Public License as
+// published by the Free Software Foundation.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg(feature = "ast")]
+
+use luau::ast::tree::TypeReference;
+use luau::ast::visit::walk_type_reference;
+use luau::ast::{parse, ParseOptions, Span, Visitor, VisitorMut};
+
+/// Collects the name of every type reference.
+#[derive(Default)]
+struct References(Vec<String>);
+
+impl Visitor for References {
+	fn visit_type_reference(&mut self, _span: Span, ty: &TypeReference) {
+		self.0.push(ty.name.clone());
+		walk_type_reference(self, ty)
+	}
+}
+
+const SOURCE: &str = r#"
+local a: A = 1
+local function f(b: B, ...: C): D
+	for c: E = 1, 2 do end
+	for d: F, e in pairs({}) do end
+	return (b :: G)
+end
+"#;
+
+#[test]
+fn local_annotations() {
+	let ast = parse(SOURCE, &ParseOptions::default()).unwrap();
+
+	let mut references = References::default();
+	references.visit_block(&ast.root);
+	references.0.sort();
+	assert_eq!(references.0, ["A", "B", "C", "D", "E", "F", "G"]);
+}
+
+#[test]
+fn local_annotations_mut() {
+	struct Rename;
+
+	impl VisitorMut for Rename {
+		fn visit_type_reference(&mut self, _span: Span, ty: &mut TypeReference) {
+			ty.name = ty.name.to_lowercase();
+		}
+	}
+
+	let mut ast = parse(SOURCE, &ParseOptions::default()).unwrap();
+	Rename.visit_block(&mut ast.root);
+
+	let mut references = References::default();
+	references.visit_block(&ast.root);
+	references.0.sort();
+	assert_eq!(references.0, ["a", "b", "c", "d", "e", "f", "g"]);
+}